@@ -0,0 +1,1314 @@
+use proc_macro::Delimiter;
+use proc_macro::Group;
+use proc_macro::Ident;
+use proc_macro::Literal;
+use proc_macro::Punct;
+use proc_macro::Spacing;
+use proc_macro::Span;
+use proc_macro::TokenStream;
+use proc_macro::TokenTree;
+
+/// Builds `php_codegen` declarations from inline PHP.
+///
+/// The input must be a single class, interface, trait, enum, function or
+/// method declaration, a `{ ... }` body, or a value expression. `#name`
+/// splices a Rust variable, and `#(#items),*` / `#(#items)*` splice
+/// parameters and methods from an iterator.
+///
+/// Interpolations in a body must be plain identifiers, as each becomes a
+/// template placeholder; anything else is rejected when expanding:
+///
+/// ```compile_fail
+/// let r#type = "int";
+/// let body = php_codegen_macros::php!({ return #r#type; });
+/// ```
+///
+/// A body's arguments are only checked when it renders, such as a name
+/// argument that is not a valid PHP name, so a declaration with body
+/// interpolations expands to a `Result<_, TemplateError>`:
+///
+/// ```
+/// let function = "my-function";
+/// let body = php_codegen_macros::php!({ return #function(); });
+///
+/// assert!(body.is_err());
+/// ```
+///
+/// The input is read as Rust tokens, which limits the PHP it can express:
+/// `'abc'` is not a Rust token, so strings longer than one character must
+/// be double-quoted, `//` and `/* */` comments are dropped, and `\` cannot
+/// appear outside string literals, so qualified names must be written as
+/// strings or interpolated. Bodies are copied as written and are not checked as
+/// PHP.
+#[proc_macro]
+pub fn php(input: TokenStream) -> TokenStream {
+    match Parser::new(input, Span::call_site()).parse() {
+        Ok(code) if code.contains(".render()?") => format!(
+            "(|| -> ::std::result::Result<_, ::php_codegen::template::TemplateError> {{ \
+                ::std::result::Result::Ok({}) \
+            }})()",
+            code
+        )
+        .parse()
+        .expect("php! produced invalid Rust code"),
+        Ok(code) => code.parse().expect("php! produced invalid Rust code"),
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+struct Error {
+    span: Span,
+    message: String,
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    fn new<T: ToString>(span: Span, message: T) -> Self {
+        Self {
+            span,
+            message: message.to_string(),
+        }
+    }
+
+    fn into_compile_error(self) -> TokenStream {
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+
+        let mut arguments = Group::new(
+            Delimiter::Parenthesis,
+            TokenStream::from(TokenTree::Literal(message)),
+        );
+        arguments.set_span(self.span);
+
+        TokenStream::from_iter([
+            TokenTree::Ident(Ident::new("compile_error", self.span)),
+            TokenTree::Punct(bang),
+            TokenTree::Group(arguments),
+        ])
+    }
+}
+
+struct Builder {
+    expression: String,
+    calls: Vec<String>,
+}
+
+impl Builder {
+    fn new<T: ToString>(expression: T) -> Self {
+        Self {
+            expression: expression.to_string(),
+            calls: vec![],
+        }
+    }
+
+    fn call<T: ToString>(&mut self, method: &str, argument: T) {
+        self.calls.push(format!(
+            "__php = __php.{}({});",
+            method,
+            argument.to_string()
+        ));
+    }
+
+    fn each<T: ToString>(&mut self, method: &str, iterator: T) {
+        self.calls.push(format!(
+            "for __item in {} {{ __php = __php.{}(__item); }}",
+            iterator.to_string(),
+            method
+        ));
+    }
+
+    fn build(self) -> String {
+        if self.calls.is_empty() {
+            return self.expression;
+        }
+
+        format!(
+            "{{ let mut __php = {}; {} __php }}",
+            self.expression,
+            self.calls.join(" ")
+        )
+    }
+}
+
+#[derive(Default)]
+struct Preamble {
+    documentation: Vec<String>,
+    attributes: Vec<String>,
+}
+
+impl Preamble {
+    fn apply(&self, builder: &mut Builder) {
+        if !self.documentation.is_empty() {
+            builder.call("document", document(&self.documentation));
+        }
+
+        for attribute in &self.attributes {
+            builder.call("attributes", attribute);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Modifiers {
+    visibility: Option<(Span, &'static str)>,
+    modifiers: Vec<(Span, &'static str)>,
+}
+
+impl Modifiers {
+    fn is_empty(&self) -> bool {
+        self.visibility.is_none() && self.modifiers.is_empty()
+    }
+
+    fn apply(&self, builder: &mut Builder) {
+        if let Some((_, visibility)) = self.visibility {
+            builder.call(visibility, "");
+        }
+
+        for (_, modifier) in &self.modifiers {
+            builder.call(
+                "modifier",
+                format!("::php_codegen::modifiers::Modifier::{}", modifier),
+            );
+        }
+    }
+
+    fn reject_visibility(&self, what: &str) -> Result<()> {
+        match self.visibility {
+            Some((span, _)) => Err(Error::new(
+                span,
+                format!("{} cannot have a visibility modifier", what),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn reject_modifiers(&self, what: &str) -> Result<()> {
+        match self.modifiers.first() {
+            Some((span, _)) => Err(Error::new(*span, format!("{} cannot have modifiers", what))),
+            None => Ok(()),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<TokenTree>,
+    position: usize,
+    end: Span,
+}
+
+impl Parser {
+    fn new(stream: TokenStream, end: Span) -> Self {
+        Self {
+            tokens: stream.into_iter().collect(),
+            position: 0,
+            end,
+        }
+    }
+
+    fn parse(mut self) -> Result<String> {
+        if self.tokens.is_empty() {
+            return Err(Error::new(self.end, "expected a PHP declaration"));
+        }
+
+        if self.tokens.len() == 1 {
+            if let TokenTree::Group(group) = &self.tokens[0] {
+                if group.delimiter() == Delimiter::Brace {
+                    return body(group);
+                }
+            }
+        }
+
+        let start = self.position;
+        let preamble = self.preamble()?;
+        let modifiers = self.modifiers();
+
+        let code = if self.eat_keyword("class") {
+            modifiers.reject_visibility("a class")?;
+            self.class(preamble, modifiers)?
+        } else if self.eat_keyword("interface") {
+            modifiers.reject_visibility("an interface")?;
+            modifiers.reject_modifiers("an interface")?;
+            self.interface(preamble)?
+        } else if self.eat_keyword("trait") {
+            modifiers.reject_visibility("a trait")?;
+            modifiers.reject_modifiers("a trait")?;
+            self.r#trait(preamble)?
+        } else if self.eat_keyword("enum") {
+            modifiers.reject_visibility("an enum")?;
+            modifiers.reject_modifiers("an enum")?;
+            self.r#enum(preamble)?
+        } else if self.eat_keyword("function") {
+            if modifiers.is_empty() {
+                self.function(preamble)?
+            } else {
+                self.method(preamble, modifiers)?
+            }
+        } else {
+            self.position = start;
+            let value = self.value(&[])?;
+
+            return match self.peek() {
+                Some(token) => Err(Error::new(token.span(), "unexpected token after value")),
+                None => Ok(value),
+            };
+        };
+
+        match self.peek() {
+            Some(token) => Err(Error::new(
+                token.span(),
+                "unexpected token after declaration",
+            )),
+            None => Ok(code),
+        }
+    }
+
+    fn class(&mut self, preamble: Preamble, modifiers: Modifiers) -> Result<String> {
+        let mut builder = Builder::new(format!(
+            "::php_codegen::class::Class::new({})",
+            self.name()?
+        ));
+
+        preamble.apply(&mut builder);
+        modifiers.apply(&mut builder);
+
+        if self.eat_keyword("extends") {
+            builder.call("extends", self.name()?);
+        }
+
+        if self.eat_keyword("implements") {
+            loop {
+                builder.call("implements", self.name()?);
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+        }
+
+        let mut members = self.braces("class body")?;
+        while !members.is_done() {
+            members.member(&mut builder, "class")?;
+        }
+
+        Ok(builder.build())
+    }
+
+    fn interface(&mut self, preamble: Preamble) -> Result<String> {
+        let mut builder = Builder::new(format!(
+            "::php_codegen::interface::Interface::new({})",
+            self.name()?
+        ));
+
+        preamble.apply(&mut builder);
+
+        if self.eat_keyword("extends") {
+            builder.call("extends", self.name()?);
+        }
+
+        let mut members = self.braces("interface body")?;
+        while !members.is_done() {
+            members.member(&mut builder, "interface")?;
+        }
+
+        Ok(builder.build())
+    }
+
+    fn r#trait(&mut self, preamble: Preamble) -> Result<String> {
+        let mut builder = Builder::new(format!(
+            "::php_codegen::r#trait::Trait::new({})",
+            self.name()?
+        ));
+
+        preamble.apply(&mut builder);
+
+        let mut members = self.braces("trait body")?;
+        while !members.is_done() {
+            members.member(&mut builder, "trait")?;
+        }
+
+        Ok(builder.build())
+    }
+
+    fn r#enum(&mut self, preamble: Preamble) -> Result<String> {
+        let mut builder = Builder::new(format!(
+            "::php_codegen::r#enum::Enum::new({})",
+            self.name()?
+        ));
+
+        preamble.apply(&mut builder);
+
+        if self.eat_punct(':') {
+            if self.eat_keyword("int") {
+                builder.call("int_backed", "");
+            } else if self.eat_keyword("string") {
+                builder.call("string_backed", "");
+            } else {
+                return Err(self.error("expected `int` or `string` as enum backing type"));
+            }
+        }
+
+        if self.eat_keyword("implements") {
+            loop {
+                builder.call("implements", self.name()?);
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+        }
+
+        let mut members = self.braces("enum body")?;
+        while !members.is_done() {
+            members.member(&mut builder, "enum")?;
+        }
+
+        Ok(builder.build())
+    }
+
+    fn function(&mut self, preamble: Preamble) -> Result<String> {
+        let mut builder = Builder::new(format!(
+            "::php_codegen::function::Function::new({})",
+            self.name()?
+        ));
+
+        preamble.apply(&mut builder);
+        self.signature(&mut builder)?;
+
+        match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                builder.call("body", body(&group)?);
+            }
+            Some(token) => return Err(Error::new(token.span(), "expected a function body")),
+            None => return Err(Error::new(self.end, "expected a function body")),
+        }
+
+        Ok(builder.build())
+    }
+
+    fn method(&mut self, preamble: Preamble, modifiers: Modifiers) -> Result<String> {
+        let mut builder = Builder::new(format!(
+            "::php_codegen::method::Method::new({})",
+            self.name()?
+        ));
+
+        preamble.apply(&mut builder);
+        modifiers.apply(&mut builder);
+        self.signature(&mut builder)?;
+
+        if !self.eat_punct(';') {
+            match self.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                    builder.call("body", body(&group)?);
+                }
+                Some(token) => {
+                    return Err(Error::new(token.span(), "expected a method body or `;`"))
+                }
+                None => return Err(Error::new(self.end, "expected a method body or `;`")),
+            }
+        }
+
+        Ok(builder.build())
+    }
+
+    fn signature(&mut self, builder: &mut Builder) -> Result<()> {
+        let mut parameters = match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                Parser::new(group.stream(), group.span_close())
+            }
+            Some(token) => return Err(Error::new(token.span(), "expected a parameter list")),
+            None => return Err(Error::new(self.end, "expected a parameter list")),
+        };
+
+        while !parameters.is_done() {
+            if let Some(iterator) = parameters.repetition(true)? {
+                builder.each("parameter", iterator);
+            } else if let Some(parameter) = parameters.interpolated_item() {
+                builder.call("parameter", parameter);
+            } else {
+                builder.call("parameter", parameters.parameter()?);
+            }
+
+            if !parameters.is_done() && !parameters.eat_punct(',') {
+                return Err(parameters.error("expected `,` between parameters"));
+            }
+        }
+
+        if self.eat_punct(':') {
+            builder.call("returns", self.data_type()?);
+        }
+
+        Ok(())
+    }
+
+    fn parameter(&mut self) -> Result<String> {
+        let preamble = self.preamble()?;
+        if let Some(documentation) = preamble.documentation.first() {
+            return Err(Error::new(
+                self.span(),
+                format!("parameters cannot be documented: {}", documentation.trim()),
+            ));
+        }
+
+        let modifiers = self.modifiers();
+        let data_type = if self.is_punct('$') || self.is_punct('.') || self.is_punct('&') {
+            None
+        } else {
+            Some(self.data_type()?)
+        };
+
+        let by_reference = self.eat_punct('&');
+        let variadic = self.is_punct('.');
+        if variadic {
+            for _ in 0..3 {
+                if !self.eat_punct('.') {
+                    return Err(self.error("expected `...`"));
+                }
+            }
+        }
+
+        let mut builder = Builder::new(format!(
+            "::php_codegen::parameter::Parameter::new({})",
+            self.variable()?
+        ));
+
+        for attribute in &preamble.attributes {
+            builder.call("attributes", attribute);
+        }
+
+        modifiers.apply(&mut builder);
+
+        if let Some(data_type) = data_type {
+            builder.call("typed", data_type);
+        }
+
+        if by_reference {
+            builder.call("reference", "");
+        }
+
+        if variadic {
+            builder.call("variadic", "");
+        }
+
+        if self.eat_punct('=') {
+            builder.call("default", self.value(&[','])?);
+        }
+
+        Ok(builder.build())
+    }
+
+    fn member(&mut self, builder: &mut Builder, kind: &str) -> Result<()> {
+        if let Some(iterator) = self.repetition(false)? {
+            builder.each("method", iterator);
+
+            return Ok(());
+        }
+
+        if let Some(method) = self.interpolated_item() {
+            builder.call("method", method);
+
+            return Ok(());
+        }
+
+        let preamble = self.preamble()?;
+        let modifiers = self.modifiers();
+
+        if self.eat_keyword("use") {
+            modifiers.reject_visibility("a trait usage")?;
+            modifiers.reject_modifiers("a trait usage")?;
+            if kind == "interface" {
+                return Err(self.error("interfaces cannot use traits"));
+            }
+
+            let mut traits = vec![];
+            loop {
+                traits.push(self.name()?);
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+
+            self.expect_punct(';')?;
+            builder.call("using", format!("::std::vec![{}]", traits.join(", ")));
+        } else if self.eat_keyword("const") {
+            if kind == "interface" {
+                return Err(self.error("interface constants are not supported"));
+            }
+
+            builder.call("constant", self.constant(preamble, modifiers)?);
+        } else if self.eat_keyword("case") {
+            if kind != "enum" {
+                return Err(self.error("only enums can declare cases"));
+            }
+
+            modifiers.reject_visibility("an enum case")?;
+            modifiers.reject_modifiers("an enum case")?;
+
+            let mut case = Builder::new(format!(
+                "::php_codegen::enum_case::EnumCase::new({})",
+                self.name()?
+            ));
+
+            preamble.apply(&mut case);
+            if self.eat_punct('=') {
+                case.call("valued", self.value(&[';'])?);
+            }
+
+            self.expect_punct(';')?;
+            builder.call("case", case.build());
+        } else if self.eat_keyword("function") {
+            builder.call("method", self.method(preamble, modifiers)?);
+        } else {
+            if kind == "interface" || kind == "enum" {
+                return Err(self.error(format!("{}s cannot declare properties", kind)));
+            }
+
+            builder.call("property", self.property(preamble, modifiers)?);
+        }
+
+        Ok(())
+    }
+
+    fn constant(&mut self, preamble: Preamble, modifiers: Modifiers) -> Result<String> {
+        let name = if self.is_punct('#') { 2 } else { 1 };
+        let typed = !self.is_punct_at(name, '=');
+        let data_type = if typed { Some(self.data_type()?) } else { None };
+
+        let mut builder = Builder::new(format!(
+            "::php_codegen::constant::ClassConstant::new({})",
+            self.name()?
+        ));
+
+        preamble.apply(&mut builder);
+        modifiers.apply(&mut builder);
+
+        if let Some(data_type) = data_type {
+            builder.call("typed", data_type);
+        }
+
+        self.expect_punct('=')?;
+        builder.call("valued", self.value(&[';'])?);
+        self.expect_punct(';')?;
+
+        Ok(builder.build())
+    }
+
+    fn property(&mut self, preamble: Preamble, modifiers: Modifiers) -> Result<String> {
+        let data_type = if self.is_punct('$') {
+            None
+        } else {
+            Some(self.data_type()?)
+        };
+
+        let mut builder = Builder::new(format!(
+            "::php_codegen::property::Property::new({})",
+            self.variable()?
+        ));
+
+        preamble.apply(&mut builder);
+        modifiers.apply(&mut builder);
+
+        if let Some(data_type) = data_type {
+            builder.call("typed", data_type);
+        }
+
+        if self.eat_punct('=') {
+            builder.call("default", self.value(&[';'])?);
+        }
+
+        if matches!(self.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace)
+        {
+            return Err(self.error("property hooks are not supported"));
+        }
+
+        self.expect_punct(';')?;
+
+        Ok(builder.build())
+    }
+
+    fn data_type(&mut self) -> Result<String> {
+        if self.eat_punct('?') {
+            return Ok(format!(
+                "::php_codegen::data_type::DataType::Nullable(::std::boxed::Box::new({}))",
+                self.data_type_atom()?
+            ));
+        }
+
+        let first = self.data_type_atom()?;
+        let separator = if self.is_punct('|') {
+            '|'
+        } else if self.is_punct('&') && !self.is_variable_at(1) && !self.is_punct_at(1, '.') {
+            '&'
+        } else {
+            return Ok(first);
+        };
+
+        let mut types = vec![first];
+        while self.eat_punct(separator) {
+            types.push(self.data_type_atom()?);
+        }
+
+        Ok(format!(
+            "::php_codegen::data_type::DataType::{}(::std::vec![{}])",
+            if separator == '|' {
+                "Union"
+            } else {
+                "Intersection"
+            },
+            types.join(", ")
+        ))
+    }
+
+    fn data_type_atom(&mut self) -> Result<String> {
+        match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                let mut inner = Parser::new(group.stream(), group.span_close());
+                let data_type = inner.data_type()?;
+                if let Some(token) = inner.peek() {
+                    return Err(Error::new(token.span(), "unexpected token in type"));
+                }
+
+                Ok(data_type)
+            }
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => match self.next() {
+                Some(TokenTree::Ident(ident)) => Ok(ident.to_string()),
+                _ => Err(Error::new(punct.span(), "expected an interpolated type")),
+            },
+            Some(TokenTree::Ident(ident)) => {
+                let name = ident.to_string();
+                let builtin = match name.to_ascii_lowercase().as_str() {
+                    "void" => "Void",
+                    "null" => "Null",
+                    "true" => "True",
+                    "false" => "False",
+                    "never" => "Never",
+                    "float" => "Float",
+                    "bool" => "Boolean",
+                    "int" => "Integer",
+                    "string" => "String",
+                    "array" => "Array",
+                    "object" => "Object",
+                    "mixed" => "Mixed",
+                    "callable" => "Callable",
+                    "iterable" => "Iterable",
+                    "static" => "StaticReference",
+                    "self" => "SelfReference",
+                    "parent" => "ParentReference",
+                    _ => {
+                        return Ok(format!(
+                            "::php_codegen::data_type::DataType::Named({:?}.into())",
+                            name
+                        ))
+                    }
+                };
+
+                Ok(format!("::php_codegen::data_type::DataType::{}", builtin))
+            }
+            Some(TokenTree::Literal(literal)) => match unquote(&literal.to_string()) {
+                Some(name) => Ok(format!(
+                    "::php_codegen::data_type::DataType::Named({:?}.into())",
+                    name
+                )),
+                None => Err(Error::new(literal.span(), "expected a type")),
+            },
+            Some(token) => Err(Error::new(token.span(), "expected a type")),
+            None => Err(Error::new(self.end, "expected a type")),
+        }
+    }
+
+    fn name(&mut self) -> Result<String> {
+        match self.next() {
+            Some(TokenTree::Ident(ident)) => Ok(format!("{:?}", ident.to_string())),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => match self.next() {
+                Some(TokenTree::Ident(ident)) => {
+                    Ok(format!("::std::string::ToString::to_string(&{})", ident))
+                }
+                _ => Err(Error::new(punct.span(), "expected an interpolated name")),
+            },
+            Some(TokenTree::Literal(literal)) => match unquote(&literal.to_string()) {
+                Some(name) => Ok(format!("{:?}", name)),
+                None => Err(Error::new(literal.span(), "expected a name")),
+            },
+            Some(token) => Err(Error::new(token.span(), "expected a name")),
+            None => Err(Error::new(self.end, "expected a name")),
+        }
+    }
+
+    fn variable(&mut self) -> Result<String> {
+        if !self.eat_punct('$') {
+            return Err(self.error("expected a variable"));
+        }
+
+        self.name()
+    }
+
+    fn value(&mut self, terminators: &[char]) -> Result<String> {
+        let start = self.position;
+        while let Some(token) = self.peek() {
+            if matches!(token, TokenTree::Punct(punct) if terminators.contains(&punct.as_char())) {
+                break;
+            }
+
+            self.position += 1;
+        }
+
+        let tokens = &self.tokens[start..self.position];
+        match tokens {
+            [] => Err(self.error("expected a value")),
+            [TokenTree::Punct(punct), TokenTree::Ident(ident)] if punct.as_char() == '#' => {
+                Ok(ident.to_string())
+            }
+            _ => literal_value(tokens),
+        }
+    }
+
+    fn preamble(&mut self) -> Result<Preamble> {
+        let mut preamble = Preamble::default();
+
+        while self.is_punct('#') {
+            let group = match self.peek_at(1) {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                    group.clone()
+                }
+                _ => break,
+            };
+
+            self.position += 2;
+
+            let tokens = group.stream().into_iter().collect::<Vec<TokenTree>>();
+            if let [TokenTree::Ident(ident), TokenTree::Punct(punct), TokenTree::Literal(literal)] =
+                tokens.as_slice()
+            {
+                if ident.to_string() == "doc" && punct.as_char() == '=' {
+                    if let Some(documentation) = unquote(&literal.to_string()) {
+                        preamble.documentation.push(documentation);
+
+                        continue;
+                    }
+                }
+            }
+
+            preamble.attributes.push(attribute_group(&group)?);
+        }
+
+        Ok(preamble)
+    }
+
+    fn modifiers(&mut self) -> Modifiers {
+        let mut modifiers = Modifiers::default();
+
+        loop {
+            let span = self.span();
+            if self.eat_keyword("public") {
+                modifiers.visibility = Some((span, "public"));
+            } else if self.eat_keyword("protected") {
+                modifiers.visibility = Some((span, "protected"));
+            } else if self.eat_keyword("private") {
+                modifiers.visibility = Some((span, "private"));
+            } else if self.eat_keyword("abstract") {
+                modifiers.modifiers.push((span, "Abstract"));
+            } else if self.eat_keyword("final") {
+                modifiers.modifiers.push((span, "Final"));
+            } else if self.eat_keyword("readonly") {
+                modifiers.modifiers.push((span, "Readonly"));
+            } else if self.eat_keyword("static") {
+                modifiers.modifiers.push((span, "Static"));
+            } else {
+                return modifiers;
+            }
+        }
+    }
+
+    fn repetition(&mut self, separated: bool) -> Result<Option<String>> {
+        if !self.is_punct('#') {
+            return Ok(None);
+        }
+
+        let group = match self.peek_at(1) {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                group.clone()
+            }
+            _ => return Ok(None),
+        };
+
+        let tokens = group.stream().into_iter().collect::<Vec<TokenTree>>();
+        let iterator = match tokens.as_slice() {
+            [TokenTree::Punct(punct), TokenTree::Ident(ident)] if punct.as_char() == '#' => {
+                ident.to_string()
+            }
+            _ => {
+                return Err(Error::new(
+                    group.span(),
+                    "expected a repetition of the form `#(#items)`",
+                ))
+            }
+        };
+
+        self.position += 2;
+        if separated && !self.eat_punct(',') {
+            return Err(self.error("expected `,` in `#(#items),*`"));
+        }
+
+        if !self.eat_punct('*') {
+            return Err(self.error("expected `*` after repetition"));
+        }
+
+        Ok(Some(iterator))
+    }
+
+    fn interpolated_item(&mut self) -> Option<String> {
+        if !self.is_punct('#') {
+            return None;
+        }
+
+        let ident = match self.peek_at(1) {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return None,
+        };
+
+        let ends = !matches!(
+            self.peek_at(2),
+            Some(TokenTree::Punct(punct)) if matches!(punct.as_char(), '$' | '.' | '&' | '|')
+        );
+
+        if !ends {
+            return None;
+        }
+
+        self.position += 2;
+
+        Some(ident)
+    }
+
+    fn braces(&mut self, what: &str) -> Result<Parser> {
+        match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                Ok(Parser::new(group.stream(), group.span_close()))
+            }
+            Some(token) => Err(Error::new(token.span(), format!("expected {}", what))),
+            None => Err(Error::new(self.end, format!("expected {}", what))),
+        }
+    }
+
+    fn peek(&self) -> Option<&TokenTree> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&TokenTree> {
+        self.tokens.get(self.position + offset)
+    }
+
+    fn next(&mut self) -> Option<TokenTree> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+
+        token
+    }
+
+    fn is_done(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    fn span(&self) -> Span {
+        self.peek().map(|token| token.span()).unwrap_or(self.end)
+    }
+
+    fn error<T: ToString>(&self, message: T) -> Error {
+        Error::new(self.span(), message)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(TokenTree::Ident(ident)) if ident.to_string().eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let matched = self.is_keyword(keyword);
+        if matched {
+            self.position += 1;
+        }
+
+        matched
+    }
+
+    fn is_punct(&self, character: char) -> bool {
+        self.is_punct_at(0, character)
+    }
+
+    fn is_punct_at(&self, offset: usize, character: char) -> bool {
+        matches!(self.peek_at(offset), Some(TokenTree::Punct(punct)) if punct.as_char() == character)
+    }
+
+    fn is_variable_at(&self, offset: usize) -> bool {
+        self.is_punct_at(offset, '$')
+    }
+
+    fn eat_punct(&mut self, character: char) -> bool {
+        let matched = self.is_punct(character);
+        if matched {
+            self.position += 1;
+        }
+
+        matched
+    }
+
+    fn expect_punct(&mut self, character: char) -> Result<()> {
+        if self.eat_punct(character) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", character)))
+        }
+    }
+}
+
+fn attribute_group(group: &Group) -> Result<String> {
+    let mut parser = Parser::new(group.stream(), group.span_close());
+    let mut code = String::from("::php_codegen::attribute::AttributeGroup::new()");
+
+    while !parser.is_done() {
+        let name = parser.name()?;
+        let arguments = match parser.peek() {
+            Some(TokenTree::Group(arguments))
+                if arguments.delimiter() == Delimiter::Parenthesis =>
+            {
+                let arguments = arguments.clone();
+                parser.position += 1;
+
+                let tokens = arguments.stream().into_iter().collect::<Vec<TokenTree>>();
+                format!(
+                    "::std::option::Option::Some(::std::string::String::from({:?}))",
+                    source(&tokens, false)?.0
+                )
+            }
+            _ => "::std::option::Option::<::std::string::String>::None".to_string(),
+        };
+
+        code.push_str(&format!(
+            ".add(::std::string::String::from({}), {})",
+            name, arguments
+        ));
+
+        if !parser.is_done() && !parser.eat_punct(',') {
+            return Err(parser.error("expected `,` between attributes"));
+        }
+    }
+
+    Ok(code)
+}
+
+fn document(documentation: &[String]) -> String {
+    let mut lines = documentation
+        .iter()
+        .flat_map(|documentation| documentation.lines())
+        .map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix('*').unwrap_or(line);
+
+            line.trim().to_string()
+        })
+        .collect::<Vec<String>>();
+
+    while lines.first().is_some_and(|line| line.is_empty()) {
+        lines.remove(0);
+    }
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    let mut code = String::from("::php_codegen::comment::Document::new()");
+    for line in lines {
+        if line.is_empty() {
+            code.push_str(".empty_line()");
+        } else {
+            code.push_str(&format!(".text({:?})", line));
+        }
+    }
+
+    code
+}
+
+fn body(group: &Group) -> Result<String> {
+    let tokens = group.stream().into_iter().collect::<Vec<TokenTree>>();
+    let (text, placeholders) = source(&tokens, true)?;
+
+    // The template is rendered at runtime, so check here that it reads back
+    // the placeholders the interpolations were turned into.
+    let found = template_placeholders(&text);
+    if let Some(placeholder) = placeholders
+        .iter()
+        .find(|placeholder| !found.contains(placeholder))
+        .or_else(|| found.iter().find(|found| !placeholders.contains(found)))
+    {
+        return Err(Error::new(
+            group.span(),
+            format!(
+                "`#{}` does not match a placeholder in the body",
+                placeholder
+            ),
+        ));
+    }
+
+    let placeholders_used = !placeholders.is_empty();
+    let mut code = format!("::php_codegen::template::Template::new({:?})", text);
+    for placeholder in placeholders {
+        code.push_str(&format!(
+            ".argument({:?}, ::php_codegen::template::Argument::from({}))",
+            placeholder, placeholder
+        ));
+    }
+
+    // Placeholders were checked above, so only the arguments can fail to
+    // render; without any, rendering cannot fail.
+    if placeholders_used {
+        code.push_str(".render()?");
+    } else {
+        code.push_str(".render().unwrap_or_default()");
+    }
+
+    Ok(code)
+}
+
+/// Returns the placeholders `php_codegen::template::Template` finds in
+/// `text`: `{name}`, but not `{{`.
+fn template_placeholders(text: &str) -> Vec<String> {
+    let mut placeholders = vec![];
+    let mut rest = text;
+
+    while let Some(position) = rest.find('{') {
+        rest = &rest[position..];
+        if let Some(stripped) = rest.strip_prefix("{{") {
+            rest = stripped;
+
+            continue;
+        }
+
+        let length = rest[1..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len() - 1);
+        let placeholder = &rest[1..1 + length];
+        if rest[1 + length..].starts_with('}')
+            && !placeholder.is_empty()
+            && !placeholder.starts_with(|c: char| c.is_ascii_digit())
+        {
+            if !placeholders.iter().any(|found| found == placeholder) {
+                placeholders.push(placeholder.to_string());
+            }
+
+            rest = &rest[length + 2..];
+        } else {
+            rest = &rest[1..];
+        }
+    }
+
+    placeholders
+}
+
+fn literal_value(tokens: &[TokenTree]) -> Result<String> {
+    let (text, _) = source(tokens, false)?;
+    let compact = text.replace(char::is_whitespace, "");
+
+    let value = match compact.to_ascii_lowercase().as_str() {
+        "null" => "Null".to_string(),
+        "true" => "True".to_string(),
+        "false" => "False".to_string(),
+        _ => {
+            if let Ok(integer) = compact.parse::<i64>() {
+                format!("Integer({})", integer)
+            } else if let Some(string) = unquote(text.trim())
+                .filter(|string| text.starts_with('"') && !string.contains(['$', '\\']))
+            {
+                format!("String({:?}.to_string())", string)
+            } else {
+                format!("Literal({:?}.to_string())", text)
+            }
+        }
+    };
+
+    Ok(format!("::php_codegen::literal::Value::{}", value))
+}
+
+struct Piece {
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    text: String,
+}
+
+impl Piece {
+    fn new<T: ToString>(start: Span, end: Span, text: T) -> Self {
+        Self {
+            line: start.line(),
+            column: start.column(),
+            end_line: end.end().line(),
+            end_column: end.end().column(),
+            text: text.to_string(),
+        }
+    }
+}
+
+/// Reconstructs PHP source text from tokens using their original positions.
+///
+/// When `template` is set, braces are escaped and `#name` interpolations
+/// become template placeholders, whose names are returned alongside.
+fn source(tokens: &[TokenTree], template: bool) -> Result<(String, Vec<String>)> {
+    let mut pieces = vec![];
+    let mut placeholders = vec![];
+    collect(tokens, template, &mut pieces, &mut placeholders)?;
+
+    let mut text = String::new();
+    let mut position: Option<(usize, usize)> = None;
+    for piece in pieces {
+        match position {
+            Some((line, column)) if line == piece.line => {
+                text.push_str(&" ".repeat(piece.column.saturating_sub(column)));
+            }
+            Some((line, _)) => {
+                text.push_str(&"\n".repeat(piece.line.saturating_sub(line)));
+                text.push_str(&" ".repeat(piece.column.saturating_sub(1)));
+            }
+            None => text.push_str(&" ".repeat(piece.column.saturating_sub(1))),
+        }
+
+        text.push_str(&piece.text);
+        position = Some((piece.end_line, piece.end_column));
+    }
+
+    let indentation = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let text = text
+        .lines()
+        .map(|line| line.get(indentation..).unwrap_or("").trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    Ok((text, placeholders))
+}
+
+fn collect(
+    tokens: &[TokenTree],
+    template: bool,
+    pieces: &mut Vec<Piece>,
+    placeholders: &mut Vec<String>,
+) -> Result<()> {
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => match tokens.get(index + 1) {
+                Some(TokenTree::Ident(ident)) => {
+                    if !template {
+                        return Err(Error::new(
+                            punct.span(),
+                            "interpolations must make up the whole value",
+                        ));
+                    }
+
+                    let name = ident.to_string();
+                    pieces.push(Piece::new(
+                        punct.span(),
+                        ident.span(),
+                        format!("{{{}}}", name),
+                    ));
+                    if !placeholders.contains(&name) {
+                        placeholders.push(name);
+                    }
+
+                    index += 2;
+
+                    continue;
+                }
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    return Err(Error::new(
+                        group.span(),
+                        "repetitions are not supported here",
+                    ));
+                }
+                _ => pieces.push(Piece::new(punct.span(), punct.span(), "#")),
+            },
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace if template => ("{{", "}"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+
+                pieces.push(Piece::new(group.span_open(), group.span_open(), open));
+                collect(
+                    &group.stream().into_iter().collect::<Vec<TokenTree>>(),
+                    template,
+                    pieces,
+                    placeholders,
+                )?;
+                pieces.push(Piece::new(group.span_close(), group.span_close(), close));
+            }
+            TokenTree::Literal(literal) if template => {
+                pieces.push(Piece::new(
+                    literal.span(),
+                    literal.span(),
+                    literal.to_string().replace('{', "{{"),
+                ));
+            }
+            token => pieces.push(Piece::new(token.span(), token.span(), token)),
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Returns the contents of a Rust string literal, if `literal` is one.
+fn unquote(literal: &str) -> Option<String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = &raw[hashes..raw.len().checked_sub(hashes)?];
+
+        return raw
+            .strip_prefix('"')
+            .and_then(|raw| raw.strip_suffix('"'))
+            .map(|raw| raw.to_string());
+    }
+
+    let mut characters = literal.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut result = String::new();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            result.push(character);
+
+            continue;
+        }
+
+        match characters.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            'u' => {
+                let code = characters
+                    .by_ref()
+                    .skip(1)
+                    .take_while(|c| *c != '}')
+                    .collect::<String>();
+
+                result.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+            '\n' => {
+                while characters.as_str().starts_with(char::is_whitespace) {
+                    characters.next();
+                }
+            }
+            other => result.push(other),
+        }
+    }
+
+    Some(result)
+}
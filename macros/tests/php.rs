@@ -0,0 +1,161 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::literal::Value;
+use php_codegen::method::Method;
+use php_codegen::parameter::Parameter;
+use php_codegen::template::TemplateError;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+use php_codegen_macros::php;
+
+#[test]
+fn test_class_declaration() {
+    let name = "UserRepository";
+    let id = DataType::Integer;
+    let finders = vec![
+        Method::new("findAll").public().returns(DataType::Array),
+        Method::new("count").public().returns(DataType::Integer),
+    ];
+
+    let class = php! {
+        /** Loads users. */
+        #[Repository(entity: User::class)]
+        final class #name extends BaseRepository implements Countable {
+            use Logging;
+
+            public const int PAGE_SIZE = 20;
+
+            private ?string $table = "users";
+
+            public function find(#id $id, array &$seen, bool ...$flags): ?User {
+                if ($id <= 0) {
+                    return null;
+                }
+
+                return $this->load($id);
+            }
+
+            #(#finders)*
+        }
+    };
+
+    assert_eq!(
+        r#"<?php
+
+/**
+ * Loads users.
+ */
+#[Repository(entity: User::class)]
+final class UserRepository extends BaseRepository implements Countable
+{
+    use Logging;
+
+    public const int PAGE_SIZE = 20;
+
+    private null|string $table = "users";
+    public function find(
+        int $id,
+        array &$seen,
+        bool ...$flags,
+    ): null|User {
+        if ($id <= 0) {
+            return null;
+        }
+
+        return $this->load($id);
+    }
+
+    public function findAll(): array;
+
+    public function count(): int;
+}
+"#,
+        File::new().class(class).to_string()
+    );
+}
+
+#[test]
+fn test_function_with_parameter_repetition_and_body_interpolation() -> Result<(), TemplateError> {
+    let parameters = vec![
+        Parameter::new("a").typed(DataType::Integer),
+        Parameter::new("b").typed(DataType::Integer).default(0),
+    ];
+    let operator = "max";
+
+    let function = php! {
+        function sum(#(#parameters),*): int {
+            return #operator($a, $b);
+        }
+    }?;
+
+    assert_eq!(
+        r#"function sum(
+    int $a,
+    int $b = 0,
+): int {
+    return max($a, $b);
+}
+"#,
+        function.generate(Indentation::default(), 0)
+    );
+
+    let operator = "my-max";
+    let error = php! {
+        function sum($a, $b) {
+            return #operator($a, $b);
+        }
+    }
+    .unwrap_err();
+
+    assert!(matches!(error, TemplateError::InvalidName { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_enum_interface_and_values() {
+    let status = php! {
+        enum Status: string implements HasLabel {
+            case Active = "active";
+            case Inactive = "inactive";
+
+            public function label(): string {
+                return ucfirst($this->value);
+            }
+        }
+    };
+
+    let interface = php! {
+        interface HasLabel {
+            public function label(): string;
+        }
+    };
+
+    let value: Value = php!([1, 2, 3]);
+
+    assert_eq!(
+        r#"<?php
+
+enum Status: string implements HasLabel
+{
+    case Active = "active";
+
+    case Inactive = "inactive";
+
+    public function label(): string {
+        return ucfirst($this->value);
+    }
+}
+
+interface HasLabel
+{
+    public function label(): string;
+}
+"#,
+        File::new().r#enum(status).interface(interface).to_string()
+    );
+
+    assert_eq!("[1, 2, 3]", value.generate(Indentation::default(), 0));
+}
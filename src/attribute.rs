@@ -1,9 +1,10 @@
+use crate::name::Name;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AttributeGroup {
-    pub members: Vec<(String, Option<String>)>,
+    pub members: Vec<(Name, Option<String>)>,
 }
 
 impl AttributeGroup {
@@ -11,9 +12,9 @@ impl AttributeGroup {
         Self { members: vec![] }
     }
 
-    pub fn add<T: ToString>(mut self, name: T, arguments: Option<T>) -> Self {
+    pub fn add<T: Into<Name> + ToString>(mut self, name: T, arguments: Option<T>) -> Self {
         self.members.push((
-            name.to_string(),
+            name.into(),
             arguments.map(|arguments| arguments.to_string()),
         ));
 
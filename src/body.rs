@@ -1,10 +1,15 @@
 use std::fmt::Debug;
+use std::rc::Rc;
 
+use crate::style::BracePlacement;
+use crate::style::EmptyBody;
+use crate::style::Style;
 use crate::Generator;
 use crate::Indentation;
 
+#[derive(Clone)]
 pub struct Body {
-    pub factory: Option<Box<dyn Fn(Indentation, usize) -> String>>,
+    pub factory: Option<Rc<dyn Fn(Indentation, usize) -> String>>,
     pub semicolon_for_empty: bool,
 }
 
@@ -18,7 +23,7 @@ impl Body {
 
     pub fn with_factory<T: Fn(Indentation, usize) -> String + 'static>(factory: T) -> Self {
         Self {
-            factory: Some(Box::new(factory)),
+            factory: Some(Rc::new(factory)),
             semicolon_for_empty: true,
         }
     }
@@ -28,6 +33,50 @@ impl Body {
 
         self
     }
+
+    /// Returns the code of the body at `level`, or `None` for a body without
+    /// code.
+    pub fn code(&self, indentation: Indentation, level: usize) -> Option<String> {
+        self.factory
+            .as_ref()
+            .map(|factory| factory(indentation, level))
+    }
+
+    /// Prints the body after a signature, opening it as `braces` says.
+    pub(crate) fn print(
+        &self,
+        braces: BracePlacement,
+        style: &Style,
+        indentation: Indentation,
+        level: usize,
+    ) -> String {
+        let body = self
+            .factory
+            .as_ref()
+            .map(|factory| style.closures(factory(indentation, level + 1)));
+
+        let mut code = String::new();
+        match body {
+            None if self.semicolon_for_empty => code.push(';'),
+            Some(body) if style.empty_body.is_none() || !body.trim().is_empty() => {
+                code.push_str(&style.open(braces, indentation, level));
+                code.push_str(&body);
+                code.push('\n');
+                code.push_str(&indentation.indent("}", level));
+            }
+            _ => match style.empty_body {
+                None | Some(EmptyBody::Collapsed) => code.push_str(" {}"),
+                Some(EmptyBody::Expanded) => {
+                    code.push_str(&style.open(braces, indentation, level));
+                    code.push_str(&indentation.indent("}", level));
+                }
+            },
+        }
+
+        code.push('\n');
+
+        code
+    }
 }
 
 impl Debug for Body {
@@ -41,33 +90,11 @@ impl Debug for Body {
 
 impl Generator for Body {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
-        match self.factory {
-            Some(ref factory) => {
-                let mut code = String::new();
-
-                code.push_str(" {");
-                code.push('\n');
-                code.push_str(&factory(indentation, level + 1));
-                code.push('\n');
-                code.push_str(&indentation.indent("}", level));
-                code.push('\n');
-
-                code
-            }
-            None => {
-                let mut code = String::new();
-
-                if self.semicolon_for_empty {
-                    code.push(';');
-                } else {
-                    code.push_str(" {}");
-                }
-
-                code.push('\n');
+        self.generate_styled(&Style::default(), indentation, level)
+    }
 
-                code
-            }
-        }
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
+        self.print(BracePlacement::SameLine, style, indentation, level)
     }
 }
 
@@ -79,7 +106,7 @@ impl<T: ToString> From<Vec<T>> for Body {
             .collect::<Vec<String>>();
 
         Self {
-            factory: Some(Box::new(move |indentation, level| {
+            factory: Some(Rc::new(move |indentation, level| {
                 let body = body.clone();
 
                 body.iter()
@@ -95,7 +122,7 @@ impl<T: ToString> From<Vec<T>> for Body {
 impl From<String> for Body {
     fn from(body: String) -> Self {
         Self {
-            factory: Some(Box::new(move |indentation, level| {
+            factory: Some(Rc::new(move |indentation, level| {
                 let body = body.clone();
 
                 indentation.indent(body, level)
@@ -114,7 +141,7 @@ impl From<&str> for Body {
 impl<T: Fn(Indentation, usize) -> String + 'static> From<T> for Body {
     fn from(factory: T) -> Self {
         Self {
-            factory: Some(Box::new(factory)),
+            factory: Some(Rc::new(factory)),
             semicolon_for_empty: true,
         }
     }
@@ -123,7 +150,7 @@ impl<T: Fn(Indentation, usize) -> String + 'static> From<T> for Body {
 impl From<Option<Box<dyn Fn(Indentation, usize) -> String>>> for Body {
     fn from(factory: Option<Box<dyn Fn(Indentation, usize) -> String>>) -> Self {
         Self {
-            factory,
+            factory: factory.map(Rc::from),
             semicolon_for_empty: true,
         }
     }
@@ -1,21 +1,30 @@
 use crate::attribute::AttributeGroup;
 use crate::comment::Document;
 use crate::constant::ClassConstant;
+use crate::identifier::Context;
 use crate::method::Method;
+use crate::modifiers;
 use crate::modifiers::Modifier;
+use crate::name::Name;
+use crate::naming::Naming;
+use crate::ordering;
+use crate::parameter;
 use crate::property::Property;
+use crate::style::BracePlacement;
+use crate::style::Style;
 use crate::usage::Usage;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Class {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
     pub modifiers: Vec<Modifier>,
     pub name: String,
-    pub extends: Option<String>,
-    pub implements: Vec<String>,
+    pub original_name: Option<String>,
+    pub extends: Option<Name>,
+    pub implements: Vec<Name>,
     pub usages: Vec<Usage>,
     pub constants: Vec<ClassConstant>,
     pub properties: Vec<Property>,
@@ -29,6 +38,7 @@ impl Class {
             attributes: vec![],
             modifiers: vec![],
             name: name.to_string(),
+            original_name: None,
             extends: None,
             implements: vec![],
             usages: vec![],
@@ -38,6 +48,14 @@ impl Class {
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut class = Self::new(naming.apply(&original, Context::Class));
+        class.original_name = Some(original);
+
+        class
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -56,14 +74,14 @@ impl Class {
         self
     }
 
-    pub fn extends<T: ToString>(mut self, extends: T) -> Self {
-        self.extends = Some(extends.to_string());
+    pub fn extends<T: Into<Name>>(mut self, extends: T) -> Self {
+        self.extends = Some(extends.into());
 
         self
     }
 
-    pub fn implements<T: ToString>(mut self, implements: T) -> Self {
-        self.implements.push(implements.to_string());
+    pub fn implements<T: Into<Name>>(mut self, implements: T) -> Self {
+        self.implements.push(implements.into());
 
         self
     }
@@ -95,6 +113,10 @@ impl Class {
 
 impl Generator for Class {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
         if let Some(documentation) = &self.documentation {
@@ -105,33 +127,40 @@ impl Generator for Class {
             code.push_str(&attribute.generate(indentation, level));
         }
 
-        for modifier in &self.modifiers {
-            code.push_str(&format!("{} ", modifier.generate(indentation, level)));
-        }
-
+        code.push_str(&modifiers::print(None, &self.modifiers));
         code.push_str(&format!("class {}", self.name));
 
         if let Some(extends) = &self.extends {
             code.push_str(&format!(" extends {}", extends));
         }
 
-        if !self.implements.is_empty() {
-            code.push_str(&format!(
-                " implements {}",
-                self.implements
-                    .iter()
-                    .map(|implement| implement.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            ));
-        }
-
-        code.push_str("\n{\n");
-
-        code.push_str(self.usages.generate(indentation, level + 1).as_str());
-        code.push_str(self.constants.generate(indentation, level + 1).as_str());
-        code.push_str(self.properties.generate(indentation, level + 1).as_str());
-        code.push_str(self.methods.generate(indentation, level + 1).as_str());
+        let implements = implements(
+            &self.implements,
+            parameter::width(&code),
+            style,
+            indentation,
+            level,
+        );
+        code.push_str(&implements);
+
+        // A broken `implements` list puts the brace on its own line.
+        let braces = if implements.contains('\n') {
+            BracePlacement::NextLine
+        } else {
+            style.class_braces
+        };
+
+        code.push_str(&style.open(braces, indentation, level));
+
+        code.push_str(&ordering::members(
+            &self.usages,
+            &self.constants,
+            &self.properties,
+            &self.methods,
+            style,
+            indentation,
+            level + 1,
+        ));
 
         code = code.trim_end().to_string();
         code.push_str("\n}\n");
@@ -142,16 +171,58 @@ impl Generator for Class {
 
 impl Generator for Vec<Class> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
         if self.is_empty() {
             return code;
         }
 
         for class in self {
-            code.push_str(class.generate(indentation, level).as_str());
+            code.push_str(class.generate_styled(style, indentation, level).as_str());
             code.push('\n');
         }
 
         code
     }
 }
+
+/// Prints an `implements` clause for a declaration header that is `width`
+/// columns long so far, with one interface per line if it is too long for
+/// the line width.
+pub(crate) fn implements(
+    names: &[Name],
+    width: usize,
+    style: &Style,
+    indentation: Indentation,
+    level: usize,
+) -> String {
+    if names.is_empty() {
+        return String::new();
+    }
+
+    let names = names
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<String>>();
+    let list = names.join(", ");
+    let brace = match style.class_braces {
+        BracePlacement::SameLine => 2,
+        BracePlacement::NextLine => 0,
+    };
+
+    if style.exceeds(width + " implements ".len() + list.chars().count() + brace) {
+        return format!(
+            " implements\n{}",
+            names
+                .iter()
+                .map(|name| indentation.indent(name, level + 1))
+                .collect::<Vec<String>>()
+                .join(",\n")
+        );
+    }
+
+    format!(" implements {}", list)
+}
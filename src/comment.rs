@@ -1,14 +1,15 @@
+use crate::style;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Element {
     Tag(String, String),
     Text(String),
     EmptyLine,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Document {
     pub elements: Vec<Element>,
 }
@@ -66,7 +67,7 @@ impl Generator for Document {
                 continue;
             }
 
-            for line in element.lines() {
+            for line in style::normalize(&element).lines() {
                 code.push_str(&format!(
                     "{} * {}\n",
                     indentation.value(level),
@@ -1,16 +1,20 @@
 use crate::attribute::AttributeGroup;
 use crate::comment::Document;
 use crate::data_type::DataType;
+use crate::identifier::Context;
 use crate::literal::Value;
+use crate::modifiers;
 use crate::modifiers::Modifier;
 use crate::modifiers::VisibilityModifier;
+use crate::naming::Naming;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Constant {
     pub documentation: Option<Document>,
     pub name: String,
+    pub original_name: Option<String>,
     pub value: Value,
 }
 
@@ -19,10 +23,19 @@ impl Constant {
         Self {
             documentation: None,
             name: name.to_string(),
+            original_name: None,
             value: Value::Null,
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut constant = Self::new(naming.apply(&original, Context::Constant));
+        constant.original_name = Some(original);
+
+        constant
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -75,12 +88,13 @@ impl<T: ToString, Tv: Into<Value>> From<(T, Tv)> for Constant {
         Self {
             documentation: None,
             name: name.to_string(),
+            original_name: None,
             value: value.into(),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ClassConstant {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
@@ -88,6 +102,7 @@ pub struct ClassConstant {
     pub modifiers: Vec<Modifier>,
     pub data_type: Option<DataType>,
     pub name: String,
+    pub original_name: Option<String>,
     pub value: Value,
 }
 
@@ -100,10 +115,19 @@ impl ClassConstant {
             modifiers: vec![],
             data_type: None,
             name: name.to_string(),
+            original_name: None,
             value: Value::Null,
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut constant = Self::new(naming.apply(&original, Context::ClassConstant));
+        constant.original_name = Some(original);
+
+        constant
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -173,13 +197,7 @@ impl Generator for ClassConstant {
 
         code.push_str(&indentation.value(level));
 
-        if let Some(visibility) = &self.visibility {
-            code.push_str(&format!("{} ", visibility.generate(indentation, level)));
-        }
-
-        for modifier in &self.modifiers {
-            code.push_str(&format!("{} ", modifier.generate(indentation, level)));
-        }
+        code.push_str(&modifiers::print(self.visibility.as_ref(), &self.modifiers));
 
         if let Some(data_type) = &self.data_type {
             code.push_str(&format!(
@@ -225,6 +243,7 @@ impl<T: ToString, Tv: Into<Value>> From<(T, Tv)> for ClassConstant {
             modifiers: vec![],
             data_type: None,
             name: name.to_string(),
+            original_name: None,
             value: value.into(),
         }
     }
@@ -1,9 +1,11 @@
+use crate::name::Name;
+use crate::style::Style;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum DataType {
-    Named(String),
+    Named(Name),
     Nullable(Box<DataType>),
     Union(Vec<DataType>),
     Intersection(Vec<DataType>),
@@ -26,6 +28,101 @@ pub enum DataType {
     ParentReference,
 }
 
+impl DataType {
+    /// Returns whether every value of `self` is also a value of `other`.
+    /// `extends` reports whether one class extends or implements another.
+    /// `self` and `parent` only match themselves, so resolve them to class
+    /// names first when comparing types declared in different classes.
+    pub fn is_subtype_of(&self, other: &DataType, extends: &dyn Fn(&Name, &Name) -> bool) -> bool {
+        match (self, other) {
+            (DataType::Never, _) => true,
+            (DataType::Nullable(inner), _) => {
+                DataType::Null.is_subtype_of(other, extends) && inner.is_subtype_of(other, extends)
+            }
+            (DataType::Union(types), _) => types.iter().all(|t| t.is_subtype_of(other, extends)),
+            (_, DataType::Intersection(types)) => {
+                types.iter().all(|t| self.is_subtype_of(t, extends))
+            }
+            (_, DataType::Nullable(inner)) => {
+                self.is_subtype_of(&DataType::Null, extends) || self.is_subtype_of(inner, extends)
+            }
+            (_, DataType::Union(types)) => types.iter().any(|t| self.is_subtype_of(t, extends)),
+            (DataType::Intersection(types), _) => {
+                types.iter().any(|t| t.is_subtype_of(other, extends))
+            }
+            (DataType::Void, DataType::Void) => true,
+            (DataType::Void, _) | (_, DataType::Void) => false,
+            (_, DataType::Mixed) => true,
+            (DataType::True | DataType::False, DataType::Boolean) => true,
+            (DataType::Array, DataType::Iterable) => true,
+            (DataType::Named(name), DataType::Named(other)) => {
+                name.matches(other) || extends(name, other)
+            }
+            (DataType::Named(name), DataType::Iterable) => {
+                let traversable = Name::new("\\Traversable");
+
+                name.matches(&traversable) || extends(name, &traversable)
+            }
+            (DataType::Named(name), DataType::Callable) => name.matches(&Name::new("\\Closure")),
+            (
+                DataType::Named(_)
+                | DataType::StaticReference
+                | DataType::SelfReference
+                | DataType::ParentReference,
+                DataType::Object,
+            ) => true,
+            (DataType::StaticReference, DataType::SelfReference) => true,
+            (DataType::Named(_), _) | (_, DataType::Named(_)) => false,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl DataType {
+    /// Returns whether `?` can be put in front of this type.
+    pub(crate) fn nullable(&self) -> bool {
+        !matches!(
+            self,
+            DataType::Nullable(_)
+                | DataType::Union(_)
+                | DataType::Intersection(_)
+                | DataType::Void
+                | DataType::Null
+                | DataType::Never
+                | DataType::Mixed
+        )
+    }
+
+    /// Prints a union with one member per line, each after the first
+    /// starting with `|` at `level + 1`. Other types print as usual.
+    pub(crate) fn print_wrapped(&self, indentation: Indentation, level: usize) -> String {
+        self.members(indentation, level)
+            .join(&format!("\n{}|", indentation.value(level + 1)))
+    }
+
+    fn members(&self, indentation: Indentation, level: usize) -> Vec<String> {
+        match self {
+            DataType::Union(types) => types
+                .iter()
+                .flat_map(|data_type| data_type.members(indentation, level))
+                .collect(),
+            DataType::Nullable(inner) => {
+                let mut members = vec![DataType::Null.generate(indentation, level)];
+                members.extend(inner.members(indentation, level));
+
+                members
+            }
+            _ => vec![self.generate(indentation, level)],
+        }
+    }
+}
+
+impl From<Name> for DataType {
+    fn from(name: Name) -> Self {
+        DataType::Named(name)
+    }
+}
+
 impl Generator for DataType {
     fn generate(&self, _indentation: Indentation, _level: usize) -> String {
         match self {
@@ -62,4 +159,13 @@ impl Generator for DataType {
             DataType::Never => "never".to_string(),
         }
     }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
+        match self {
+            DataType::Nullable(inner) if style.short_nullable && inner.nullable() => {
+                format!("?{}", inner.generate(indentation, level))
+            }
+            _ => self.generate(indentation, level),
+        }
+    }
 }
@@ -0,0 +1,593 @@
+use std::rc::Rc;
+
+use crate::body::Body;
+use crate::class::Class;
+use crate::comment::Document;
+use crate::constant::ClassConstant;
+use crate::data_type::DataType;
+use crate::file::File;
+use crate::lexer::tokenize;
+use crate::lexer::Token;
+use crate::lexer::TokenKind;
+use crate::literal::Value;
+use crate::location::Location;
+use crate::method::Method;
+use crate::modifiers::Modifier;
+use crate::modifiers::VisibilityModifier;
+use crate::parameter::Parameter;
+use crate::property::Property;
+use crate::r#enum::Enum;
+use crate::r#enum::EnumBackingType;
+use crate::style::Style;
+use crate::validation::Diagnostic;
+use crate::version;
+use crate::version::PhpVersion;
+use crate::Generator;
+use crate::Indentation;
+
+/// Rewrites `file` to run on `target`. Enums become final classes, nullable
+/// types print as `?T` before PHP 8.0, and `readonly`, `final` and types the
+/// target cannot declare move into docblocks. Returns errors for constructs with
+/// no equivalent on `target`, which are left unchanged, and warnings for
+/// enum methods that refer to cases as constants.
+pub fn lower(file: &mut File, target: PhpVersion) -> Vec<Diagnostic> {
+    lower_at(file, target, &Location::new().child("File"))
+}
+
+pub(crate) fn lower_at(
+    file: &mut File,
+    target: PhpVersion,
+    location: &Location,
+) -> Vec<Diagnostic> {
+    let mut lowering = Lowering {
+        target,
+        style: Style::new().short_nullable(target < PhpVersion::PHP_8_0),
+        diagnostics: vec![],
+    };
+    lowering.file(file, location);
+
+    if lowering.style.short_nullable {
+        file.style.short_nullable = true;
+    }
+
+    lowering.diagnostics
+}
+
+struct Lowering {
+    target: PhpVersion,
+    /// How lowered types print, which decides the types the target accepts.
+    style: Style,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Lowering {
+    fn unsupported(&mut self, feature: &str, version: PhpVersion, location: &Location) {
+        self.diagnostics.push(Diagnostic::error(
+            "unsupported-feature",
+            location,
+            format!(
+                "{} require PHP {} and cannot be lowered to PHP {}",
+                feature, version, self.target
+            ),
+        ));
+    }
+
+    fn file(&mut self, file: &mut File, location: &Location) {
+        for function in &mut file.functions {
+            self.signature(
+                &mut function.parameters,
+                &mut function.return_type,
+                &mut function.documentation,
+                &mut function.trailing_comma,
+            );
+        }
+
+        if self.target < PhpVersion::PHP_8_1 {
+            for r#enum in &file.enums {
+                self.case_references(r#enum, &location.child(format!("Enum {}", r#enum.name)));
+            }
+
+            for r#enum in std::mem::take(&mut file.enums) {
+                file.classes.push(enum_class(r#enum, self.target));
+            }
+        }
+
+        for class in &mut file.classes {
+            self.class(class, &location.child(format!("Class {}", class.name)));
+        }
+
+        for r#trait in &mut file.traits {
+            let location = location.child(format!("Trait {}", r#trait.name));
+            if self.target < PhpVersion::PHP_8_2 {
+                for constant in &r#trait.constants {
+                    self.unsupported(
+                        "constants in traits",
+                        PhpVersion::PHP_8_2,
+                        &location.child(format!("constant {}", constant.name)),
+                    );
+                }
+            }
+
+            self.methods(&mut r#trait.methods, &mut r#trait.properties);
+            self.properties(&mut r#trait.properties, &location);
+            self.constants(&mut r#trait.constants, &location);
+        }
+
+        for r#enum in &mut file.enums {
+            let location = location.child(format!("Enum {}", r#enum.name));
+            self.methods(&mut r#enum.methods, &mut vec![]);
+            self.constants(&mut r#enum.constants, &location);
+        }
+
+        for interface in &mut file.interfaces {
+            self.methods(&mut interface.methods, &mut vec![]);
+        }
+    }
+
+    /// Warns about `self::Case` in enum methods, which refers to the case's
+    /// value once the enum is a class. The case itself is `self::Case()`.
+    fn case_references(&mut self, r#enum: &Enum, location: &Location) {
+        for method in &r#enum.methods {
+            let Ok(tokens) = tokenize(&method.generate(Indentation::default(), 0)) else {
+                continue;
+            };
+
+            let tokens = tokens
+                .iter()
+                .filter(|token| {
+                    !matches!(
+                        token.kind,
+                        TokenKind::Whitespace | TokenKind::Comment | TokenKind::DocComment
+                    )
+                })
+                .collect::<Vec<&Token>>();
+
+            for window in tokens.windows(4) {
+                let [class, separator, case, next] = window else {
+                    continue;
+                };
+
+                let scoped = class.kind == TokenKind::Name
+                    && (["self", "static"]
+                        .iter()
+                        .any(|name| class.text.eq_ignore_ascii_case(name))
+                        || class.text == r#enum.name);
+                if scoped
+                    && separator.text == "::"
+                    && next.text != "("
+                    && r#enum.cases.iter().any(|c| c.name == case.text)
+                {
+                    self.diagnostics.push(Diagnostic::warning(
+                        "unsupported-feature",
+                        &location.child(format!("method {}", method.name)),
+                        format!(
+                            "`{0}::{1}` refers to the value of case {1} once lowered to PHP {2}, use `{0}::{1}()` for the case",
+                            class.text, case.text, self.target
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn class(&mut self, class: &mut Class, location: &Location) {
+        if self.target < PhpVersion::PHP_8_2 && class.modifiers.contains(&Modifier::Readonly) {
+            class
+                .modifiers
+                .retain(|modifier| *modifier != Modifier::Readonly);
+
+            let properties = class
+                .properties
+                .iter_mut()
+                .map(|property| &mut property.modifiers);
+            let promoted = class
+                .methods
+                .iter_mut()
+                .filter(|method| method.name.eq_ignore_ascii_case("__construct"))
+                .flat_map(|method| &mut method.parameters)
+                .filter(|parameter| parameter.visibility.is_some())
+                .map(|parameter| &mut parameter.modifiers);
+
+            for modifiers in properties.chain(promoted) {
+                if !modifiers.contains(&Modifier::Readonly) {
+                    modifiers.push(Modifier::Readonly);
+                }
+            }
+        }
+
+        // Methods come first, as lowering promoted parameters adds properties.
+        self.methods(&mut class.methods, &mut class.properties);
+        self.properties(&mut class.properties, location);
+        self.constants(&mut class.constants, location);
+    }
+
+    fn methods(&mut self, methods: &mut [Method], properties: &mut Vec<Property>) {
+        for method in methods {
+            if method.name.eq_ignore_ascii_case("__construct") {
+                self.demote(method, properties);
+            }
+
+            self.signature(
+                &mut method.parameters,
+                &mut method.return_type,
+                &mut method.documentation,
+                &mut method.trailing_comma,
+            );
+        }
+    }
+
+    /// Turns promoted constructor parameters the target cannot declare into
+    /// properties assigned at the start of the constructor.
+    fn demote(&mut self, constructor: &mut Method, properties: &mut Vec<Property>) {
+        let mut assigned = vec![];
+
+        for parameter in &mut constructor.parameters {
+            let promoted = parameter.visibility.is_some() || !parameter.modifiers.is_empty();
+            let readonly = parameter.modifiers.contains(&Modifier::Readonly);
+            if !promoted
+                || (self.target >= PhpVersion::PHP_8_0
+                    && (!readonly || self.target >= PhpVersion::PHP_8_1))
+            {
+                continue;
+            }
+
+            let mut property = Property::new(&parameter.name);
+            property.visibility = Some(
+                parameter
+                    .visibility
+                    .take()
+                    .unwrap_or(VisibilityModifier::Public),
+            );
+            property.modifiers = std::mem::take(&mut parameter.modifiers);
+            property.data_type = parameter.data_type.clone();
+            properties.push(property);
+
+            assigned.push(format!(
+                "$this->{} = {}${};",
+                parameter.name,
+                if parameter.by_reference { "&" } else { "" },
+                parameter.name
+            ));
+        }
+
+        if !assigned.is_empty() {
+            let factory = constructor.body.factory.clone();
+            constructor.body.factory = Some(Rc::new(move |indentation, level| {
+                let mut code = assigned
+                    .iter()
+                    .map(|line| indentation.indent(line, level))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+
+                if let Some(factory) = &factory {
+                    let body = factory(indentation, level);
+                    if !body.trim().is_empty() {
+                        code.push_str("\n\n");
+                        code.push_str(&body);
+                    }
+                }
+
+                code
+            }));
+        }
+    }
+
+    fn signature(
+        &mut self,
+        parameters: &mut [Parameter],
+        return_type: &mut Option<DataType>,
+        documentation: &mut Option<Document>,
+        trailing_comma: &mut bool,
+    ) {
+        if self.target < PhpVersion::PHP_8_0 {
+            *trailing_comma = false;
+        }
+
+        let mut tags = vec![];
+        for parameter in parameters {
+            self.shorten(&mut parameter.data_type);
+            if let Some(data_type) = &parameter.data_type {
+                if version::type_version(data_type, true, &self.style) > self.target {
+                    tags.push((
+                        "param",
+                        format!(
+                            "{} {}${}",
+                            print(data_type),
+                            if parameter.variadic { "..." } else { "" },
+                            parameter.name
+                        ),
+                    ));
+                    parameter.data_type = self.native(data_type, false);
+                }
+            }
+        }
+
+        self.shorten(return_type);
+        if let Some(data_type) = return_type {
+            if version::type_version(data_type, true, &self.style) > self.target {
+                tags.push(("return", print(data_type)));
+                *return_type = self.native(data_type, true);
+            }
+        }
+
+        document(documentation, tags);
+    }
+
+    fn properties(&mut self, properties: &mut [Property], location: &Location) {
+        for property in properties {
+            let location = location.child(format!("property ${}", property.name));
+            if !property.hooks.is_empty() && self.target < PhpVersion::PHP_8_4 {
+                self.unsupported("property hooks", PhpVersion::PHP_8_4, &location);
+            }
+
+            if self.target < PhpVersion::PHP_8_4 && property.modifiers.contains(&Modifier::Abstract)
+            {
+                self.unsupported("abstract properties", PhpVersion::PHP_8_4, &location);
+            }
+
+            let mut tags = vec![];
+            if self.target < PhpVersion::PHP_8_4 && property.modifiers.contains(&Modifier::Final) {
+                property
+                    .modifiers
+                    .retain(|modifier| *modifier != Modifier::Final);
+                tags.push(("final", String::new()));
+            }
+
+            if self.target < PhpVersion::PHP_8_1 && property.modifiers.contains(&Modifier::Readonly)
+            {
+                property
+                    .modifiers
+                    .retain(|modifier| *modifier != Modifier::Readonly);
+                property.visibility = Some(VisibilityModifier::Private);
+                tags.push(("readonly", String::new()));
+            }
+
+            self.shorten(&mut property.data_type);
+            if let Some(data_type) = &property.data_type {
+                if self.target < PhpVersion::PHP_7_4 {
+                    tags.push(("var", print(data_type)));
+                    property.data_type = None;
+                } else if version::type_version(data_type, true, &self.style) > self.target {
+                    tags.push(("var", print(data_type)));
+                    property.data_type = self.native(data_type, false);
+                }
+            }
+
+            document(&mut property.documentation, tags);
+        }
+    }
+
+    fn constants(&mut self, constants: &mut [ClassConstant], location: &Location) {
+        for constant in constants {
+            let location = location.child(format!("constant {}", constant.name));
+
+            let mut tags = vec![];
+            if self.target < PhpVersion::PHP_8_1 && constant.modifiers.contains(&Modifier::Final) {
+                constant
+                    .modifiers
+                    .retain(|modifier| *modifier != Modifier::Final);
+                tags.push(("final", String::new()));
+            }
+
+            if self.target < PhpVersion::PHP_8_3 {
+                if let Some(data_type) = constant.data_type.take() {
+                    tags.push(("var", print(&data_type)));
+                }
+            }
+
+            if self.target < PhpVersion::PHP_7_1 {
+                match constant.visibility {
+                    Some(VisibilityModifier::Public) => constant.visibility = None,
+                    Some(_) => self.unsupported(
+                        "private and protected class constants",
+                        PhpVersion::PHP_7_1,
+                        &location,
+                    ),
+                    None => {}
+                }
+            }
+
+            document(&mut constant.documentation, tags);
+        }
+    }
+
+    /// Turns `null|T` into `?T` where the target has no union types.
+    fn shorten(&self, data_type: &mut Option<DataType>) {
+        if !self.style.short_nullable {
+            return;
+        }
+
+        if let Some(DataType::Union(types)) = data_type {
+            if let [DataType::Null, other] | [other, DataType::Null] = types.as_slice() {
+                if other.nullable() {
+                    *data_type = Some(DataType::Nullable(Box::new(other.clone())));
+                }
+            }
+        }
+    }
+
+    /// The type to declare in place of one the target cannot declare, if
+    /// any. The full type is kept in the docblock.
+    fn native(&self, data_type: &DataType, returned: bool) -> Option<DataType> {
+        match data_type {
+            DataType::StaticReference if returned => Some(DataType::SelfReference),
+            _ => None,
+        }
+    }
+}
+
+/// Builds the final class that stands in for `r#enum` on PHP 8.0 and older.
+/// Each case becomes a constant holding its value, or its name for pure
+/// enums, and a static factory returning a shared instance. Enum methods
+/// are kept as written, so `self::Case` in them now means the constant.
+fn enum_class(r#enum: Enum, target: PhpVersion) -> Class {
+    let value_type = r#enum.backing_type.map(|backing_type| match backing_type {
+        EnumBackingType::Int => DataType::Integer,
+        EnumBackingType::String => DataType::String,
+    });
+
+    let mut class = Class::new(&r#enum.name).modifier(Modifier::Final);
+    class.documentation = r#enum.documentation;
+    class.attributes = r#enum.attributes;
+    class.original_name = r#enum.original_name;
+    class.implements = r#enum.implements;
+    class.usages = r#enum.usages;
+
+    for case in &r#enum.cases {
+        let mut constant = ClassConstant::new(&case.name).public().valued(
+            case.value
+                .clone()
+                .unwrap_or(Value::String(case.name.clone())),
+        );
+        constant.documentation = case.documentation.clone();
+        constant.attributes = case.attributes.clone();
+
+        class.constants.push(constant);
+    }
+
+    class.constants.extend(r#enum.constants);
+
+    let readonly = || Document::new().simple_tag("readonly");
+    class = class.property(
+        Property::new("name")
+            .public()
+            .typed(DataType::String)
+            .document(readonly()),
+    );
+
+    let mut constructor = Method::new("__construct")
+        .private()
+        .parameter(Parameter::new("name").typed(DataType::String));
+    let mut assignments = vec!["$this->name = $name;"];
+
+    if let Some(value_type) = &value_type {
+        class = class.property(
+            Property::new("value")
+                .public()
+                .typed(value_type.clone())
+                .document(readonly()),
+        );
+
+        constructor = constructor.parameter(Parameter::new("value").typed(value_type.clone()));
+        assignments.push("$this->value = $value;");
+    }
+
+    class = class.property(
+        Property::new("instances")
+            .private()
+            .modifier(Modifier::Static)
+            .typed(DataType::Array)
+            .default(Value::List(vec![]))
+            .document(Document::new().tag("var", "array<string, self>")),
+    );
+
+    class = class.method(constructor.body(assignments));
+
+    for case in &r#enum.cases {
+        let value = match value_type {
+            Some(_) => format!(", self::{}", case.name),
+            None => String::new(),
+        };
+
+        class = class.method(
+            Method::new(&case.name)
+                .public()
+                .modifier(Modifier::Static)
+                .returns(DataType::SelfReference)
+                .body(format!(
+                    "return self::$instances['{0}'] ?? (self::$instances['{0}'] = new self('{0}'{1}));",
+                    case.name, value
+                )),
+        );
+    }
+
+    class = class.method(
+        Method::new("cases")
+            .document(Document::new().tag("return", "list<self>"))
+            .public()
+            .modifier(Modifier::Static)
+            .returns(DataType::Array)
+            .body(format!(
+                "return [{}];",
+                r#enum
+                    .cases
+                    .iter()
+                    .map(|case| format!("self::{}()", case.name))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+    );
+
+    if let Some(value_type) = value_type {
+        let exception = if target < PhpVersion::PHP_8_0 {
+            "\\InvalidArgumentException"
+        } else {
+            "\\ValueError"
+        };
+
+        class = class.method(
+            Method::new("from")
+                .public()
+                .modifier(Modifier::Static)
+                .parameter(Parameter::new("value").typed(value_type.clone()))
+                .returns(DataType::SelfReference)
+                .body(block(vec![
+                    (0, "$case = self::tryFrom($value);".to_string()),
+                    (0, "if ($case === null) {".to_string()),
+                    (1, format!("throw new {}(sprintf('%s is not a valid backing value for enum %s', var_export($value, true), self::class));", exception)),
+                    (0, "}".to_string()),
+                    (0, String::new()),
+                    (0, "return $case;".to_string()),
+                ])),
+        );
+
+        class = class.method(
+            Method::new("tryFrom")
+                .public()
+                .modifier(Modifier::Static)
+                .parameter(Parameter::new("value").typed(value_type))
+                .returns(DataType::Nullable(Box::new(DataType::SelfReference)))
+                .body(block(vec![
+                    (0, "foreach (self::cases() as $case) {".to_string()),
+                    (1, "if ($case->value === $value) {".to_string()),
+                    (2, "return $case;".to_string()),
+                    (1, "}".to_string()),
+                    (0, "}".to_string()),
+                    (0, String::new()),
+                    (0, "return null;".to_string()),
+                ])),
+        );
+    }
+
+    class.methods.extend(r#enum.methods);
+
+    class
+}
+
+/// A body of lines, each nested `depth` levels below the body.
+fn block(lines: Vec<(usize, String)>) -> Body {
+    Body::with_factory(move |indentation, level| {
+        lines
+            .iter()
+            .map(|(depth, line)| indentation.indent(line, level + depth))
+            .collect::<Vec<String>>()
+            .join("\n")
+    })
+}
+
+fn document(documentation: &mut Option<Document>, tags: Vec<(&str, String)>) {
+    if tags.is_empty() {
+        return;
+    }
+
+    let mut document = documentation.take().unwrap_or_default();
+    for (tag, description) in tags {
+        document = document.tag(tag.to_string(), description);
+    }
+
+    *documentation = Some(document);
+}
+
+fn print(data_type: &DataType) -> String {
+    data_type.generate(Indentation::default(), 0)
+}
@@ -1,25 +1,58 @@
+use std::fmt::Display;
+
 use crate::attribute::AttributeGroup;
+use crate::class;
 use crate::comment::Document;
 use crate::constant::ClassConstant;
 use crate::enum_case::EnumCase;
+use crate::identifier::Context;
+use crate::literal::Value;
 use crate::method::Method;
+use crate::name::Name;
+use crate::naming::Naming;
+use crate::ordering;
+use crate::parameter;
+use crate::style::BracePlacement;
+use crate::style::Style;
 use crate::usage::Usage;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum EnumBackingType {
     Int,
     String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EnumError {
+    Empty {
+        name: String,
+    },
+    UnsupportedValue {
+        case: String,
+    },
+    MismatchedValue {
+        case: String,
+        backing_type: EnumBackingType,
+    },
+    DuplicateCase {
+        case: String,
+    },
+    DuplicateValue {
+        case: String,
+        other: String,
+    },
+}
+
+#[derive(Debug, Clone)]
 pub struct Enum {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
     pub name: String,
+    pub original_name: Option<String>,
     pub backing_type: Option<EnumBackingType>,
-    pub implements: Vec<String>,
+    pub implements: Vec<Name>,
     pub usages: Vec<Usage>,
     pub constants: Vec<ClassConstant>,
     pub cases: Vec<EnumCase>,
@@ -32,6 +65,7 @@ impl Enum {
             documentation: None,
             attributes: vec![],
             name: name.to_string(),
+            original_name: None,
             backing_type: None,
             implements: vec![],
             usages: vec![],
@@ -41,6 +75,68 @@ impl Enum {
         }
     }
 
+    /// Creates a backed enum from `(name, value)` pairs, inferring the backing
+    /// type from the first value. Every value must be of that type, and
+    /// names and values must be unique.
+    pub fn backed_from<T: ToString, N: ToString, V: Into<Value>>(
+        name: T,
+        cases: Vec<(N, V)>,
+    ) -> Result<Self, EnumError> {
+        let mut r#enum = Self::new(name);
+
+        for (case, value) in cases {
+            let case = EnumCase::new(case).valued(value);
+            let value = case.value.as_ref().unwrap();
+
+            let Some(backing_type) = EnumBackingType::of(value) else {
+                return Err(EnumError::UnsupportedValue { case: case.name });
+            };
+
+            match r#enum.backing_type {
+                Some(expected) if expected != backing_type => {
+                    return Err(EnumError::MismatchedValue {
+                        case: case.name,
+                        backing_type: expected,
+                    });
+                }
+                _ => r#enum.backing_type = Some(backing_type),
+            }
+
+            for other in &r#enum.cases {
+                if other.name == case.name {
+                    return Err(EnumError::DuplicateCase { case: case.name });
+                }
+
+                if other
+                    .value
+                    .as_ref()
+                    .is_some_and(|other| same_value(other, value))
+                {
+                    return Err(EnumError::DuplicateValue {
+                        case: case.name,
+                        other: other.name.clone(),
+                    });
+                }
+            }
+
+            r#enum.cases.push(case);
+        }
+
+        if r#enum.cases.is_empty() {
+            return Err(EnumError::Empty { name: r#enum.name });
+        }
+
+        Ok(r#enum)
+    }
+
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut r#enum = Self::new(naming.apply(&original, Context::Class));
+        r#enum.original_name = Some(original);
+
+        r#enum
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -53,8 +149,8 @@ impl Enum {
         self
     }
 
-    pub fn implements<T: ToString>(mut self, implements: T) -> Self {
-        self.implements.push(implements.to_string());
+    pub fn implements<T: Into<Name>>(mut self, implements: T) -> Self {
+        self.implements.push(implements.into());
 
         self
     }
@@ -104,6 +200,10 @@ impl Enum {
 
 impl Generator for Enum {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
         if let Some(documentation) = &self.documentation {
@@ -117,32 +217,35 @@ impl Generator for Enum {
         code.push_str(&format!("enum {}", self.name));
 
         if let Some(backing_type) = &self.backing_type {
-            code.push_str(&format!(
-                ": {}",
-                match backing_type {
-                    EnumBackingType::Int => "int",
-                    EnumBackingType::String => "string",
-                }
-            ));
+            code.push_str(&format!(": {}", backing_type));
         }
 
-        if !self.implements.is_empty() {
-            code.push_str(&format!(
-                " implements {}",
-                self.implements
-                    .iter()
-                    .map(|implement| implement.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            ));
-        }
-
-        code.push_str("\n{\n");
-
-        code.push_str(self.usages.generate(indentation, level + 1).as_str());
-        code.push_str(self.constants.generate(indentation, level + 1).as_str());
-        code.push_str(self.cases.generate(indentation, level + 1).as_str());
-        code.push_str(self.methods.generate(indentation, level + 1).as_str());
+        let implements = class::implements(
+            &self.implements,
+            parameter::width(&code),
+            style,
+            indentation,
+            level,
+        );
+        code.push_str(&implements);
+
+        let braces = if implements.contains('\n') {
+            BracePlacement::NextLine
+        } else {
+            style.class_braces
+        };
+
+        code.push_str(&style.open(braces, indentation, level));
+
+        code.push_str(&ordering::enum_members(
+            &self.usages,
+            &self.constants,
+            &self.cases,
+            &self.methods,
+            style,
+            indentation,
+            level + 1,
+        ));
 
         code = code.trim_end().to_string();
         code.push_str("\n}\n");
@@ -151,15 +254,77 @@ impl Generator for Enum {
     }
 }
 
+impl EnumBackingType {
+    /// Returns the backing type a case value belongs to, if it can back an
+    /// enum at all.
+    pub fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(_) => Some(EnumBackingType::Int),
+            Value::String(_) => Some(EnumBackingType::String),
+            _ => None,
+        }
+    }
+}
+
+impl Display for EnumBackingType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnumBackingType::Int => write!(f, "int"),
+            EnumBackingType::String => write!(f, "string"),
+        }
+    }
+}
+
+impl Display for EnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnumError::Empty { name } => write!(
+                f,
+                "cannot infer the backing type of enum `{}` without cases",
+                name
+            ),
+            EnumError::UnsupportedValue { case } => {
+                write!(f, "case `{}` must have an int or string value", case)
+            }
+            EnumError::MismatchedValue { case, backing_type } => write!(
+                f,
+                "case `{}` must have a value of type `{}`, like the cases before it",
+                case, backing_type
+            ),
+            EnumError::DuplicateCase { case } => {
+                write!(f, "case `{}` is declared more than once", case)
+            }
+            EnumError::DuplicateValue { case, other } => {
+                write!(f, "case `{}` has the same value as case `{}`", case, other)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnumError {}
+
+/// Returns whether two case values back the same case.
+pub(crate) fn same_value(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Integer(a), Value::Integer(b)) => a == b,
+        (Value::String(a), Value::String(b)) => a == b,
+        _ => false,
+    }
+}
+
 impl Generator for Vec<Enum> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
         if self.is_empty() {
             return code;
         }
 
         for r#enum in self {
-            code.push_str(r#enum.generate(indentation, level).as_str());
+            code.push_str(r#enum.generate_styled(style, indentation, level).as_str());
             code.push('\n');
         }
 
@@ -1,14 +1,17 @@
 use crate::attribute::AttributeGroup;
 use crate::comment::Document;
+use crate::identifier::Context;
 use crate::literal::Value;
+use crate::naming::Naming;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EnumCase {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
     pub name: String,
+    pub original_name: Option<String>,
     pub value: Option<Value>,
 }
 
@@ -18,10 +21,19 @@ impl EnumCase {
             documentation: None,
             attributes: vec![],
             name: name.to_string(),
+            original_name: None,
             value: None,
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut case = Self::new(naming.apply(&original, Context::EnumCase));
+        case.original_name = Some(original);
+
+        case
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -87,6 +99,7 @@ impl<T: ToString, Tv: Into<Value>> From<(T, Tv)> for EnumCase {
             documentation: None,
             attributes: vec![],
             name: name.to_string(),
+            original_name: None,
             value: Some(value.into()),
         }
     }
@@ -98,6 +111,7 @@ impl From<String> for EnumCase {
             documentation: None,
             attributes: vec![],
             name,
+            original_name: None,
             value: None,
         }
     }
@@ -109,6 +123,7 @@ impl From<&str> for EnumCase {
             documentation: None,
             attributes: vec![],
             name: name.to_string(),
+            original_name: None,
             value: None,
         }
     }
@@ -2,27 +2,43 @@ use std::fmt::Display;
 
 use crate::class::Class;
 use crate::constant::Constant;
+use crate::downlevel;
 use crate::function::Function;
+use crate::import;
+use crate::import::Import;
+use crate::import::ImportOrder;
 use crate::interface::Interface;
 use crate::literal::Value;
+use crate::name::Name;
+use crate::name::Scope;
 use crate::r#enum::Enum;
 use crate::r#trait::Trait;
+use crate::style::Style;
+use crate::version::PhpVersion;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct File {
-    pub namespace: Option<String>,
+    pub namespace: Option<Name>,
     pub declares: Vec<(String, Value)>,
-    pub uses: Vec<String>,
-    pub constant_uses: Vec<String>,
-    pub function_uses: Vec<String>,
+    pub uses: Vec<Import>,
+    pub constant_uses: Vec<Import>,
+    pub function_uses: Vec<Import>,
     pub functions: Vec<Function>,
     pub constants: Vec<Constant>,
     pub classes: Vec<Class>,
     pub traits: Vec<Trait>,
     pub enums: Vec<Enum>,
     pub interfaces: Vec<Interface>,
+    pub auto_import: bool,
+    pub alias_conflicts: bool,
+    pub import_order: ImportOrder,
+    pub deduplicate_imports: bool,
+    pub prune_imports: bool,
+    pub group_imports: Option<usize>,
+    pub target: Option<PhpVersion>,
+    pub style: Style,
 }
 
 impl File {
@@ -39,6 +55,14 @@ impl File {
             traits: vec![],
             enums: vec![],
             interfaces: vec![],
+            auto_import: false,
+            alias_conflicts: false,
+            import_order: ImportOrder::Insertion,
+            deduplicate_imports: false,
+            prune_imports: false,
+            group_imports: None,
+            target: None,
+            style: Style::default(),
         }
     }
 
@@ -48,26 +72,85 @@ impl File {
         self
     }
 
-    pub fn namespaced<T: ToString>(mut self, namespace: T) -> Self {
-        self.namespace = Some(namespace.to_string());
+    pub fn namespaced<T: Into<Name>>(mut self, namespace: T) -> Self {
+        self.namespace = Some(namespace.into());
 
         self
     }
 
-    pub fn uses<T: ToString>(mut self, symbol: T) -> Self {
-        self.uses.push(symbol.to_string());
+    pub fn uses<T: Into<Import>>(mut self, symbol: T) -> Self {
+        self.uses.push(symbol.into());
 
         self
     }
 
-    pub fn uses_constant<T: ToString>(mut self, constant: T) -> Self {
-        self.constant_uses.push(constant.to_string());
+    pub fn uses_constant<T: Into<Import>>(mut self, constant: T) -> Self {
+        self.constant_uses.push(constant.into());
 
         self
     }
 
-    pub fn uses_function<T: ToString>(mut self, function: T) -> Self {
-        self.function_uses.push(function.to_string());
+    pub fn uses_function<T: Into<Import>>(mut self, function: T) -> Self {
+        self.function_uses.push(function.into());
+
+        self
+    }
+
+    /// Imports fully qualified names used in the file at generation time,
+    /// printing them by their short names.
+    pub fn auto_import(mut self) -> Self {
+        self.auto_import = true;
+
+        self
+    }
+
+    /// Aliases imports whose short names clash with another import or with a
+    /// symbol declared in the file, instead of leaving them as they are.
+    pub fn alias_conflicts(mut self) -> Self {
+        self.alias_conflicts = true;
+
+        self
+    }
+
+    pub fn sort_imports(mut self, order: ImportOrder) -> Self {
+        self.import_order = order;
+
+        self
+    }
+
+    pub fn deduplicate_imports(mut self) -> Self {
+        self.deduplicate_imports = true;
+
+        self
+    }
+
+    /// Removes imports that nothing in the file refers to.
+    pub fn prune_imports(mut self) -> Self {
+        self.prune_imports = true;
+
+        self
+    }
+
+    /// Prints imports sharing a namespace as a group use statement, such as
+    /// `use App\Model\{User, Post};`, once there are `threshold` of them.
+    pub fn group_imports(mut self, threshold: usize) -> Self {
+        self.group_imports = Some(threshold);
+
+        self
+    }
+
+    /// Lowers constructs newer than `version` when generating, see
+    /// [`downlevel::lower`]. Constructs that cannot be lowered are printed
+    /// as they are; `validate` and `try_generate` report them.
+    pub fn target(mut self, version: PhpVersion) -> Self {
+        self.target = Some(version);
+
+        self
+    }
+
+    /// Lays out the generated code in `style`.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
 
         self
     }
@@ -107,18 +190,68 @@ impl File {
 
         self
     }
+
+    /// Returns the namespace and imports that names in this file resolve against.
+    pub fn scope(&self) -> Scope {
+        let mut scope = Scope::new(self.namespace.clone());
+
+        for import in &self.uses {
+            scope = scope.import(&import.name, import.alias.as_deref());
+        }
+
+        for import in &self.function_uses {
+            scope = scope.import_function(&import.name, import.alias.as_deref());
+        }
+
+        for import in &self.constant_uses {
+            scope = scope.import_constant(&import.name, import.alias.as_deref());
+        }
+
+        scope
+    }
 }
 
 impl Generator for File {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&self.style, indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
+        if let Some(target) = self.target {
+            let mut file = self.clone();
+            file.target = None;
+            downlevel::lower(&mut file, target);
+
+            let style = Style {
+                short_nullable: style.short_nullable || file.style.short_nullable,
+                ..*style
+            };
+
+            return file.generate_styled(&style, indentation, level);
+        }
+
+        if self.auto_import
+            || self.alias_conflicts
+            || self.deduplicate_imports
+            || self.prune_imports
+            || self.import_order != ImportOrder::Insertion
+        {
+            let mut file = self.clone();
+            import::organize(&mut file);
+
+            return file.generate_styled(style, indentation, level);
+        }
+
         let mut code = String::new();
 
         code.push_str("<?php\n\n");
 
         for (name, value) in &self.declares {
+            let assignment = if style.declare_spacing { " = " } else { "=" };
             code.push_str(&format!(
-                "declare({}={});\n\n",
+                "declare({}{}{});\n\n",
                 name,
+                assignment,
                 value.generate(indentation, level)
             ));
         }
@@ -130,27 +263,29 @@ impl Generator for File {
         let mut used = false;
         if !self.uses.is_empty() {
             used = true;
-            for r#use in &self.uses {
-                code.push_str(&format!("use {};\n", r#use));
-            }
+            code.push_str(&import::print("use ", &self.uses, self.group_imports));
 
             code.push('\n');
         }
 
         if !self.function_uses.is_empty() {
             used = true;
-            for function in &self.function_uses {
-                code.push_str(&format!("use function {};\n", function));
-            }
+            code.push_str(&import::print(
+                "use function ",
+                &self.function_uses,
+                self.group_imports,
+            ));
 
             code.push('\n');
         }
 
         if !self.constant_uses.is_empty() {
             used = true;
-            for constant in &self.constant_uses {
-                code.push_str(&format!("use const {};\n", constant));
-            }
+            code.push_str(&import::print(
+                "use const ",
+                &self.constant_uses,
+                self.group_imports,
+            ));
 
             code.push('\n');
         }
@@ -160,16 +295,13 @@ impl Generator for File {
         }
 
         code.push_str(self.constants.generate(indentation, level).as_str());
-        code.push_str(self.functions.generate(indentation, level).as_str());
-        code.push_str(self.classes.generate(indentation, level).as_str());
-        code.push_str(self.traits.generate(indentation, level).as_str());
-        code.push_str(self.enums.generate(indentation, level).as_str());
-        code.push_str(self.interfaces.generate(indentation, level).as_str());
-
-        code = code.trim_end().to_string();
-        code.push('\n');
+        code.push_str(&self.functions.generate_styled(style, indentation, level));
+        code.push_str(&self.classes.generate_styled(style, indentation, level));
+        code.push_str(&self.traits.generate_styled(style, indentation, level));
+        code.push_str(&self.enums.generate_styled(style, indentation, level));
+        code.push_str(&self.interfaces.generate_styled(style, indentation, level));
 
-        code
+        style.finish(&code)
     }
 }
 
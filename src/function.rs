@@ -2,32 +2,49 @@ use crate::attribute::AttributeGroup;
 use crate::body::Body;
 use crate::comment::Document;
 use crate::data_type::DataType;
+use crate::identifier::Context;
+use crate::naming::Naming;
+use crate::parameter;
 use crate::parameter::Parameter;
+use crate::style::BracePlacement;
+use crate::style::Style;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Function {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
     pub name: String,
+    pub original_name: Option<String>,
     pub parameters: Vec<Parameter>,
     pub return_type: Option<DataType>,
     pub body: Body,
+    pub trailing_comma: bool,
 }
 
 impl Function {
     pub fn new<T: ToString>(name: T) -> Self {
         Self {
             name: name.to_string(),
+            original_name: None,
             parameters: vec![],
             return_type: None,
             body: Body::new().with_semicolon_for_empty(false),
+            trailing_comma: true,
             attributes: vec![],
             documentation: None,
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut function = Self::new(naming.apply(&original, Context::Function));
+        function.original_name = Some(original);
+
+        function
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -62,6 +79,10 @@ impl Function {
 
 impl Generator for Function {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
         if let Some(document) = &self.documentation {
@@ -73,13 +94,41 @@ impl Generator for Function {
         }
 
         code.push_str(format!("function {}", self.name).as_str());
-        code.push_str(self.parameters.generate(indentation, level).as_str());
-
-        if let Some(return_type) = &self.return_type {
-            code.push_str(format!(": {}", return_type.generate(indentation, level)).as_str());
-        }
-
-        code.push_str(self.body.generate(indentation, level).as_str());
+        let return_type = self
+            .return_type
+            .as_ref()
+            .map(|return_type| {
+                format!(
+                    ": {}",
+                    return_type.generate_styled(style, indentation, level)
+                )
+            })
+            .unwrap_or_default();
+        let brace = match style.function_braces {
+            BracePlacement::SameLine => 2,
+            BracePlacement::NextLine => 0,
+        };
+
+        let parameters = parameter::print(
+            &self.parameters,
+            self.trailing_comma,
+            style,
+            (parameter::width(&code), return_type.chars().count() + brace),
+            indentation,
+            level,
+        );
+
+        // Multi-line parameter lists keep the brace on the closing line.
+        let braces = if parameters.contains('\n') {
+            BracePlacement::SameLine
+        } else {
+            style.function_braces
+        };
+
+        code.push_str(&parameters);
+        code.push_str(&return_type);
+
+        code.push_str(&self.body.print(braces, style, indentation, level));
 
         code
     }
@@ -87,13 +136,17 @@ impl Generator for Function {
 
 impl Generator for Vec<Function> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
         if self.is_empty() {
             return code;
         }
 
         for function in self {
-            code.push_str(function.generate(indentation, level).as_str());
+            code.push_str(function.generate_styled(style, indentation, level).as_str());
             code.push('\n');
         }
 
@@ -0,0 +1,250 @@
+use std::fmt::Display;
+
+use crate::name::is_label;
+
+/// Where an identifier is declared, which decides the words it may not be.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Context {
+    Class,
+    Function,
+    Constant,
+    Method,
+    ClassConstant,
+    EnumCase,
+    Property,
+    Variable,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IdentifierError {
+    Empty,
+    Invalid { name: String },
+    Reserved { name: String, context: Context },
+    SoftReserved { name: String, context: Context },
+}
+
+const KEYWORDS: [&str; 77] = [
+    "__halt_compiler",
+    "abstract",
+    "and",
+    "array",
+    "as",
+    "break",
+    "callable",
+    "case",
+    "catch",
+    "class",
+    "clone",
+    "const",
+    "continue",
+    "declare",
+    "default",
+    "die",
+    "do",
+    "echo",
+    "else",
+    "elseif",
+    "empty",
+    "enddeclare",
+    "endfor",
+    "endforeach",
+    "endif",
+    "endswitch",
+    "endwhile",
+    "eval",
+    "exit",
+    "extends",
+    "final",
+    "finally",
+    "fn",
+    "for",
+    "foreach",
+    "function",
+    "global",
+    "goto",
+    "if",
+    "implements",
+    "include",
+    "include_once",
+    "instanceof",
+    "insteadof",
+    "interface",
+    "isset",
+    "list",
+    "match",
+    "namespace",
+    "new",
+    "or",
+    "print",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "require",
+    "require_once",
+    "return",
+    "static",
+    "switch",
+    "throw",
+    "trait",
+    "try",
+    "unset",
+    "use",
+    "var",
+    "while",
+    "xor",
+    "yield",
+    "__class__",
+    "__dir__",
+    "__file__",
+    "__function__",
+    "__line__",
+    "__method__",
+    "__namespace__",
+];
+
+const MAGIC_CONSTANTS: [&str; 2] = ["__trait__", "__property__"];
+
+const TYPE_NAMES: [&str; 14] = [
+    "bool", "false", "float", "int", "iterable", "mixed", "never", "null", "object", "parent",
+    "self", "string", "true", "void",
+];
+
+const SOFT_RESERVED: [&str; 2] = ["resource", "numeric"];
+
+const CONSTANT_NAMES: [&str; 4] = ["true", "false", "null", "__compiler_halt_offset__"];
+
+/// Checks `name` against PHP's identifier grammar and the words reserved in
+/// `context`.
+///
+/// Methods, class constants, enum cases and properties may use most
+/// keywords, as PHP lexes them as identifiers after `->`, `::` and
+/// `function`. Class-like names may additionally not be one of the builtin
+/// type names.
+pub fn validate(name: &str, context: Context) -> Result<(), IdentifierError> {
+    if name.is_empty() {
+        return Err(IdentifierError::Empty);
+    }
+
+    if !is_label(name) {
+        return Err(IdentifierError::Invalid {
+            name: name.to_string(),
+        });
+    }
+
+    if is_reserved(name, context) {
+        return Err(IdentifierError::Reserved {
+            name: name.to_string(),
+            context,
+        });
+    }
+
+    if context == Context::Class && SOFT_RESERVED.contains(&name.to_ascii_lowercase().as_str()) {
+        return Err(IdentifierError::SoftReserved {
+            name: name.to_string(),
+            context,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn is_valid(name: &str, context: Context) -> bool {
+    validate(name, context).is_ok()
+}
+
+pub fn is_reserved(name: &str, context: Context) -> bool {
+    let lowercase = name.to_ascii_lowercase();
+    let lowercase = lowercase.as_str();
+
+    match context {
+        Context::Class => {
+            KEYWORDS.contains(&lowercase)
+                || MAGIC_CONSTANTS.contains(&lowercase)
+                || TYPE_NAMES.contains(&lowercase)
+        }
+        Context::Function => {
+            lowercase != "readonly"
+                && (KEYWORDS.contains(&lowercase) || MAGIC_CONSTANTS.contains(&lowercase))
+        }
+        Context::Constant => {
+            KEYWORDS.contains(&lowercase)
+                || MAGIC_CONSTANTS.contains(&lowercase)
+                || CONSTANT_NAMES.contains(&lowercase)
+        }
+        Context::ClassConstant | Context::EnumCase => lowercase == "class",
+        Context::Method | Context::Property => false,
+        Context::Variable => name == "this",
+    }
+}
+
+/// Rewrites `name` into a valid identifier for `context`:
+///
+/// - each run of characters that cannot appear in an identifier becomes `_`,
+/// - a name starting with a digit, or an empty name, is prefixed with `_`,
+/// - a reserved name gets a `_` suffix, so `list` becomes `list_`.
+///
+/// Valid names are returned unchanged.
+pub fn sanitize(name: &str, context: Context) -> String {
+    let mut sanitized = String::with_capacity(name.len() + 1);
+    for character in name.chars() {
+        if character == '_' || character.is_ascii_alphanumeric() || !character.is_ascii() {
+            sanitized.push(character);
+        } else if !sanitized.ends_with('_') {
+            sanitized.push('_');
+        }
+    }
+
+    if !sanitized.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic() || !c.is_ascii()) {
+        sanitized.insert(0, '_');
+    }
+
+    loop {
+        match validate(&sanitized, context) {
+            Err(IdentifierError::Reserved { .. } | IdentifierError::SoftReserved { .. }) => {
+                sanitized.push('_')
+            }
+            _ => return sanitized,
+        }
+    }
+}
+
+impl Display for Context {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Context::Class => write!(f, "class"),
+            Context::Function => write!(f, "function"),
+            Context::Constant => write!(f, "constant"),
+            Context::Method => write!(f, "method"),
+            Context::ClassConstant => write!(f, "class constant"),
+            Context::EnumCase => write!(f, "enum case"),
+            Context::Property => write!(f, "property"),
+            Context::Variable => write!(f, "variable"),
+        }
+    }
+}
+
+impl Display for IdentifierError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdentifierError::Empty => write!(f, "identifier cannot be empty"),
+            IdentifierError::Invalid { name } => {
+                write!(f, "`{}` is not a valid PHP identifier", name)
+            }
+            IdentifierError::Reserved { name, context } => {
+                write!(
+                    f,
+                    "`{}` is reserved and cannot be used as a {} name",
+                    name, context
+                )
+            }
+            IdentifierError::SoftReserved { name, context } => write!(
+                f,
+                "`{}` is reserved for future use and should not be used as a {} name",
+                name, context
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IdentifierError {}
@@ -0,0 +1,817 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use crate::body::Body;
+use crate::file::File;
+use crate::name::Name;
+use crate::name::SymbolKind;
+use crate::visitor::VisitorMut;
+use crate::visitor::Walk;
+use crate::Indentation;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Import {
+    pub name: Name,
+    pub alias: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ImportOrder {
+    #[default]
+    Insertion,
+    /// Sorts by the printed import, comparing bytes.
+    Alphabetical,
+    /// Sorts case-insensitively segment by segment, so that a namespace
+    /// comes before the namespaces nested in it.
+    Psr12,
+}
+
+impl Import {
+    pub fn new<T: Into<Name>>(name: T) -> Self {
+        Self {
+            name: name.into(),
+            alias: None,
+        }
+    }
+
+    pub fn aliased<T: ToString>(mut self, alias: T) -> Self {
+        self.alias = Some(alias.to_string());
+
+        self
+    }
+
+    /// Returns the name this import is referred to by in the file.
+    pub fn local_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(self.name.last())
+    }
+}
+
+impl Display for Import {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.alias {
+            Some(alias) => write!(f, "{} as {}", self.name.unqualified(), alias),
+            None => write!(f, "{}", self.name.unqualified()),
+        }
+    }
+}
+
+impl From<&str> for Import {
+    fn from(import: &str) -> Self {
+        let import = import.trim();
+        let split = import
+            .to_ascii_lowercase()
+            .rfind(" as ")
+            .map(|position| (&import[..position], &import[position + 4..]));
+
+        match split {
+            Some((name, alias)) => Self::new(name.trim()).aliased(alias.trim()),
+            None => Self::new(import),
+        }
+    }
+}
+
+impl From<String> for Import {
+    fn from(import: String) -> Self {
+        import.as_str().into()
+    }
+}
+
+impl<T: Into<Name>, A: ToString> From<(T, A)> for Import {
+    fn from((name, alias): (T, A)) -> Self {
+        Self::new(name).aliased(alias)
+    }
+}
+
+impl From<Name> for Import {
+    fn from(name: Name) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A name found in PHP source text.
+#[derive(Debug)]
+pub(crate) struct Occurrence {
+    pub start: usize,
+    pub end: usize,
+    pub name: Name,
+    pub kind: SymbolKind,
+}
+
+/// Finds the names in PHP source, skipping strings, comments, variables and
+/// member names.
+///
+/// Whether a name refers to a class, function or constant is inferred from
+/// the surrounding tokens: `new`, `instanceof`, `::`, parameter, return and
+/// caught types mark classes, a following `(` marks functions, and an
+/// upper-case name that is neither is taken to be a constant. Named arguments and labels are not
+/// names of symbols and are skipped.
+pub(crate) fn scan(code: &str) -> Vec<Occurrence> {
+    let bytes = code.as_bytes();
+    let mut occurrences = vec![];
+    let mut previous = String::new();
+    let mut position = 0;
+
+    while position < bytes.len() {
+        let byte = bytes[position];
+        let next = bytes.get(position + 1).copied();
+
+        match byte {
+            b'\'' | b'"' | b'`' => {
+                position = skip_string(bytes, position);
+                previous = String::from("string");
+            }
+            b'/' if next == Some(b'/') => position = skip_line(bytes, position),
+            b'#' if next != Some(b'[') => position = skip_line(bytes, position),
+            b'/' if next == Some(b'*') => {
+                position = code[position + 2..]
+                    .find("*/")
+                    .map(|end| position + end + 4)
+                    .unwrap_or(bytes.len());
+            }
+            b'<' if code[position..].starts_with("<<<") => {
+                position = skip_heredoc(code, position);
+                previous = String::from("string");
+            }
+            b'$' => {
+                position = skip_label(bytes, position + 1);
+                previous = String::from("$");
+            }
+            b'\\' => {
+                let start = position;
+                while bytes.get(position) == Some(&b'\\')
+                    && bytes
+                        .get(position + 1)
+                        .is_some_and(|byte| is_label_start(*byte))
+                {
+                    position = skip_label(bytes, position + 1);
+                }
+
+                if position == start {
+                    position += 1;
+
+                    continue;
+                }
+
+                let name = Name::new(&code[start..position]);
+                let kind = classify(&previous, code[position..].trim_start(), &name);
+                occurrences.push(Occurrence {
+                    start,
+                    end: position,
+                    name,
+                    kind,
+                });
+
+                previous = String::from("name");
+            }
+            byte if is_label_start(byte) => {
+                let start = position;
+                position = skip_label(bytes, position);
+                while bytes.get(position) == Some(&b'\\')
+                    && bytes
+                        .get(position + 1)
+                        .is_some_and(|byte| is_label_start(*byte))
+                {
+                    position = skip_label(bytes, position + 1);
+                }
+
+                let text = &code[start..position];
+                let rest = code[position..].trim_start();
+                // A name followed by a single `:` after `(`, `,` or at the
+                // start of a statement is a named argument or a label.
+                let label = !text.contains('\\')
+                    && rest.starts_with(':')
+                    && !rest.starts_with("::")
+                    && matches!(previous.as_str(), "" | "(" | "," | ";" | "{" | "}");
+                if previous != "->" && previous != "::" && !label {
+                    let name = Name::new(text);
+                    let kind = classify(&previous, rest, &name);
+                    occurrences.push(Occurrence {
+                        start,
+                        end: position,
+                        name,
+                        kind,
+                    });
+                }
+
+                previous = text.to_ascii_lowercase();
+            }
+            byte if byte.is_ascii_whitespace() => position += 1,
+            b'-' if next == Some(b'>') => {
+                previous = String::from("->");
+                position += 2;
+            }
+            b':' if next == Some(b':') => {
+                previous = String::from("::");
+                position += 2;
+            }
+            // Return types follow `):` and caught types follow `catch (`.
+            b':' if previous == ")" => {
+                previous = String::from("):");
+                position += 1;
+            }
+            b'(' if previous == "catch" => {
+                previous = String::from("catch (");
+                position += 1;
+            }
+            byte => {
+                previous = String::from(byte as char);
+                position += 1;
+            }
+        }
+    }
+
+    occurrences
+}
+
+/// Replaces the names in `code` for which `replacement` returns a new
+/// spelling.
+pub(crate) fn rewrite(
+    code: &str,
+    replacement: &dyn Fn(&Name, SymbolKind) -> Option<String>,
+) -> String {
+    let mut result = String::with_capacity(code.len());
+    let mut position = 0;
+
+    for occurrence in scan(code) {
+        if let Some(replacement) = replacement(&occurrence.name, occurrence.kind) {
+            result.push_str(&code[position..occurrence.start]);
+            result.push_str(&replacement);
+            position = occurrence.end;
+        }
+    }
+
+    result.push_str(&code[position..]);
+
+    result
+}
+
+fn classify(previous: &str, rest: &str, name: &Name) -> SymbolKind {
+    if matches!(
+        previous,
+        "new" | "instanceof" | "extends" | "implements" | "insteadof" | "|"
+    ) {
+        return SymbolKind::Class;
+    }
+
+    if rest.starts_with("::") || rest.starts_with(['$', '&', '|']) || rest.starts_with("...") {
+        return SymbolKind::Class;
+    }
+
+    // A return type, possibly nullable, comes before a closure body or an
+    // arrow function's `=>`, where a constant in a ternary cannot.
+    if matches!(previous, "):" | "?") && (rest.starts_with('{') || rest.starts_with("=>")) {
+        return SymbolKind::Class;
+    }
+
+    if previous == "catch (" && rest.starts_with(')') {
+        return SymbolKind::Class;
+    }
+
+    if rest.starts_with('(') {
+        return SymbolKind::Function;
+    }
+
+    let last = name.last();
+    if last.chars().any(|c| c.is_ascii_alphabetic())
+        && !last.chars().any(|c| c.is_ascii_lowercase())
+    {
+        SymbolKind::Constant
+    } else {
+        SymbolKind::Class
+    }
+}
+
+fn is_label_start(byte: u8) -> bool {
+    byte == b'_' || byte.is_ascii_alphabetic() || byte >= 0x80
+}
+
+fn skip_label(bytes: &[u8], mut position: usize) -> usize {
+    while bytes
+        .get(position)
+        .is_some_and(|byte| *byte == b'_' || byte.is_ascii_alphanumeric() || *byte >= 0x80)
+    {
+        position += 1;
+    }
+
+    position
+}
+
+fn skip_line(bytes: &[u8], mut position: usize) -> usize {
+    while position < bytes.len() && bytes[position] != b'\n' {
+        position += 1;
+    }
+
+    position
+}
+
+fn skip_string(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut position = start + 1;
+
+    while position < bytes.len() {
+        match bytes[position] {
+            b'\\' => position += 2,
+            byte if byte == quote => return position + 1,
+            _ => position += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+fn skip_heredoc(code: &str, start: usize) -> usize {
+    let header = &code[start + 3..];
+    let label = header
+        .trim_start_matches([' ', '\t'])
+        .trim_start_matches(['\'', '"']);
+    let label = &label[..skip_label(label.as_bytes(), 0)];
+    if label.is_empty() {
+        return start + 3;
+    }
+
+    let mut position = code[start..]
+        .find('\n')
+        .map(|end| start + end + 1)
+        .unwrap_or(code.len());
+
+    while position < code.len() {
+        let end = skip_line(code.as_bytes(), position);
+        let line = code[position..end].trim_start();
+        if let Some(rest) = line.strip_prefix(label) {
+            if !rest.starts_with(|c: char| c == '_' || c.is_alphanumeric()) {
+                return end - rest.len();
+            }
+        }
+
+        position = end + 1;
+    }
+
+    code.len()
+}
+
+pub(crate) fn key(name: &Name, kind: SymbolKind) -> String {
+    match kind {
+        SymbolKind::Constant => match name.namespace() {
+            Some(namespace) => format!(
+                "{}\\{}",
+                namespace.unqualified().to_ascii_lowercase(),
+                name.last()
+            ),
+            None => name.last().to_string(),
+        },
+        SymbolKind::Class | SymbolKind::Function => name.unqualified().to_ascii_lowercase(),
+    }
+}
+
+fn local_key(local: &str, kind: SymbolKind) -> String {
+    match kind {
+        SymbolKind::Constant => local.to_string(),
+        SymbolKind::Class | SymbolKind::Function => local.to_ascii_lowercase(),
+    }
+}
+
+#[derive(Default)]
+struct Collector {
+    names: Vec<(Name, SymbolKind)>,
+}
+
+impl Collector {
+    fn add(&mut self, name: &Name, kind: SymbolKind) {
+        if name.is_fully_qualified()
+            && name.parts.len() > 1
+            && !self
+                .names
+                .iter()
+                .any(|(existing, existing_kind)| *existing_kind == kind && existing == name)
+        {
+            self.names.push((name.clone(), kind));
+        }
+    }
+
+    fn add_code(&mut self, code: &str) {
+        for occurrence in scan(code) {
+            self.add(&occurrence.name, occurrence.kind);
+        }
+    }
+}
+
+impl VisitorMut for Collector {
+    fn name(&mut self, name: &mut Name, kind: SymbolKind) {
+        self.add(name, kind);
+    }
+
+    fn code(&mut self, code: &mut String) {
+        self.add_code(code);
+    }
+
+    fn body(&mut self, body: &mut Body) {
+        if let Some(factory) = &body.factory {
+            self.add_code(&factory(Indentation::default(), 0));
+        }
+    }
+}
+
+struct Shortener {
+    short: Rc<HashMap<(SymbolKind, String), String>>,
+}
+
+impl Shortener {
+    fn replacement(
+        short: &HashMap<(SymbolKind, String), String>,
+        name: &Name,
+        kind: SymbolKind,
+    ) -> Option<String> {
+        if !name.is_fully_qualified() {
+            return None;
+        }
+
+        short.get(&(kind, key(name, kind))).cloned()
+    }
+}
+
+impl VisitorMut for Shortener {
+    fn name(&mut self, name: &mut Name, kind: SymbolKind) {
+        if let Some(short) = Self::replacement(&self.short, name, kind) {
+            *name = Name::new(short);
+        }
+    }
+
+    fn code(&mut self, code: &mut String) {
+        *code = rewrite(code, &|name, kind| {
+            Self::replacement(&self.short, name, kind)
+        });
+    }
+
+    fn body(&mut self, body: &mut Body) {
+        if let Some(factory) = body.factory.clone() {
+            let short = self.short.clone();
+
+            body.factory = Some(Rc::new(move |indentation, level| {
+                rewrite(&factory(indentation, level), &|name, kind| {
+                    Self::replacement(&short, name, kind)
+                })
+            }));
+        }
+    }
+}
+
+/// Organizes the imports of `file` according to its import settings, and
+/// resets them so that the result can be printed as is.
+pub(crate) fn organize(file: &mut File) {
+    if file.deduplicate_imports {
+        for imports in [
+            &mut file.uses,
+            &mut file.function_uses,
+            &mut file.constant_uses,
+        ] {
+            let mut unique: Vec<Import> = vec![];
+            for import in imports.drain(..) {
+                if !unique.contains(&import) {
+                    unique.push(import);
+                }
+            }
+
+            *imports = unique;
+        }
+    }
+
+    import(file);
+
+    if file.prune_imports {
+        prune(file);
+    }
+
+    for imports in [
+        &mut file.uses,
+        &mut file.function_uses,
+        &mut file.constant_uses,
+    ] {
+        match file.import_order {
+            ImportOrder::Insertion => {}
+            ImportOrder::Alphabetical => imports.sort_by_key(|import| import.to_string()),
+            ImportOrder::Psr12 => imports.sort_by_cached_key(|import| {
+                import
+                    .name
+                    .parts
+                    .iter()
+                    .map(|part| part.to_ascii_lowercase())
+                    .collect::<Vec<String>>()
+            }),
+        }
+    }
+
+    file.auto_import = false;
+    file.alias_conflicts = false;
+    file.deduplicate_imports = false;
+    file.prune_imports = false;
+    file.import_order = ImportOrder::Insertion;
+}
+
+/// With `auto_import`, imports the fully qualified names used in the file
+/// and shortens them. Names in the file's own namespace are shortened
+/// without an import, and names in the global namespace are left as they are.
+///
+/// A name whose short form is already taken by another import or by a
+/// declaration is given an alias when `alias_conflicts` is set, and is
+/// otherwise kept fully qualified.
+fn import(file: &mut File) {
+    let namespace = file
+        .namespace
+        .as_ref()
+        .map(|namespace| namespace.unqualified().to_ascii_lowercase());
+    let declared = |local: &str| match &file.namespace {
+        Some(namespace) => Name::new(format!("{}\\{}", namespace.unqualified(), local)),
+        None => Name::new(local),
+    };
+
+    let mut short = HashMap::new();
+    let mut taken = HashMap::new();
+
+    let mut declarations = vec![];
+    declarations.extend(
+        file.classes
+            .iter()
+            .map(|class| (SymbolKind::Class, &class.name)),
+    );
+    declarations.extend(
+        file.interfaces
+            .iter()
+            .map(|interface| (SymbolKind::Class, &interface.name)),
+    );
+    declarations.extend(
+        file.traits
+            .iter()
+            .map(|r#trait| (SymbolKind::Class, &r#trait.name)),
+    );
+    declarations.extend(
+        file.enums
+            .iter()
+            .map(|r#enum| (SymbolKind::Class, &r#enum.name)),
+    );
+    declarations.extend(
+        file.functions
+            .iter()
+            .map(|function| (SymbolKind::Function, &function.name)),
+    );
+    declarations.extend(
+        file.constants
+            .iter()
+            .map(|constant| (SymbolKind::Constant, &constant.name)),
+    );
+    for (kind, local) in declarations {
+        taken
+            .entry((kind, local_key(local, kind)))
+            .or_insert_with(|| key(&declared(local), kind));
+    }
+
+    let alias_conflicts = file.alias_conflicts;
+    for (kind, imports) in [
+        (SymbolKind::Class, &mut file.uses),
+        (SymbolKind::Function, &mut file.function_uses),
+        (SymbolKind::Constant, &mut file.constant_uses),
+    ] {
+        for import in imports {
+            let symbol = key(&import.name, kind);
+            let clashes = taken
+                .get(&(kind, local_key(import.local_name(), kind)))
+                .is_some_and(|existing| *existing != symbol);
+            if alias_conflicts && clashes && import.alias.is_none() {
+                import.alias = Some(alias(&import.name, kind, &taken));
+            }
+
+            short.insert((kind, symbol.clone()), import.local_name().to_string());
+            taken.insert((kind, local_key(import.local_name(), kind)), symbol);
+        }
+    }
+
+    if !file.auto_import {
+        return;
+    }
+
+    let mut collector = Collector::default();
+    file.walk(&mut collector);
+
+    let mut imports = vec![];
+    for (name, kind) in collector.names {
+        let symbol = key(&name, kind);
+        if short.contains_key(&(kind, symbol.clone())) {
+            continue;
+        }
+
+        let mut local = name.last().to_string();
+        match taken.get(&(kind, local_key(&local, kind))) {
+            Some(existing) if *existing != symbol => {
+                if !alias_conflicts {
+                    continue;
+                }
+
+                local = alias(&name, kind, &taken);
+                taken.insert((kind, local_key(&local, kind)), symbol.clone());
+                imports.push((kind, Import::new(name.unqualified()).aliased(&local)));
+            }
+            Some(_) => {}
+            None => {
+                taken.insert((kind, local_key(&local, kind)), symbol.clone());
+
+                let same_namespace = name
+                    .namespace()
+                    .map(|parent| parent.unqualified().to_ascii_lowercase())
+                    == namespace;
+                if !same_namespace {
+                    imports.push((kind, Import::new(name.unqualified())));
+                }
+            }
+        }
+
+        short.insert((kind, symbol), local);
+    }
+
+    file.walk(&mut Shortener {
+        short: Rc::new(short),
+    });
+
+    for (kind, import) in imports {
+        match kind {
+            SymbolKind::Class => file.uses.push(import),
+            SymbolKind::Function => file.function_uses.push(import),
+            SymbolKind::Constant => file.constant_uses.push(import),
+        }
+    }
+}
+
+#[derive(Default)]
+struct References {
+    names: Vec<Name>,
+}
+
+impl References {
+    fn add(&mut self, name: &Name) {
+        if name.is_unqualified() || name.is_qualified() {
+            self.names.push(name.clone());
+        }
+    }
+
+    fn add_code(&mut self, code: &str) {
+        for occurrence in scan(code) {
+            self.add(&occurrence.name);
+        }
+    }
+
+    /// Returns whether any reference could resolve through `import`. This
+    /// errs on the side of keeping imports, since removing a used one would
+    /// break the generated code.
+    fn uses(&self, import: &Import, kind: SymbolKind) -> bool {
+        let local = import.local_name();
+
+        self.names.iter().any(|name| match kind {
+            SymbolKind::Class => name.first().eq_ignore_ascii_case(local),
+            SymbolKind::Function => {
+                name.is_unqualified() && name.first().eq_ignore_ascii_case(local)
+            }
+            SymbolKind::Constant => name.is_unqualified() && name.first() == local,
+        })
+    }
+}
+
+impl VisitorMut for References {
+    fn name(&mut self, name: &mut Name, _: SymbolKind) {
+        self.add(name);
+    }
+
+    fn code(&mut self, code: &mut String) {
+        self.add_code(code);
+    }
+
+    fn body(&mut self, body: &mut Body) {
+        if let Some(factory) = &body.factory {
+            self.add_code(&factory(Indentation::default(), 0));
+        }
+    }
+}
+
+fn prune(file: &mut File) {
+    let mut references = References::default();
+    file.walk(&mut references);
+
+    file.uses
+        .retain(|import| references.uses(import, SymbolKind::Class));
+    file.function_uses
+        .retain(|import| references.uses(import, SymbolKind::Function));
+    file.constant_uses
+        .retain(|import| references.uses(import, SymbolKind::Constant));
+}
+
+/// Prints `use` statements for `imports`, combining imports that share a
+/// namespace into a group once there are at least `threshold` of them.
+pub(crate) fn print(keyword: &str, imports: &[Import], threshold: Option<usize>) -> String {
+    let namespace = |import: &Import| {
+        import
+            .name
+            .namespace()
+            .map(|namespace| namespace.unqualified())
+    };
+
+    let mut code = String::new();
+    let mut printed = vec![false; imports.len()];
+    for (index, import) in imports.iter().enumerate() {
+        if printed[index] {
+            continue;
+        }
+
+        let group = match (threshold, namespace(import)) {
+            (Some(threshold), Some(prefix)) => {
+                let members = (index..imports.len())
+                    .filter(|other| {
+                        !printed[*other] && namespace(&imports[*other]).as_ref() == Some(&prefix)
+                    })
+                    .collect::<Vec<usize>>();
+
+                if members.len() >= threshold.max(2) {
+                    Some((prefix, members))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        match group {
+            Some((prefix, members)) => {
+                let names = members
+                    .iter()
+                    .map(|member| {
+                        printed[*member] = true;
+
+                        let member = &imports[*member];
+                        match &member.alias {
+                            Some(alias) => format!("{} as {}", member.name.last(), alias),
+                            None => member.name.last().to_string(),
+                        }
+                    })
+                    .collect::<Vec<String>>();
+
+                code.push_str(&format!(
+                    "{}{}\\{{{}}};\n",
+                    keyword,
+                    prefix,
+                    names.join(", ")
+                ));
+            }
+            None => {
+                printed[index] = true;
+                code.push_str(&format!("{}{};\n", keyword, import));
+            }
+        }
+    }
+
+    code
+}
+
+/// Picks an alias for `name` that is not yet taken, prefixing its short
+/// name with as many namespace segments as needed: `Vendor\Sdk\User`
+/// becomes `SdkUser`, then `VendorSdkUser`, then `VendorSdkUser2`.
+fn alias(name: &Name, kind: SymbolKind, taken: &HashMap<(SymbolKind, String), String>) -> String {
+    let spell = |parts: &[String]| match kind {
+        SymbolKind::Class => parts.iter().map(|part| capitalize(part)).collect(),
+        SymbolKind::Function => parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| match index == parts.len() - 1 {
+                true => part.to_string(),
+                false => part.to_ascii_lowercase(),
+            })
+            .collect::<Vec<String>>()
+            .join("_"),
+        SymbolKind::Constant => parts
+            .iter()
+            .map(|part| part.to_ascii_uppercase())
+            .collect::<Vec<String>>()
+            .join("_"),
+    };
+    let available = |candidate: &str| !taken.contains_key(&(kind, local_key(candidate, kind)));
+
+    let mut candidate = name.last().to_string();
+    for length in 2..=name.parts.len() {
+        candidate = spell(&name.parts[name.parts.len() - length..]);
+        if available(&candidate) {
+            return candidate;
+        }
+    }
+
+    let mut suffix = 2;
+    while !available(&format!("{}{}", candidate, suffix)) {
+        suffix += 1;
+    }
+
+    format!("{}{}", candidate, suffix)
+}
+
+fn capitalize(part: &str) -> String {
+    let mut characters = part.chars();
+
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,843 @@
+use std::collections::HashMap;
+
+use crate::attribute::AttributeGroup;
+use crate::data_type::DataType;
+use crate::file::File;
+use crate::import;
+use crate::location::Location;
+use crate::method::Method;
+use crate::modifiers::Modifier;
+use crate::modifiers::VisibilityModifier;
+use crate::name::Name;
+use crate::name::Scope;
+use crate::name::SymbolKind;
+use crate::parameter::Parameter;
+use crate::usage::Usage;
+use crate::validation::Diagnostic;
+use crate::Generator;
+use crate::Indentation;
+
+/// Builtin classes and interfaces whose parents matter for variance, such
+/// as an `ArrayIterator` being `iterable`.
+const BUILTIN_PARENTS: &[(&str, &str)] = &[
+    ("Iterator", "Traversable"),
+    ("IteratorAggregate", "Traversable"),
+    ("SeekableIterator", "Iterator"),
+    ("RecursiveIterator", "Iterator"),
+    ("OuterIterator", "Iterator"),
+    ("Generator", "Iterator"),
+    ("ArrayIterator", "SeekableIterator"),
+    ("ArrayIterator", "ArrayAccess"),
+    ("ArrayIterator", "Countable"),
+    ("RecursiveArrayIterator", "ArrayIterator"),
+    ("RecursiveArrayIterator", "RecursiveIterator"),
+    ("ArrayObject", "IteratorAggregate"),
+    ("ArrayObject", "ArrayAccess"),
+    ("ArrayObject", "Countable"),
+    ("EmptyIterator", "Iterator"),
+    ("MultipleIterator", "Iterator"),
+    ("IteratorIterator", "OuterIterator"),
+    ("FilterIterator", "IteratorIterator"),
+    ("CallbackFilterIterator", "FilterIterator"),
+    ("RegexIterator", "FilterIterator"),
+    ("LimitIterator", "IteratorIterator"),
+    ("CachingIterator", "IteratorIterator"),
+    ("CachingIterator", "ArrayAccess"),
+    ("CachingIterator", "Countable"),
+    ("NoRewindIterator", "IteratorIterator"),
+    ("AppendIterator", "IteratorIterator"),
+    ("InfiniteIterator", "IteratorIterator"),
+    ("RecursiveIteratorIterator", "OuterIterator"),
+    ("SplDoublyLinkedList", "Iterator"),
+    ("SplDoublyLinkedList", "ArrayAccess"),
+    ("SplDoublyLinkedList", "Countable"),
+    ("SplQueue", "SplDoublyLinkedList"),
+    ("SplStack", "SplDoublyLinkedList"),
+    ("SplHeap", "Iterator"),
+    ("SplHeap", "Countable"),
+    ("SplMinHeap", "SplHeap"),
+    ("SplMaxHeap", "SplHeap"),
+    ("SplPriorityQueue", "Iterator"),
+    ("SplPriorityQueue", "Countable"),
+    ("SplFixedArray", "IteratorAggregate"),
+    ("SplFixedArray", "ArrayAccess"),
+    ("SplFixedArray", "Countable"),
+    ("SplObjectStorage", "Iterator"),
+    ("SplObjectStorage", "ArrayAccess"),
+    ("SplObjectStorage", "Countable"),
+    ("DirectoryIterator", "SplFileInfo"),
+    ("DirectoryIterator", "SeekableIterator"),
+    ("FilesystemIterator", "DirectoryIterator"),
+    ("RecursiveDirectoryIterator", "FilesystemIterator"),
+    ("RecursiveDirectoryIterator", "RecursiveIterator"),
+    ("GlobIterator", "FilesystemIterator"),
+    ("GlobIterator", "Countable"),
+    ("SplFileObject", "SplFileInfo"),
+    ("SplFileObject", "RecursiveIterator"),
+    ("SplFileObject", "SeekableIterator"),
+    ("SplTempFileObject", "SplFileObject"),
+    ("DatePeriod", "IteratorAggregate"),
+    ("WeakMap", "IteratorAggregate"),
+    ("WeakMap", "ArrayAccess"),
+    ("WeakMap", "Countable"),
+    ("PDOStatement", "IteratorAggregate"),
+    ("mysqli_result", "IteratorAggregate"),
+    ("DOMNodeList", "IteratorAggregate"),
+    ("DOMNodeList", "Countable"),
+    ("DOMNamedNodeMap", "IteratorAggregate"),
+    ("DOMNamedNodeMap", "Countable"),
+    ("SimpleXMLElement", "RecursiveIterator"),
+    ("SimpleXMLElement", "Countable"),
+    ("SimpleXMLElement", "Stringable"),
+    ("Exception", "Throwable"),
+    ("Error", "Throwable"),
+    ("ErrorException", "Exception"),
+    ("JsonException", "Exception"),
+    ("LogicException", "Exception"),
+    ("BadFunctionCallException", "LogicException"),
+    ("BadMethodCallException", "BadFunctionCallException"),
+    ("DomainException", "LogicException"),
+    ("InvalidArgumentException", "LogicException"),
+    ("LengthException", "LogicException"),
+    ("OutOfRangeException", "LogicException"),
+    ("RuntimeException", "Exception"),
+    ("OutOfBoundsException", "RuntimeException"),
+    ("OverflowException", "RuntimeException"),
+    ("RangeException", "RuntimeException"),
+    ("UnderflowException", "RuntimeException"),
+    ("UnexpectedValueException", "RuntimeException"),
+    ("TypeError", "Error"),
+    ("ArgumentCountError", "TypeError"),
+    ("ValueError", "Error"),
+    ("ArithmeticError", "Error"),
+    ("DivisionByZeroError", "ArithmeticError"),
+    ("BackedEnum", "UnitEnum"),
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Kind {
+    Class,
+    Interface,
+    Trait,
+    Enum,
+}
+
+#[derive(Debug)]
+struct Declaration<'a> {
+    kind: Kind,
+    name: Name,
+    scope: Scope,
+    r#abstract: bool,
+    parent: Option<Name>,
+    interfaces: Vec<Name>,
+    traits: Vec<Name>,
+    methods: &'a [Method],
+    external: bool,
+}
+
+/// A method as seen from a class-like, which may have imported it from a
+/// trait. Types in its signature resolve against `source`, while `self`
+/// refers to `owner`.
+struct Member<'a> {
+    method: &'a Method,
+    source: &'a Declaration<'a>,
+    owner: &'a Declaration<'a>,
+}
+
+/// The class-likes declared in a set of files, used to check that classes
+/// implement their abstract methods and that overrides are compatible with
+/// the methods they override.
+#[derive(Debug)]
+pub struct Hierarchy<'a> {
+    declarations: HashMap<String, Declaration<'a>>,
+    order: Vec<String>,
+}
+
+impl<'a> Hierarchy<'a> {
+    pub fn from_files(files: &'a [File]) -> Self {
+        let mut hierarchy = Self {
+            declarations: HashMap::new(),
+            order: vec![],
+        };
+        hierarchy.add(files, false);
+
+        hierarchy
+    }
+
+    /// Adds stubs for class-likes declared outside the generated files, such
+    /// as those of a framework. Stubs are not checked themselves.
+    pub fn external(mut self, stubs: &'a [File]) -> Self {
+        self.add(stubs, true);
+
+        self
+    }
+
+    fn add(&mut self, files: &'a [File], external: bool) {
+        for file in files {
+            let scope = file.scope();
+            let declared = |name: &str| match &file.namespace {
+                Some(namespace) => Name::fully_qualified(
+                    namespace
+                        .parts
+                        .iter()
+                        .map(String::as_str)
+                        .chain([name])
+                        .collect(),
+                ),
+                None => Name::fully_qualified(vec![name]),
+            };
+            let resolve = |names: Vec<&Name>| {
+                names
+                    .into_iter()
+                    .map(|name| scope.resolve_class(name))
+                    .collect::<Vec<Name>>()
+            };
+            let traits =
+                |usages: &[Usage]| resolve(usages.iter().flat_map(|usage| &usage.traits).collect());
+
+            for class in &file.classes {
+                self.insert(Declaration {
+                    kind: Kind::Class,
+                    name: declared(&class.name),
+                    scope: scope.clone(),
+                    r#abstract: class.modifiers.contains(&Modifier::Abstract),
+                    parent: class.extends.as_ref().map(|name| scope.resolve_class(name)),
+                    interfaces: resolve(class.implements.iter().collect()),
+                    traits: traits(&class.usages),
+                    methods: &class.methods,
+                    external,
+                });
+            }
+
+            for interface in &file.interfaces {
+                self.insert(Declaration {
+                    kind: Kind::Interface,
+                    name: declared(&interface.name),
+                    scope: scope.clone(),
+                    r#abstract: true,
+                    parent: None,
+                    interfaces: resolve(interface.extends.iter().collect()),
+                    traits: vec![],
+                    methods: &interface.methods,
+                    external,
+                });
+            }
+
+            for r#trait in &file.traits {
+                self.insert(Declaration {
+                    kind: Kind::Trait,
+                    name: declared(&r#trait.name),
+                    scope: scope.clone(),
+                    r#abstract: true,
+                    parent: None,
+                    interfaces: vec![],
+                    traits: traits(&r#trait.usages),
+                    methods: &r#trait.methods,
+                    external,
+                });
+            }
+
+            for r#enum in &file.enums {
+                self.insert(Declaration {
+                    kind: Kind::Enum,
+                    name: declared(&r#enum.name),
+                    scope: scope.clone(),
+                    r#abstract: false,
+                    parent: None,
+                    interfaces: resolve(r#enum.implements.iter().collect()),
+                    traits: traits(&r#enum.usages),
+                    methods: &r#enum.methods,
+                    external,
+                });
+            }
+        }
+    }
+
+    /// Returns whether the class-like `child` extends or implements
+    /// `parent`, directly or through its ancestors. Both names must be fully
+    /// qualified.
+    pub fn is_subclass(&self, child: &Name, parent: &Name) -> bool {
+        let mut pending = self.parents(child);
+        let mut visited: Vec<Name> = vec![];
+
+        while let Some(name) = pending.pop() {
+            if name.matches(parent) {
+                return true;
+            }
+
+            if !visited.iter().any(|visited| visited.matches(&name)) {
+                pending.extend(self.parents(&name));
+                visited.push(name);
+            }
+        }
+
+        false
+    }
+
+    /// Returns the problems found across the hierarchy: concrete classes
+    /// missing implementations, incompatible overrides and overridden final
+    /// methods.
+    pub fn check(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        for key in &self.order {
+            let declaration = &self.declarations[key];
+            if declaration.kind == Kind::Trait || declaration.external {
+                continue;
+            }
+
+            let location = Location::new().child(format!(
+                "{:?} {}",
+                declaration.kind,
+                declaration.name.unqualified()
+            ));
+
+            if !declaration.r#abstract {
+                self.check_implemented(declaration, &location, &mut diagnostics);
+            }
+
+            let ancestors = self.ancestors(declaration);
+            for member in self.members(declaration) {
+                for ancestor in &ancestors {
+                    for inherited in self.members(ancestor) {
+                        if inherited
+                            .method
+                            .name
+                            .eq_ignore_ascii_case(&member.method.name)
+                        {
+                            self.check_override(
+                                &member,
+                                &inherited,
+                                &location.child(format!("method {}", member.method.name)),
+                                &mut diagnostics,
+                            );
+                        }
+                    }
+                }
+            }
+
+            self.check_override_attributes(declaration, &location, &mut diagnostics);
+        }
+
+        diagnostics
+    }
+
+    /// Returns the methods that override or implement a method inherited
+    /// from a known class or interface, as the lowercase name of their
+    /// class-like and their index in its methods.
+    fn overriding(&self) -> Vec<(String, usize)> {
+        let mut overriding = vec![];
+
+        for key in &self.order {
+            let declaration = &self.declarations[key];
+            if declaration.kind == Kind::Trait || declaration.external {
+                continue;
+            }
+
+            for (index, method) in declaration.methods.iter().enumerate() {
+                if self.overrides(declaration, &method.name) == Some(true) {
+                    overriding.push((key.clone(), index));
+                }
+            }
+        }
+
+        overriding
+    }
+
+    /// Returns whether a method named `name` on `declaration` overrides or
+    /// implements an inherited method, or `None` when that cannot be known
+    /// because some ancestor is missing.
+    fn overrides(&self, declaration: &Declaration, name: &str) -> Option<bool> {
+        for ancestor in self.ancestors(declaration) {
+            let inherited = self.members(ancestor).into_iter().any(|member| {
+                member.method.name.eq_ignore_ascii_case(name)
+                    && (member.method.visibility != Some(VisibilityModifier::Private)
+                        || member.method.modifiers.contains(&Modifier::Abstract))
+            });
+
+            if inherited {
+                return Some(true);
+            }
+        }
+
+        match self.resolved(declaration) {
+            true => Some(false),
+            false => None,
+        }
+    }
+
+    /// Returns whether every class and interface `declaration` inherits
+    /// from is known.
+    fn resolved(&self, declaration: &Declaration) -> bool {
+        let mut pending = self.parents(&declaration.name);
+        let mut visited: Vec<Name> = vec![];
+        while let Some(name) = pending.pop() {
+            if visited.iter().any(|visited| visited.matches(&name)) {
+                continue;
+            }
+
+            match self.get(&name) {
+                Some(ancestor) => pending.extend(self.parents(&ancestor.name)),
+                None => return false,
+            }
+
+            visited.push(name);
+        }
+
+        true
+    }
+
+    fn check_override_attributes(
+        &self,
+        declaration: &Declaration,
+        location: &Location,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        for method in declaration.methods {
+            if has_override(method, &declaration.scope)
+                && self.overrides(declaration, &method.name) == Some(false)
+            {
+                diagnostics.push(Diagnostic::error(
+                    "invalid-override",
+                    &location.child(format!("method {}", method.name)),
+                    format!(
+                        "method `{}` has `#[\\Override]` but does not override or implement any inherited method",
+                        method.name
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn insert(&mut self, declaration: Declaration<'a>) {
+        let key = import::key(&declaration.name, SymbolKind::Class);
+        if !self.declarations.contains_key(&key) {
+            self.order.push(key.clone());
+        }
+
+        self.declarations.insert(key, declaration);
+    }
+
+    fn get(&self, name: &Name) -> Option<&Declaration<'a>> {
+        self.declarations.get(&import::key(name, SymbolKind::Class))
+    }
+
+    fn parents(&self, name: &Name) -> Vec<Name> {
+        if let Some(declaration) = self.get(name) {
+            return declaration
+                .parent
+                .iter()
+                .chain(&declaration.interfaces)
+                .cloned()
+                .collect();
+        }
+
+        BUILTIN_PARENTS
+            .iter()
+            .filter(|(child, _)| name.matches(&Name::fully_qualified(vec![*child])))
+            .map(|(_, parent)| Name::fully_qualified(vec![*parent]))
+            .collect()
+    }
+
+    /// The known classes and interfaces `declaration` inherits from, nearest
+    /// first.
+    fn ancestors(&self, declaration: &Declaration) -> Vec<&Declaration<'a>> {
+        let mut ancestors: Vec<&Declaration<'a>> = vec![];
+        let mut pending = self.parents(&declaration.name);
+        pending.reverse();
+
+        while let Some(name) = pending.pop() {
+            if let Some(ancestor) = self.get(&name) {
+                if ancestor.name.matches(&declaration.name)
+                    || ancestors
+                        .iter()
+                        .any(|known| known.name.matches(&ancestor.name))
+                {
+                    continue;
+                }
+
+                ancestors.push(ancestor);
+                let mut parents = self.parents(&ancestor.name);
+                parents.reverse();
+                pending.extend(parents);
+            }
+        }
+
+        ancestors
+    }
+
+    /// The methods a class-like declares itself or imports from its traits.
+    /// Its own methods take precedence over trait methods.
+    fn members<'b>(&'b self, declaration: &'b Declaration<'a>) -> Vec<Member<'b>> {
+        let mut members = declaration
+            .methods
+            .iter()
+            .map(|method| Member {
+                method,
+                source: declaration,
+                owner: declaration,
+            })
+            .collect::<Vec<Member>>();
+
+        let mut pending = declaration.traits.clone();
+        let mut visited: Vec<Name> = vec![];
+        while let Some(name) = pending.pop() {
+            if visited.iter().any(|visited| visited.matches(&name)) {
+                continue;
+            }
+
+            if let Some(r#trait) = self.get(&name) {
+                for method in r#trait.methods {
+                    if !members
+                        .iter()
+                        .any(|member| member.method.name.eq_ignore_ascii_case(&method.name))
+                    {
+                        members.push(Member {
+                            method,
+                            source: r#trait,
+                            owner: declaration,
+                        });
+                    }
+                }
+
+                pending.extend(r#trait.traits.iter().cloned());
+            }
+
+            visited.push(name);
+        }
+
+        members
+    }
+
+    fn check_implemented(
+        &self,
+        declaration: &Declaration,
+        location: &Location,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        // A missing ancestor or trait may implement anything.
+        let ancestors = self.ancestors(declaration);
+        let mut pending = std::iter::once(declaration)
+            .chain(ancestors.iter().copied())
+            .flat_map(|declaration| declaration.traits.iter().cloned())
+            .collect::<Vec<Name>>();
+        let mut visited: Vec<Name> = vec![];
+        while let Some(name) = pending.pop() {
+            if visited.iter().any(|visited| visited.matches(&name)) {
+                continue;
+            }
+
+            match self.get(&name) {
+                Some(r#trait) => pending.extend(r#trait.traits.iter().cloned()),
+                None => return,
+            }
+
+            visited.push(name);
+        }
+
+        if !self.resolved(declaration) {
+            return;
+        }
+
+        let members = std::iter::once(declaration)
+            .chain(ancestors.iter().copied())
+            .flat_map(|declaration| self.members(declaration))
+            .collect::<Vec<Member>>();
+
+        let mut reported: Vec<String> = vec![];
+        for member in &members {
+            let required = member.source.kind == Kind::Interface
+                || member.method.modifiers.contains(&Modifier::Abstract);
+            let name = member.method.name.to_ascii_lowercase();
+            if !required || reported.contains(&name) {
+                continue;
+            }
+
+            let implemented = members.iter().any(|candidate| {
+                candidate
+                    .method
+                    .name
+                    .eq_ignore_ascii_case(&member.method.name)
+                    && candidate.source.kind != Kind::Interface
+                    && !candidate.method.modifiers.contains(&Modifier::Abstract)
+            });
+
+            if !implemented {
+                reported.push(name);
+                diagnostics.push(Diagnostic::error(
+                    "unimplemented-method",
+                    location,
+                    format!(
+                        "{} `{}` must implement `{}::{}()` or be declared abstract",
+                        describe(declaration.kind),
+                        declaration.name.last(),
+                        member.source.name.last(),
+                        member.method.name,
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn check_override(
+        &self,
+        member: &Member,
+        inherited: &Member,
+        location: &Location,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let method = member.method;
+        let parent = inherited.method;
+        if parent.visibility == Some(VisibilityModifier::Private)
+            && !parent.modifiers.contains(&Modifier::Abstract)
+        {
+            return;
+        }
+
+        let overridden = format!("{}::{}()", inherited.owner.name.last(), parent.name);
+        if parent.modifiers.contains(&Modifier::Final) {
+            diagnostics.push(Diagnostic::error(
+                "final-method-override",
+                location,
+                format!(
+                    "method `{}` overrides final method `{}`",
+                    method.name, overridden
+                ),
+            ));
+        }
+
+        // Constructors are exempt from signature checks unless the parent
+        // declares them as a contract.
+        if method.name.eq_ignore_ascii_case("__construct")
+            && inherited.source.kind != Kind::Interface
+            && !parent.modifiers.contains(&Modifier::Abstract)
+        {
+            return;
+        }
+
+        let mut incompatible = |reason: String| {
+            diagnostics.push(Diagnostic::error(
+                "incompatible-signature",
+                location,
+                format!(
+                    "method `{}` is not compatible with `{}`: {}",
+                    method.name, overridden, reason
+                ),
+            ))
+        };
+
+        let variadic = method
+            .parameters
+            .iter()
+            .position(|parameter| parameter.variadic);
+        for (index, expected) in parent.parameters.iter().enumerate() {
+            let actual = match method.parameters.get(index) {
+                Some(actual) if !actual.variadic || variadic == Some(index) => actual,
+                _ => match variadic {
+                    Some(position) if position <= index => &method.parameters[position],
+                    _ => {
+                        incompatible(format!("parameter ${} is missing", expected.name));
+                        continue;
+                    }
+                },
+            };
+
+            if expected.variadic && !actual.variadic {
+                incompatible(format!("parameter ${} must be variadic", actual.name));
+            }
+
+            if expected.by_reference != actual.by_reference {
+                incompatible(format!(
+                    "parameter ${} must {}be passed by reference",
+                    actual.name,
+                    if expected.by_reference { "" } else { "not " }
+                ));
+            }
+
+            if is_optional(expected) && !is_optional(actual) {
+                incompatible(format!("parameter ${} must be optional", actual.name));
+            }
+
+            // Parameters are contravariant: the override must accept
+            // everything the parent accepts.
+            let accepted = self.resolve(expected.data_type.as_ref(), inherited, false);
+            let accepts = self.resolve(actual.data_type.as_ref(), member, false);
+            if !self.is_subtype(&accepted, &accepts) {
+                incompatible(format!(
+                    "parameter ${} of type `{}` does not accept `{}`",
+                    actual.name,
+                    print(actual.data_type.as_ref()),
+                    print(expected.data_type.as_ref()),
+                ));
+            }
+        }
+
+        for extra in method.parameters.iter().skip(parent.parameters.len()) {
+            if !is_optional(extra) {
+                incompatible(format!(
+                    "additional parameter ${} must be optional",
+                    extra.name
+                ));
+            }
+        }
+
+        // Return types are covariant: the override may only narrow them.
+        if let Some(expected) = &parent.return_type {
+            match &method.return_type {
+                None => incompatible(format!(
+                    "return type must be compatible with `{}`",
+                    expected.generate(Indentation::default(), 0)
+                )),
+                Some(actual) => {
+                    let returned = self.resolve(Some(actual), member, true);
+                    let allowed = self.resolve(Some(expected), inherited, false);
+                    if !self.is_subtype(&returned, &allowed) {
+                        incompatible(format!(
+                            "return type `{}` is not a subtype of `{}`",
+                            actual.generate(Indentation::default(), 0),
+                            expected.generate(Indentation::default(), 0)
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns whether `child` is a subtype of `parent`, giving the benefit
+    /// of the doubt to classes whose ancestors are not all known.
+    fn is_subtype(&self, child: &DataType, parent: &DataType) -> bool {
+        child.is_subtype_of(parent, &|child, parent| {
+            self.is_subclass(child, parent) || !self.is_known(child) || !self.is_known(parent)
+        })
+    }
+
+    /// Returns whether `name` and all of its ancestors are declared in the
+    /// hierarchy or are builtins.
+    fn is_known(&self, name: &Name) -> bool {
+        let builtin = |name: &Name| {
+            BUILTIN_PARENTS.iter().any(|(child, parent)| {
+                name.matches(&Name::fully_qualified(vec![*child]))
+                    || name.matches(&Name::fully_qualified(vec![*parent]))
+            })
+        };
+
+        let mut pending = vec![name.clone()];
+        let mut visited: Vec<Name> = vec![];
+        while let Some(name) = pending.pop() {
+            if visited.iter().any(|visited| visited.matches(&name)) {
+                continue;
+            }
+
+            if self.get(&name).is_none() && !builtin(&name) {
+                return false;
+            }
+
+            pending.extend(self.parents(&name));
+            visited.push(name);
+        }
+
+        true
+    }
+
+    /// Resolves class names in `data_type` to fully qualified names, with
+    /// `self` and `parent` replaced by the classes they refer to. A missing
+    /// type is `mixed`. When `narrowing`, `static` is also known to be a
+    /// subtype of the owning class.
+    fn resolve(&self, data_type: Option<&DataType>, member: &Member, narrowing: bool) -> DataType {
+        let Some(data_type) = data_type else {
+            return DataType::Mixed;
+        };
+
+        let resolve = |data_type| self.resolve(Some(data_type), member, narrowing);
+        match data_type {
+            DataType::Named(name) => DataType::Named(member.source.scope.resolve_class(name)),
+            DataType::SelfReference => DataType::Named(member.owner.name.clone()),
+            DataType::ParentReference => match &member.owner.parent {
+                Some(parent) => DataType::Named(parent.clone()),
+                None => DataType::ParentReference,
+            },
+            DataType::StaticReference if narrowing => DataType::Intersection(vec![
+                DataType::StaticReference,
+                DataType::Named(member.owner.name.clone()),
+            ]),
+            DataType::Nullable(inner) => DataType::Nullable(Box::new(resolve(inner))),
+            DataType::Union(types) => DataType::Union(types.iter().map(resolve).collect()),
+            DataType::Intersection(types) => {
+                DataType::Intersection(types.iter().map(resolve).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// Adds `#[\Override]` to every method in `files` that overrides or
+/// implements a method of a class or interface declared in `files` or
+/// `stubs`. Methods whose ancestors are not all known are left unchanged.
+pub fn add_override_attributes(files: &mut [File], stubs: &[File]) {
+    let overriding = Hierarchy::from_files(files).external(stubs).overriding();
+
+    for file in files.iter_mut() {
+        let scope = file.scope();
+        let declared = |name: &str| match &file.namespace {
+            Some(namespace) => {
+                format!("{}\\{}", namespace.unqualified(), name).to_ascii_lowercase()
+            }
+            None => name.to_ascii_lowercase(),
+        };
+
+        let class_likes = file
+            .classes
+            .iter_mut()
+            .map(|class| (declared(&class.name), &mut class.methods))
+            .chain(
+                file.interfaces
+                    .iter_mut()
+                    .map(|interface| (declared(&interface.name), &mut interface.methods)),
+            )
+            .chain(
+                file.enums
+                    .iter_mut()
+                    .map(|r#enum| (declared(&r#enum.name), &mut r#enum.methods)),
+            );
+
+        for (key, methods) in class_likes {
+            for (index, method) in methods.iter_mut().enumerate() {
+                if overriding.contains(&(key.clone(), index)) && !has_override(method, &scope) {
+                    method
+                        .attributes
+                        .push(AttributeGroup::new().add("\\Override", None));
+                }
+            }
+        }
+    }
+}
+
+fn has_override(method: &Method, scope: &Scope) -> bool {
+    let attribute = Name::new("\\Override");
+
+    method
+        .attributes
+        .iter()
+        .flat_map(|group| &group.members)
+        .any(|(name, _)| scope.resolve_class(name).matches(&attribute))
+}
+
+fn is_optional(parameter: &Parameter) -> bool {
+    parameter.default.is_some() || parameter.variadic
+}
+
+fn print(data_type: Option<&DataType>) -> String {
+    match data_type {
+        Some(data_type) => data_type.generate(Indentation::default(), 0),
+        None => "mixed".to_string(),
+    }
+}
+
+fn describe(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Class => "class",
+        Kind::Interface => "interface",
+        Kind::Trait => "trait",
+        Kind::Enum => "enum",
+    }
+}
@@ -1,15 +1,21 @@
 use crate::attribute::AttributeGroup;
 use crate::comment::Document;
+use crate::identifier::Context;
 use crate::method::Method;
+use crate::name::Name;
+use crate::naming::Naming;
+use crate::ordering;
+use crate::style::Style;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Interface {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
     pub name: String,
-    pub extends: Option<String>,
+    pub original_name: Option<String>,
+    pub extends: Option<Name>,
     pub methods: Vec<Method>,
 }
 
@@ -19,11 +25,20 @@ impl Interface {
             documentation: None,
             attributes: vec![],
             name: name.to_string(),
+            original_name: None,
             extends: None,
             methods: vec![],
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut interface = Self::new(naming.apply(&original, Context::Class));
+        interface.original_name = Some(original);
+
+        interface
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -36,8 +51,8 @@ impl Interface {
         self
     }
 
-    pub fn extends<T: ToString>(mut self, extends: T) -> Self {
-        self.extends = Some(extends.to_string());
+    pub fn extends<T: Into<Name>>(mut self, extends: T) -> Self {
+        self.extends = Some(extends.into());
 
         self
     }
@@ -51,6 +66,10 @@ impl Interface {
 
 impl Generator for Interface {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
         if let Some(documentation) = &self.documentation {
@@ -67,9 +86,14 @@ impl Generator for Interface {
             code.push_str(&format!(" extends {}", extends));
         }
 
-        code.push_str("\n{\n");
+        code.push_str(&style.open(style.class_braces, indentation, level));
 
-        code.push_str(self.methods.generate(indentation, level + 1).as_str());
+        code.push_str(&ordering::interface_members(
+            &self.methods,
+            style,
+            indentation,
+            level + 1,
+        ));
 
         code = code.trim_end().to_string();
         code.push_str("\n}\n");
@@ -80,13 +104,21 @@ impl Generator for Interface {
 
 impl Generator for Vec<Interface> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
         if self.is_empty() {
             return code;
         }
 
         for interface in self {
-            code.push_str(interface.generate(indentation, level).as_str());
+            code.push_str(
+                interface
+                    .generate_styled(style, indentation, level)
+                    .as_str(),
+            );
             code.push('\n');
         }
 
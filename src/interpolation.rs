@@ -0,0 +1,186 @@
+use crate::literal::Value;
+use crate::Generator;
+use crate::Indentation;
+
+#[derive(Debug, Clone)]
+pub enum Expression {
+    Variable(String),
+    Property(Box<Expression>, String),
+    Offset(Box<Expression>, Value),
+    MethodCall(Box<Expression>, String, Vec<Value>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Segment {
+    Literal(String),
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone)]
+pub struct InterpolatedString {
+    pub segments: Vec<Segment>,
+    pub heredoc: Option<String>,
+}
+
+impl Expression {
+    pub fn variable<T: ToString>(name: T) -> Self {
+        Expression::Variable(name.to_string())
+    }
+
+    pub fn property<T: ToString>(self, property: T) -> Self {
+        Expression::Property(Box::new(self), property.to_string())
+    }
+
+    pub fn offset<T: Into<Value>>(self, offset: T) -> Self {
+        Expression::Offset(Box::new(self), offset.into())
+    }
+
+    pub fn call<T: ToString>(self, method: T, arguments: Vec<Value>) -> Self {
+        Expression::MethodCall(Box::new(self), method.to_string(), arguments)
+    }
+}
+
+impl InterpolatedString {
+    pub fn new() -> Self {
+        Self {
+            segments: vec![],
+            heredoc: None,
+        }
+    }
+
+    pub fn literal<T: ToString>(mut self, literal: T) -> Self {
+        self.segments.push(Segment::Literal(literal.to_string()));
+
+        self
+    }
+
+    pub fn expression(mut self, expression: Expression) -> Self {
+        self.segments.push(Segment::Expression(expression));
+
+        self
+    }
+
+    pub fn variable<T: ToString>(self, name: T) -> Self {
+        self.expression(Expression::variable(name))
+    }
+
+    pub fn heredoc<T: ToString>(mut self, label: T) -> Self {
+        self.heredoc = Some(label.to_string());
+
+        self
+    }
+
+    /// Returns the heredoc label, suffixed as needed so that no literal
+    /// line can be mistaken for the closing marker.
+    fn heredoc_label(&self, label: &str) -> String {
+        let literal = self
+            .segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Literal(literal) => Some(literal.as_str()),
+                Segment::Expression(_) => None,
+            })
+            .collect::<Vec<&str>>()
+            .join("");
+
+        let mut candidate = label.to_string();
+        let mut suffix = 0;
+        while literal.lines().any(|line| closes_heredoc(line, &candidate)) {
+            suffix += 1;
+            candidate = format!("{}_{}", label, suffix);
+        }
+
+        candidate
+    }
+}
+
+fn closes_heredoc(line: &str, label: &str) -> bool {
+    match line.trim_start().strip_prefix(label) {
+        Some(rest) => !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'),
+        None => false,
+    }
+}
+
+pub(crate) fn escape(literal: &str, heredoc: bool) -> String {
+    let mut result = String::with_capacity(literal.len());
+
+    for character in literal.chars() {
+        match character {
+            '\\' => result.push_str("\\\\"),
+            '$' => result.push_str("\\$"),
+            '"' if !heredoc => result.push_str("\\\""),
+            '\n' if heredoc => result.push('\n'),
+            '\t' if heredoc => result.push('\t'),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            '\u{0B}' => result.push_str("\\v"),
+            '\u{0C}' => result.push_str("\\f"),
+            '\u{1B}' => result.push_str("\\e"),
+            character if character.is_ascii_control() => {
+                result.push_str(&format!("\\x{:02X}", character as u32))
+            }
+            character => result.push(character),
+        }
+    }
+
+    result
+}
+
+impl Generator for Expression {
+    fn generate(&self, indentation: Indentation, level: usize) -> String {
+        match self {
+            Expression::Variable(name) => format!("${}", name),
+            Expression::Property(target, property) => {
+                format!("{}->{}", target.generate(indentation, level), property)
+            }
+            Expression::Offset(target, offset) => format!(
+                "{}[{}]",
+                target.generate(indentation, level),
+                offset.generate(indentation, level)
+            ),
+            Expression::MethodCall(target, method, arguments) => format!(
+                "{}->{}({})",
+                target.generate(indentation, level),
+                method,
+                arguments
+                    .iter()
+                    .map(|argument| argument.generate(indentation, level))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl Generator for InterpolatedString {
+    fn generate(&self, indentation: Indentation, level: usize) -> String {
+        let heredoc = self.heredoc.is_some();
+        let content = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => escape(literal, heredoc),
+                Segment::Expression(expression) => {
+                    format!("{{{}}}", expression.generate(indentation, level))
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        match &self.heredoc {
+            Some(label) => {
+                let label = self.heredoc_label(label);
+
+                format!("<<<{}\n{}\n{}", label, content, label)
+            }
+            None => format!("\"{}\"", content),
+        }
+    }
+}
+
+impl Default for InterpolatedString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
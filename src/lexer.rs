@@ -0,0 +1,470 @@
+use std::fmt::Display;
+
+/// Operators and punctuation, longest first so that the longest match wins.
+const SYMBOLS: [&str; 36] = [
+    "<<=", ">>=", "**=", "...", "<=>", "===", "!==", "??=", "?->", "#[", "::", "->", "=>", "++",
+    "--", "==", "!=", "<>", "<=", ">=", "&&", "||", "??", "+=", "-=", "*=", "/=", ".=", "%=", "&=",
+    "|=", "^=", "<<", ">>", "**", "$",
+];
+
+const PUNCTUATION: &str = ";:,.()[]{}+-*/%=<>!?&|^~@";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TokenKind {
+    InlineHtml,
+    OpenTag,
+    CloseTag,
+    Whitespace,
+    Comment,
+    DocComment,
+    Variable,
+    /// An identifier, keyword or qualified name such as `\App\User`.
+    Name,
+    Integer,
+    Float,
+    /// A single-quoted, double-quoted or backtick string.
+    String,
+    /// A heredoc or nowdoc, including its closing label.
+    Heredoc,
+    Symbol,
+}
+
+/// A token and the 1-based line and column where it starts.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyntaxError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Splits PHP code into tokens. Code that does not start with an opening tag
+/// is read as PHP, so fragments such as a generated class can be tokenized
+/// on their own.
+pub fn tokenize(code: &str) -> Result<Vec<Token>, SyntaxError> {
+    let mut lexer = Lexer {
+        chars: code.chars().collect(),
+        position: 0,
+        line: 1,
+        column: 1,
+        tokens: vec![],
+    };
+
+    let mut php = !code.trim_start().starts_with("<?");
+    while lexer.position < lexer.chars.len() {
+        if php {
+            php = lexer.token()? != TokenKind::CloseTag;
+        } else {
+            lexer.html();
+            php = true;
+        }
+    }
+
+    Ok(lexer.tokens)
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    position: usize,
+    line: usize,
+    column: usize,
+    tokens: Vec<Token>,
+}
+
+impl Lexer {
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(offset, c)| self.peek(offset) == Some(c))
+    }
+
+    fn starts_with_ignore_case(&self, text: &str) -> bool {
+        text.chars().enumerate().all(|(offset, c)| {
+            self.peek(offset)
+                .is_some_and(|actual| actual.eq_ignore_ascii_case(&c))
+        })
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.position += 1;
+
+        // A `\r` ends a line unless a `\n` follows it.
+        if c == '\n' || (c == '\r' && self.peek(0) != Some('\n')) {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    /// Consumes a `\n`, `\r\n` or `\r` line ending.
+    fn newline(&mut self) -> bool {
+        match self.peek(0) {
+            Some('\n') => self.bump().is_some(),
+            Some('\r') => {
+                self.bump();
+                if self.peek(0) == Some('\n') {
+                    self.bump();
+                }
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn bump_n(&mut self, count: usize) {
+        for _ in 0..count {
+            self.bump();
+        }
+    }
+
+    fn bump_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek(0).is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    fn error<T: ToString>(&self, message: T, line: usize, column: usize) -> SyntaxError {
+        SyntaxError {
+            message: message.to_string(),
+            line,
+            column,
+        }
+    }
+
+    fn push(&mut self, kind: TokenKind, start: (usize, usize, usize)) -> TokenKind {
+        let (position, line, column) = start;
+        self.tokens.push(Token {
+            kind,
+            text: self.chars[position..self.position].iter().collect(),
+            line,
+            column,
+        });
+
+        kind
+    }
+
+    /// Reads inline HTML up to and including the next opening tag.
+    fn html(&mut self) {
+        let start = (self.position, self.line, self.column);
+        while self.position < self.chars.len() && !self.starts_with("<?") {
+            self.bump();
+        }
+
+        if self.position > start.0 {
+            self.push(TokenKind::InlineHtml, start);
+        }
+
+        let start = (self.position, self.line, self.column);
+        if self.starts_with_ignore_case("<?php") {
+            self.bump_n(5);
+        } else if self.starts_with("<?=") {
+            self.bump_n(3);
+        } else {
+            self.bump_n(2);
+        }
+
+        if self.position > start.0 {
+            self.push(TokenKind::OpenTag, start);
+        }
+    }
+
+    fn token(&mut self) -> Result<TokenKind, SyntaxError> {
+        let start = (self.position, self.line, self.column);
+        let (_, line, column) = start;
+        let c = self.peek(0).unwrap_or_default();
+
+        let kind = if c.is_whitespace() {
+            self.bump_while(char::is_whitespace);
+
+            TokenKind::Whitespace
+        } else if self.starts_with("?>") {
+            self.bump_n(2);
+            self.newline();
+
+            TokenKind::CloseTag
+        } else if self.starts_with_ignore_case("<?php") || self.starts_with("<?=") {
+            return Err(self.error("unexpected opening tag", line, column));
+        } else if self.starts_with("#[") {
+            self.bump_n(2);
+
+            TokenKind::Symbol
+        } else if c == '#' || self.starts_with("//") {
+            while self.position < self.chars.len()
+                && !matches!(self.peek(0), Some('\n' | '\r'))
+                && !self.starts_with("?>")
+            {
+                self.bump();
+            }
+
+            TokenKind::Comment
+        } else if self.starts_with("/*") {
+            let documentation = self.starts_with("/**") && !self.starts_with("/**/");
+            self.bump_n(2);
+            while !self.starts_with("*/") {
+                if self.bump().is_none() {
+                    return Err(self.error("unterminated comment", line, column));
+                }
+            }
+
+            self.bump_n(2);
+            if documentation {
+                TokenKind::DocComment
+            } else {
+                TokenKind::Comment
+            }
+        } else if c == '$' && self.peek(1).is_some_and(is_label_start) {
+            self.bump();
+            self.bump_while(is_label);
+
+            TokenKind::Variable
+        } else if c == '$' && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error("invalid variable name", line, column));
+        } else if c == '\'' {
+            self.single_quoted(line, column)?;
+
+            TokenKind::String
+        } else if c == '"' || c == '`' {
+            self.double_quoted(c, line, column)?;
+
+            TokenKind::String
+        } else if self.starts_with("<<<") {
+            self.heredoc(line, column)?;
+
+            TokenKind::Heredoc
+        } else if c.is_ascii_digit()
+            || (c == '.' && self.peek(1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            self.number(line, column)?
+        } else if is_label_start(c) || c == '\\' {
+            self.name(line, column)?;
+
+            TokenKind::Name
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| self.starts_with(symbol)) {
+            self.bump_n(symbol.chars().count());
+
+            TokenKind::Symbol
+        } else if PUNCTUATION.contains(c) {
+            self.bump();
+
+            TokenKind::Symbol
+        } else {
+            return Err(self.error(
+                format!("unexpected character `{}`", c.escape_default()),
+                line,
+                column,
+            ));
+        };
+
+        Ok(self.push(kind, start))
+    }
+
+    fn single_quoted(&mut self, line: usize, column: usize) -> Result<(), SyntaxError> {
+        self.bump();
+
+        loop {
+            match self.bump() {
+                None => return Err(self.error("unterminated string", line, column)),
+                Some('\\') => {
+                    self.bump();
+                }
+                Some('\'') => return Ok(()),
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Reads a double-quoted or backtick string, skipping over `{$...}`
+    /// interpolations that may themselves contain strings.
+    fn double_quoted(
+        &mut self,
+        quote: char,
+        line: usize,
+        column: usize,
+    ) -> Result<(), SyntaxError> {
+        self.bump();
+
+        loop {
+            match self.peek(0) {
+                None => return Err(self.error("unterminated string", line, column)),
+                Some('\\') => self.bump_n(2),
+                Some('{') if self.peek(1) == Some('$') => self.interpolation(line, column)?,
+                Some(c) => {
+                    self.bump();
+                    if c == quote {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    fn interpolation(&mut self, line: usize, column: usize) -> Result<(), SyntaxError> {
+        self.bump();
+
+        let mut depth = 1;
+        while depth > 0 {
+            match self.peek(0) {
+                None => return Err(self.error("unterminated string", line, column)),
+                Some('\'') => {
+                    let (line, column) = (self.line, self.column);
+                    self.single_quoted(line, column)?;
+                }
+                Some('"') => {
+                    let (line, column) = (self.line, self.column);
+                    self.double_quoted('"', line, column)?;
+                }
+                Some(c) => {
+                    self.bump();
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn heredoc(&mut self, line: usize, column: usize) -> Result<(), SyntaxError> {
+        self.bump_n(3);
+        self.bump_while(|c| c == ' ' || c == '\t');
+
+        let quote = match self.peek(0) {
+            Some(quote @ ('\'' | '"')) => {
+                self.bump();
+                Some(quote)
+            }
+            _ => None,
+        };
+
+        let label_start = self.position;
+        if !self.peek(0).is_some_and(is_label_start) {
+            return Err(self.error("invalid heredoc label", line, column));
+        }
+
+        self.bump_while(is_label);
+        let label: String = self.chars[label_start..self.position].iter().collect();
+
+        if let Some(quote) = quote {
+            if self.bump() != Some(quote) {
+                return Err(self.error("invalid heredoc label", line, column));
+            }
+        }
+
+        if !self.newline() {
+            return Err(self.error("expected a new line after the heredoc label", line, column));
+        }
+
+        loop {
+            self.bump_while(|c| c == ' ' || c == '\t');
+            if self.starts_with(&label) && !self.peek(label.chars().count()).is_some_and(is_label) {
+                self.bump_n(label.chars().count());
+
+                return Ok(());
+            }
+
+            while self.peek(0).is_some_and(|c| c != '\n' && c != '\r') {
+                self.bump();
+            }
+
+            if !self.newline() {
+                return Err(self.error(
+                    format!("unterminated heredoc, expected `{}`", label),
+                    line,
+                    column,
+                ));
+            }
+        }
+    }
+
+    fn number(&mut self, line: usize, column: usize) -> Result<TokenKind, SyntaxError> {
+        let mut kind = TokenKind::Integer;
+        let prefix = self.peek(1).map(|c| c.to_ascii_lowercase());
+
+        if self.peek(0) == Some('0') && matches!(prefix, Some('x' | 'b' | 'o')) {
+            self.bump_n(2);
+            self.bump_while(|c| c.is_ascii_hexdigit() || c == '_');
+        } else {
+            self.bump_while(|c| c.is_ascii_digit() || c == '_');
+
+            if self.peek(0) == Some('.') && self.peek(1) != Some('.') {
+                kind = TokenKind::Float;
+                self.bump();
+                self.bump_while(|c| c.is_ascii_digit() || c == '_');
+            }
+
+            let exponent = self.peek(0).is_some_and(|c| c == 'e' || c == 'E');
+            let signed = self.peek(1).is_some_and(|c| c == '+' || c == '-');
+            let digit = self.peek(if signed { 2 } else { 1 });
+            if exponent && digit.is_some_and(|c| c.is_ascii_digit()) {
+                kind = TokenKind::Float;
+                self.bump_n(if signed { 2 } else { 1 });
+                self.bump_while(|c| c.is_ascii_digit());
+            }
+        }
+
+        if self.peek(0).is_some_and(is_label) {
+            return Err(self.error("invalid numeric literal", line, column));
+        }
+
+        Ok(kind)
+    }
+
+    /// Reads a name. A prefix such as `App\` is allowed before the `{` of a
+    /// group use statement.
+    fn name(&mut self, line: usize, column: usize) -> Result<(), SyntaxError> {
+        let start = self.position;
+        loop {
+            if self.peek(0) == Some('\\') {
+                self.bump();
+
+                if self.position > start + 1 && self.peek(0) == Some('{') {
+                    return Ok(());
+                }
+            }
+
+            if !self.peek(0).is_some_and(is_label_start) {
+                return Err(self.error("expected an identifier after `\\`", line, column));
+            }
+
+            self.bump_while(is_label);
+            if self.peek(0) != Some('\\') {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn is_label_start(c: char) -> bool {
+    c == '_' || c.is_ascii_alphabetic() || c as u32 >= 0x80
+}
+
+fn is_label(c: char) -> bool {
+    is_label_start(c) || c.is_ascii_digit()
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
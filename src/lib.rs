@@ -4,18 +4,40 @@ pub mod class;
 pub mod comment;
 pub mod constant;
 pub mod data_type;
+pub mod downlevel;
 pub mod r#enum;
 pub mod enum_case;
 pub mod file;
 pub mod function;
+pub mod identifier;
+pub mod import;
+pub mod inheritance;
 pub mod interface;
+pub mod interpolation;
+pub mod lexer;
+pub mod lint;
 pub mod literal;
+pub mod location;
 pub mod method;
 pub mod modifiers;
+pub mod name;
+pub mod naming;
+pub mod ordering;
 pub mod parameter;
+pub mod parser;
 pub mod property;
+pub mod rename;
+pub mod style;
+pub mod symbols;
+pub mod template;
 pub mod r#trait;
 pub mod usage;
+pub mod validation;
+pub mod version;
+
+mod visitor;
+
+use crate::style::Style;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Clone, Copy)]
 pub enum Indentation {
@@ -31,7 +53,7 @@ impl Indentation {
     pub fn indent<T: ToString>(&self, code: T, level: usize) -> String {
         let indentation = self.value(level);
 
-        code.to_string()
+        style::normalize(&code.to_string())
             .lines()
             .map(|line| format!("{}{}", indentation, line.trim_end()))
             .collect::<Vec<String>>()
@@ -56,4 +78,10 @@ impl Default for Indentation {
 
 pub trait Generator {
     fn generate(&self, indentation: Indentation, level: usize) -> String;
+
+    /// Generates code laid out in `style`. The default ignores the style, for
+    /// nodes whose layout it does not affect.
+    fn generate_styled(&self, _style: &Style, indentation: Indentation, level: usize) -> String {
+        self.generate(indentation, level)
+    }
 }
@@ -0,0 +1,80 @@
+use crate::lexer::tokenize;
+use crate::lexer::SyntaxError;
+use crate::lexer::TokenKind;
+use crate::Generator;
+use crate::Indentation;
+
+/// Checks that `code` tokenizes cleanly and that its braces, brackets and
+/// parentheses are balanced.
+pub fn lint(code: &str) -> Vec<SyntaxError> {
+    let tokens = match tokenize(code) {
+        Ok(tokens) => tokens,
+        Err(error) => return vec![error],
+    };
+
+    let mut errors = vec![];
+    let mut open = vec![];
+    for token in tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Symbol)
+    {
+        match token.text.as_str() {
+            "(" | "[" | "{" | "#[" => open.push(token),
+            ")" | "]" | "}" => match open.pop() {
+                Some(opening) if closer(&opening.text) == token.text => {}
+                Some(opening) => errors.push(SyntaxError {
+                    message: format!(
+                        "unexpected `{}`, expected `{}` to close `{}` at {}:{}",
+                        token.text,
+                        closer(&opening.text),
+                        opening.text,
+                        opening.line,
+                        opening.column
+                    ),
+                    line: token.line,
+                    column: token.column,
+                }),
+                None => errors.push(SyntaxError {
+                    message: format!("unexpected `{}`", token.text),
+                    line: token.line,
+                    column: token.column,
+                }),
+            },
+            _ => {}
+        }
+    }
+
+    for opening in open {
+        errors.push(SyntaxError {
+            message: format!("unclosed `{}`", opening.text),
+            line: opening.line,
+            column: opening.column,
+        });
+    }
+
+    errors
+}
+
+/// Generates `node`, returning the code only if it passes [`lint`].
+pub fn check<T: Generator + ?Sized>(
+    node: &T,
+    indentation: Indentation,
+    level: usize,
+) -> Result<String, Vec<SyntaxError>> {
+    let code = node.generate(indentation, level);
+    let errors = lint(&code);
+
+    if errors.is_empty() {
+        Ok(code)
+    } else {
+        Err(errors)
+    }
+}
+
+fn closer(opening: &str) -> &'static str {
+    match opening {
+        "(" => ")",
+        "{" => "}",
+        _ => "]",
+    }
+}
@@ -1,7 +1,8 @@
+use crate::interpolation::InterpolatedString;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Value {
     Null,
     True,
@@ -10,6 +11,7 @@ pub enum Value {
     Integer(i64),
     String(String),
     Literal(String),
+    Interpolated(InterpolatedString),
     List(Vec<Value>),
     HashMap(Vec<(Value, Value)>),
 }
@@ -48,6 +50,12 @@ impl From<&str> for Value {
     }
 }
 
+impl From<InterpolatedString> for Value {
+    fn from(value: InterpolatedString) -> Self {
+        Value::Interpolated(value)
+    }
+}
+
 impl From<()> for Value {
     fn from(_: ()) -> Self {
         Value::Null
@@ -81,6 +89,7 @@ impl Generator for Value {
             Value::String(value) => format!("\"{}\"", value),
             Value::Float(value) => value.to_string(),
             Value::Literal(value) => value.to_string(),
+            Value::Interpolated(value) => value.generate(_identation, _level),
             Value::List(values) => {
                 let mut result = String::new();
 
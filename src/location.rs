@@ -0,0 +1,26 @@
+use std::fmt::Display;
+
+/// The path to a node in a model, such as `File > Class Foo > method bar`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Location {
+    pub segments: Vec<String>,
+}
+
+impl Location {
+    pub fn new() -> Self {
+        Self { segments: vec![] }
+    }
+
+    pub fn child<T: ToString>(&self, segment: T) -> Self {
+        let mut segments = self.segments.clone();
+        segments.push(segment.to_string());
+
+        Self { segments }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.segments.join(" > "))
+    }
+}
@@ -4,20 +4,28 @@ use crate::attribute::AttributeGroup;
 use crate::body::Body;
 use crate::comment::Document;
 use crate::data_type::DataType;
+use crate::identifier::Context;
+use crate::modifiers;
 use crate::modifiers::Modifier;
 use crate::modifiers::VisibilityModifier;
+use crate::naming::Naming;
+use crate::parameter;
 use crate::parameter::Parameter;
+use crate::style::BracePlacement;
+use crate::style::Style;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Method {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
     pub name: String,
+    pub original_name: Option<String>,
     pub parameters: Vec<Parameter>,
     pub return_type: Option<DataType>,
     pub body: Body,
+    pub trailing_comma: bool,
     pub modifiers: Vec<Modifier>,
     pub visibility: Option<VisibilityModifier>,
 }
@@ -26,9 +34,11 @@ impl Method {
     pub fn new<T: ToString>(name: T) -> Self {
         Self {
             name: name.to_string(),
+            original_name: None,
             parameters: vec![],
             return_type: None,
             body: Body::default(),
+            trailing_comma: true,
             modifiers: vec![],
             attributes: vec![],
             documentation: None,
@@ -36,6 +46,14 @@ impl Method {
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut method = Self::new(naming.apply(&original, Context::Method));
+        method.original_name = Some(original);
+
+        method
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -99,6 +117,10 @@ impl Method {
 
 impl Generator for Method {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
         if let Some(document) = &self.documentation {
@@ -110,31 +132,44 @@ impl Generator for Method {
         }
 
         code.push_str(&indentation.value(level));
-        if let Some(visibility) = &self.visibility {
-            code.push_str(format!("{} ", visibility.generate(indentation, level)).as_str());
-        }
-
-        if !self.modifiers.is_empty() {
-            code.push_str(
-                &self
-                    .modifiers
-                    .iter()
-                    .map(|modifier| modifier.generate(indentation, level))
-                    .collect::<Vec<String>>()
-                    .join(" "),
-            );
-
-            code.push(' ');
-        }
+        code.push_str(&modifiers::print(self.visibility.as_ref(), &self.modifiers));
 
         code.push_str(format!("function {}", self.name).as_str());
-        code.push_str(&self.parameters.generate(indentation, level));
-
-        if let Some(return_type) = &self.return_type {
-            code.push_str(format!(": {}", return_type.generate(indentation, level)).as_str());
-        }
-
-        code.push_str(&self.body.generate(indentation, level));
+        let return_type = self
+            .return_type
+            .as_ref()
+            .map(|return_type| {
+                format!(
+                    ": {}",
+                    return_type.generate_styled(style, indentation, level)
+                )
+            })
+            .unwrap_or_default();
+        let brace = match style.method_braces {
+            BracePlacement::SameLine => 2,
+            BracePlacement::NextLine => 0,
+        };
+
+        let parameters = parameter::print(
+            &self.parameters,
+            self.trailing_comma,
+            style,
+            (parameter::width(&code), return_type.chars().count() + brace),
+            indentation,
+            level,
+        );
+
+        // Multi-line parameter lists keep the brace on the closing line.
+        let braces = if parameters.contains('\n') {
+            BracePlacement::SameLine
+        } else {
+            style.method_braces
+        };
+
+        code.push_str(&parameters);
+        code.push_str(&return_type);
+
+        code.push_str(&self.body.print(braces, style, indentation, level));
 
         code
     }
@@ -142,13 +177,17 @@ impl Generator for Method {
 
 impl Generator for Vec<Method> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
         if self.is_empty() {
             return code;
         }
 
         for method in self {
-            code.push_str(method.generate(indentation, level).as_str());
+            code.push_str(method.generate_styled(style, indentation, level).as_str());
             code.push('\n');
         }
 
@@ -1,7 +1,9 @@
+use std::fmt::Display;
+
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Modifier {
     Abstract,
     Final,
@@ -9,7 +11,7 @@ pub enum Modifier {
     Static,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum VisibilityModifier {
     Public,
     Protected,
@@ -36,3 +38,215 @@ impl Generator for VisibilityModifier {
         }
     }
 }
+
+/// The kinds of declaration that modifiers can be attached to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ModifierSite {
+    Class,
+    Property,
+    Method,
+    /// A `__construct` method of a class or trait.
+    Constructor,
+    TraitMethod,
+    InterfaceMethod,
+    EnumMethod,
+    ClassConstant,
+    InterfaceConstant,
+    EnumConstant,
+    PromotedParameter,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ModifierError {
+    Duplicate {
+        modifier: String,
+    },
+    NotAllowed {
+        modifier: String,
+        site: ModifierSite,
+    },
+    Conflict {
+        first: String,
+        second: String,
+        site: ModifierSite,
+    },
+}
+
+impl Modifier {
+    fn rank(&self) -> usize {
+        match self {
+            Modifier::Abstract | Modifier::Final => 0,
+            Modifier::Static => 2,
+            Modifier::Readonly => 3,
+        }
+    }
+}
+
+/// Prints modifiers in PER coding style order: `abstract` or `final`, then
+/// the visibility, then `static`, then `readonly`. The result ends with a
+/// space unless it is empty.
+pub(crate) fn print(visibility: Option<&VisibilityModifier>, modifiers: &[Modifier]) -> String {
+    let mut keywords = modifiers
+        .iter()
+        .map(|modifier| {
+            (
+                modifier.rank(),
+                modifier.generate(Indentation::default(), 0),
+            )
+        })
+        .collect::<Vec<(usize, String)>>();
+
+    if let Some(visibility) = visibility {
+        keywords.push((1, visibility.generate(Indentation::default(), 0)));
+    }
+
+    keywords.sort_by_key(|(rank, _)| *rank);
+
+    keywords
+        .into_iter()
+        .map(|(_, keyword)| format!("{} ", keyword))
+        .collect()
+}
+
+/// Checks a combination of modifiers against the rules PHP applies at `site`.
+pub fn validate(
+    site: ModifierSite,
+    visibility: Option<&VisibilityModifier>,
+    modifiers: &[Modifier],
+) -> Result<(), ModifierError> {
+    let keyword = |modifier: &Modifier| modifier.generate(Indentation::default(), 0);
+
+    for (index, modifier) in modifiers.iter().enumerate() {
+        if modifiers[..index].contains(modifier) {
+            return Err(ModifierError::Duplicate {
+                modifier: keyword(modifier),
+            });
+        }
+    }
+
+    let allowed: &[Modifier] = match site {
+        ModifierSite::Class => &[Modifier::Abstract, Modifier::Final, Modifier::Readonly],
+        ModifierSite::Property => &[
+            Modifier::Abstract,
+            Modifier::Final,
+            Modifier::Static,
+            Modifier::Readonly,
+        ],
+        ModifierSite::Method | ModifierSite::TraitMethod => {
+            &[Modifier::Abstract, Modifier::Final, Modifier::Static]
+        }
+        ModifierSite::Constructor => &[Modifier::Abstract, Modifier::Final],
+        ModifierSite::EnumMethod => &[Modifier::Final, Modifier::Static],
+        ModifierSite::InterfaceMethod => &[Modifier::Static],
+        ModifierSite::ClassConstant
+        | ModifierSite::InterfaceConstant
+        | ModifierSite::EnumConstant => &[Modifier::Final],
+        ModifierSite::PromotedParameter => &[Modifier::Readonly],
+    };
+
+    if let Some(modifier) = modifiers
+        .iter()
+        .find(|modifier| !allowed.contains(modifier))
+    {
+        return Err(ModifierError::NotAllowed {
+            modifier: keyword(modifier),
+            site,
+        });
+    }
+
+    let visibility_allowed = match site {
+        ModifierSite::Class => false,
+        ModifierSite::InterfaceMethod | ModifierSite::InterfaceConstant => {
+            matches!(visibility, None | Some(VisibilityModifier::Public))
+        }
+        _ => true,
+    };
+
+    if let (Some(visibility), false) = (visibility, visibility_allowed) {
+        return Err(ModifierError::NotAllowed {
+            modifier: visibility.generate(Indentation::default(), 0),
+            site,
+        });
+    }
+
+    let private = matches!(visibility, Some(VisibilityModifier::Private));
+    let has = |modifier: Modifier| modifiers.contains(&modifier);
+    let conflict = match site {
+        _ if has(Modifier::Abstract) && has(Modifier::Final) => Some(("abstract", "final")),
+        ModifierSite::Property if has(Modifier::Static) && has(Modifier::Readonly) => {
+            Some(("static", "readonly"))
+        }
+        ModifierSite::Property if has(Modifier::Static) && has(Modifier::Abstract) => {
+            Some(("abstract", "static"))
+        }
+        ModifierSite::Property | ModifierSite::Method | ModifierSite::Constructor
+            if private && has(Modifier::Abstract) =>
+        {
+            Some(("abstract", "private"))
+        }
+        // Private constructors can be final to stop child classes
+        // redeclaring them; other private members can never be overridden.
+        ModifierSite::Property
+        | ModifierSite::Method
+        | ModifierSite::TraitMethod
+        | ModifierSite::EnumMethod
+        | ModifierSite::ClassConstant
+        | ModifierSite::EnumConstant
+            if private && has(Modifier::Final) =>
+        {
+            Some(("final", "private"))
+        }
+        _ => None,
+    };
+
+    match conflict {
+        Some((first, second)) => Err(ModifierError::Conflict {
+            first: first.to_string(),
+            second: second.to_string(),
+            site,
+        }),
+        None => Ok(()),
+    }
+}
+
+impl Display for ModifierSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModifierSite::Class => write!(f, "classes"),
+            ModifierSite::Property => write!(f, "properties"),
+            ModifierSite::Method => write!(f, "methods"),
+            ModifierSite::Constructor => write!(f, "constructors"),
+            ModifierSite::TraitMethod => write!(f, "trait methods"),
+            ModifierSite::InterfaceMethod => write!(f, "interface methods"),
+            ModifierSite::EnumMethod => write!(f, "enum methods"),
+            ModifierSite::ClassConstant => write!(f, "class constants"),
+            ModifierSite::InterfaceConstant => write!(f, "interface constants"),
+            ModifierSite::EnumConstant => write!(f, "enum constants"),
+            ModifierSite::PromotedParameter => write!(f, "promoted parameters"),
+        }
+    }
+}
+
+impl Display for ModifierError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModifierError::Duplicate { modifier } => {
+                write!(f, "`{}` modifier is used more than once", modifier)
+            }
+            ModifierError::NotAllowed { modifier, site } => {
+                write!(f, "`{}` modifier is not allowed on {}", modifier, site)
+            }
+            ModifierError::Conflict {
+                first,
+                second,
+                site,
+            } => write!(
+                f,
+                "`{}` and `{}` modifiers cannot be combined on {}",
+                first, second, site
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ModifierError {}
@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::Generator;
+use crate::Indentation;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum NameKind {
+    Unqualified,
+    Qualified,
+    FullyQualified,
+    Relative,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SymbolKind {
+    Class,
+    Function,
+    Constant,
+}
+
+/// A PHP symbol name, such as `Foo`, `Foo\Bar`, `\Foo\Bar` or `namespace\Foo`.
+///
+/// Converting from a string never fails; use `Name::parse` to reject names
+/// that are not valid PHP.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Name {
+    pub kind: NameKind,
+    pub parts: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NameError {
+    Empty,
+    InvalidSegment { name: String, segment: String },
+}
+
+/// The namespace and imports that names are resolved against.
+#[derive(Debug, Default, Clone)]
+pub struct Scope {
+    pub namespace: Option<Name>,
+    pub classes: HashMap<String, Name>,
+    pub functions: HashMap<String, Name>,
+    pub constants: HashMap<String, Name>,
+}
+
+impl Name {
+    pub fn new<T: AsRef<str>>(name: T) -> Self {
+        let name = name.as_ref();
+
+        let (kind, rest) = if let Some(rest) = name.strip_prefix('\\') {
+            (NameKind::FullyQualified, rest)
+        } else if name
+            .get(..10)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("namespace\\"))
+        {
+            (NameKind::Relative, &name[10..])
+        } else if name.contains('\\') {
+            (NameKind::Qualified, name)
+        } else {
+            (NameKind::Unqualified, name)
+        };
+
+        Self {
+            kind,
+            parts: rest.split('\\').map(|part| part.to_string()).collect(),
+        }
+    }
+
+    pub fn parse<T: AsRef<str>>(name: T) -> Result<Self, NameError> {
+        let name = Self::new(name);
+        name.validate()?;
+
+        Ok(name)
+    }
+
+    pub fn fully_qualified<T: ToString>(parts: Vec<T>) -> Self {
+        Self {
+            kind: NameKind::FullyQualified,
+            parts: parts.into_iter().map(|part| part.to_string()).collect(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), NameError> {
+        if self.parts.iter().all(|part| part.is_empty()) {
+            return Err(NameError::Empty);
+        }
+
+        match self.parts.iter().find(|part| !is_label(part)) {
+            Some(segment) => Err(NameError::InvalidSegment {
+                name: self.to_string(),
+                segment: segment.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    pub fn is_unqualified(&self) -> bool {
+        self.kind == NameKind::Unqualified
+    }
+
+    pub fn is_qualified(&self) -> bool {
+        self.kind == NameKind::Qualified
+    }
+
+    pub fn is_fully_qualified(&self) -> bool {
+        self.kind == NameKind::FullyQualified
+    }
+
+    pub fn is_relative(&self) -> bool {
+        self.kind == NameKind::Relative
+    }
+
+    /// Returns true for `self`, `static` and `parent`, which are never resolved.
+    pub fn is_special(&self) -> bool {
+        self.is_unqualified()
+            && matches!(
+                self.parts[0].to_ascii_lowercase().as_str(),
+                "self" | "static" | "parent"
+            )
+    }
+
+    pub fn first(&self) -> &str {
+        &self.parts[0]
+    }
+
+    pub fn last(&self) -> &str {
+        &self.parts[self.parts.len() - 1]
+    }
+
+    /// Returns the namespace part of the name, if it has one.
+    pub fn namespace(&self) -> Option<Name> {
+        if self.parts.len() < 2 {
+            return None;
+        }
+
+        Some(Self {
+            kind: self.kind,
+            parts: self.parts[..self.parts.len() - 1].to_vec(),
+        })
+    }
+
+    /// Returns the name without its leading `\` or `namespace\`.
+    pub fn unqualified(&self) -> String {
+        self.parts.join("\\")
+    }
+
+    /// Compares two names the way PHP compares class and function names.
+    pub fn matches(&self, other: &Name) -> bool {
+        self.kind == other.kind
+            && self.parts.len() == other.parts.len()
+            && self
+                .parts
+                .iter()
+                .zip(&other.parts)
+                .all(|(a, b)| a.eq_ignore_ascii_case(b))
+    }
+
+    pub fn resolve(&self, scope: &Scope) -> Name {
+        scope.resolve_class(self)
+    }
+}
+
+impl Scope {
+    pub fn new(namespace: Option<Name>) -> Self {
+        Self {
+            namespace: namespace.map(|namespace| Name {
+                kind: NameKind::FullyQualified,
+                parts: namespace.parts,
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn import<T: Into<Name>>(mut self, name: T, alias: Option<&str>) -> Self {
+        let name = absolute(name.into());
+        let alias = alias.unwrap_or(name.last()).to_ascii_lowercase();
+        self.classes.insert(alias, name);
+
+        self
+    }
+
+    pub fn import_function<T: Into<Name>>(mut self, name: T, alias: Option<&str>) -> Self {
+        let name = absolute(name.into());
+        let alias = alias.unwrap_or(name.last()).to_ascii_lowercase();
+        self.functions.insert(alias, name);
+
+        self
+    }
+
+    pub fn import_constant<T: Into<Name>>(mut self, name: T, alias: Option<&str>) -> Self {
+        let name = absolute(name.into());
+        let alias = alias.unwrap_or(name.last()).to_string();
+        self.constants.insert(alias, name);
+
+        self
+    }
+
+    pub fn resolve_class(&self, name: &Name) -> Name {
+        if name.is_special() {
+            return name.clone();
+        }
+
+        self.resolve(name, self.classes.get(&name.first().to_ascii_lowercase()))
+    }
+
+    /// Resolves a function name. Unqualified names that are not imported
+    /// resolve to the current namespace; PHP's runtime fallback to the global
+    /// namespace is left to the caller.
+    pub fn resolve_function(&self, name: &Name) -> Name {
+        let imported = if name.is_unqualified() {
+            self.functions.get(&name.first().to_ascii_lowercase())
+        } else {
+            self.classes.get(&name.first().to_ascii_lowercase())
+        };
+
+        self.resolve(name, imported)
+    }
+
+    pub fn resolve_constant(&self, name: &Name) -> Name {
+        let imported = if name.is_unqualified() {
+            self.constants.get(name.first())
+        } else {
+            self.classes.get(&name.first().to_ascii_lowercase())
+        };
+
+        self.resolve(name, imported)
+    }
+
+    fn resolve(&self, name: &Name, imported: Option<&Name>) -> Name {
+        match name.kind {
+            NameKind::FullyQualified => name.clone(),
+            NameKind::Relative => self.prefix(&name.parts),
+            NameKind::Unqualified | NameKind::Qualified => match imported {
+                Some(imported) => {
+                    let mut parts = imported.parts.clone();
+                    parts.extend(name.parts[1..].iter().cloned());
+
+                    Name::fully_qualified(parts)
+                }
+                None => self.prefix(&name.parts),
+            },
+        }
+    }
+
+    fn prefix(&self, parts: &[String]) -> Name {
+        let mut resolved = match &self.namespace {
+            Some(namespace) => namespace.parts.clone(),
+            None => vec![],
+        };
+
+        resolved.extend(parts.iter().cloned());
+
+        Name::fully_qualified(resolved)
+    }
+}
+
+fn absolute(name: Name) -> Name {
+    Name {
+        kind: NameKind::FullyQualified,
+        parts: name.parts,
+    }
+}
+
+pub(crate) fn is_label(label: &str) -> bool {
+    let mut characters = label.chars();
+
+    matches!(characters.next(), Some(c) if c == '_' || c.is_ascii_alphabetic() || !c.is_ascii())
+        && characters.all(|c| c == '_' || c.is_ascii_alphanumeric() || !c.is_ascii())
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            NameKind::FullyQualified => write!(f, "\\{}", self.parts.join("\\")),
+            NameKind::Relative => write!(f, "namespace\\{}", self.parts.join("\\")),
+            NameKind::Unqualified | NameKind::Qualified => write!(f, "{}", self.parts.join("\\")),
+        }
+    }
+}
+
+impl Generator for Name {
+    fn generate(&self, _: Indentation, _: usize) -> String {
+        self.to_string()
+    }
+}
+
+impl From<&str> for Name {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&String> for Name {
+    fn from(name: &String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&Name> for Name {
+    fn from(name: &Name) -> Self {
+        name.clone()
+    }
+}
+
+impl Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "name cannot be empty"),
+            NameError::InvalidSegment { name, segment } => write!(
+                f,
+                "`{}` is not a valid PHP name: invalid segment `{}`",
+                name, segment
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
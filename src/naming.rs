@@ -0,0 +1,202 @@
+use crate::identifier;
+use crate::identifier::Context;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Case {
+    Preserve,
+    Camel,
+    Pascal,
+    Snake,
+    ScreamingSnake,
+}
+
+/// How names from Rust code or external schemas are turned into PHP names.
+///
+/// The defaults follow PSR-1: `PascalCase` classes, `camelCase` methods and
+/// `SCREAMING_SNAKE_CASE` constants. Enum cases are `PascalCase`, and
+/// functions, properties and variables are `camelCase`.
+///
+/// The `named()` constructors of the declaration types apply a `Naming` to
+/// the given name and keep that name as `original_name`.
+#[derive(Debug, Clone)]
+pub struct Naming {
+    pub classes: Case,
+    pub functions: Case,
+    pub constants: Case,
+    pub methods: Case,
+    pub class_constants: Case,
+    pub enum_cases: Case,
+    pub properties: Case,
+    pub variables: Case,
+    pub acronyms: Vec<String>,
+    pub sanitize: bool,
+}
+
+impl Case {
+    pub fn apply(&self, name: &str) -> String {
+        convert(name, *self, &[])
+    }
+}
+
+impl Naming {
+    pub fn new() -> Self {
+        Self {
+            classes: Case::Pascal,
+            functions: Case::Camel,
+            constants: Case::ScreamingSnake,
+            methods: Case::Camel,
+            class_constants: Case::ScreamingSnake,
+            enum_cases: Case::Pascal,
+            properties: Case::Camel,
+            variables: Case::Camel,
+            acronyms: vec![],
+            sanitize: false,
+        }
+    }
+
+    pub fn case(mut self, context: Context, case: Case) -> Self {
+        match context {
+            Context::Class => self.classes = case,
+            Context::Function => self.functions = case,
+            Context::Constant => self.constants = case,
+            Context::Method => self.methods = case,
+            Context::ClassConstant => self.class_constants = case,
+            Context::EnumCase => self.enum_cases = case,
+            Context::Property => self.properties = case,
+            Context::Variable => self.variables = case,
+        }
+
+        self
+    }
+
+    /// Keeps `acronym` in the given spelling inside camel and pascal case
+    /// names, so that with `ID` registered `user_id` becomes `userID` rather
+    /// than `userId`.
+    pub fn acronym<T: ToString>(mut self, acronym: T) -> Self {
+        self.acronyms.push(acronym.to_string());
+
+        self
+    }
+
+    /// Passes converted names through `identifier::sanitize`.
+    pub fn sanitize(mut self) -> Self {
+        self.sanitize = true;
+
+        self
+    }
+
+    pub fn case_for(&self, context: Context) -> Case {
+        match context {
+            Context::Class => self.classes,
+            Context::Function => self.functions,
+            Context::Constant => self.constants,
+            Context::Method => self.methods,
+            Context::ClassConstant => self.class_constants,
+            Context::EnumCase => self.enum_cases,
+            Context::Property => self.properties,
+            Context::Variable => self.variables,
+        }
+    }
+
+    pub fn apply(&self, name: &str, context: Context) -> String {
+        let name = convert(name, self.case_for(context), &self.acronyms);
+
+        if self.sanitize {
+            identifier::sanitize(&name, context)
+        } else {
+            name
+        }
+    }
+}
+
+/// Splits `name` into words at separators and case changes.
+///
+/// A run of capitals is kept together as an acronym, except for its last
+/// letter when a lowercase letter follows: `HTTPServer` is `HTTP`, `Server`.
+/// Digits stay with the word before them, and a capital after a digit starts
+/// a new word: `base64Encode` is `base64`, `Encode`.
+pub fn words(name: &str) -> Vec<String> {
+    let characters = name.chars().collect::<Vec<char>>();
+    let mut words = vec![];
+    let mut word = String::new();
+
+    for (index, character) in characters.iter().copied().enumerate() {
+        if !character.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+
+            continue;
+        }
+
+        if let Some(previous) = word.chars().last() {
+            let next = characters.get(index + 1).copied();
+            let boundary = character.is_uppercase()
+                && (previous.is_lowercase()
+                    || previous.is_numeric()
+                    || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase())));
+
+            if boundary {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+
+        word.push(character);
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn convert(name: &str, case: Case, acronyms: &[String]) -> String {
+    let words = words(name);
+    let capitalize = |word: &String| match acronyms
+        .iter()
+        .find(|acronym| acronym.to_lowercase() == word.to_lowercase())
+    {
+        Some(acronym) => acronym.to_string(),
+        None => {
+            let mut characters = word.chars();
+
+            match characters.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(characters.flat_map(|c| c.to_lowercase()))
+                    .collect(),
+                None => String::new(),
+            }
+        }
+    };
+
+    match case {
+        Case::Preserve => name.to_string(),
+        Case::Pascal => words.iter().map(capitalize).collect(),
+        Case::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| match index {
+                0 => word.to_lowercase(),
+                _ => capitalize(word),
+            })
+            .collect(),
+        Case::Snake => words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<String>>()
+            .join("_"),
+        Case::ScreamingSnake => words
+            .iter()
+            .map(|word| word.to_uppercase())
+            .collect::<Vec<String>>()
+            .join("_"),
+    }
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
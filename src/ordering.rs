@@ -0,0 +1,338 @@
+use crate::constant::ClassConstant;
+use crate::enum_case::EnumCase;
+use crate::method::Method;
+use crate::modifiers::Modifier;
+use crate::modifiers::VisibilityModifier;
+use crate::property::Property;
+use crate::style::Style;
+use crate::usage::Usage;
+use crate::Generator;
+use crate::Indentation;
+
+/// The order class-like members print in. Members are always grouped by
+/// kind: trait uses, constants, properties or enum cases, then methods.
+/// Within a kind other than cases, members keep their insertion order
+/// unless a policy is enabled; enabled policies apply in the order
+/// constructor, static, visibility, then name.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MemberOrder {
+    pub constructor_first: bool,
+    pub statics_first: bool,
+    /// Public members first, then protected, then private.
+    pub visibility: bool,
+    /// Sorts members case-insensitively by name within their group.
+    pub alphabetical: bool,
+}
+
+impl MemberOrder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn constructor_first(mut self) -> Self {
+        self.constructor_first = true;
+
+        self
+    }
+
+    pub fn statics_first(mut self) -> Self {
+        self.statics_first = true;
+
+        self
+    }
+
+    pub fn by_visibility(mut self) -> Self {
+        self.visibility = true;
+
+        self
+    }
+
+    pub fn alphabetical(mut self) -> Self {
+        self.alphabetical = true;
+
+        self
+    }
+
+    /// Splits `members` into the groups the enabled policies form, in print
+    /// order.
+    pub(crate) fn groups<'a, T: Member>(&self, members: &'a [T]) -> Vec<Vec<&'a T>> {
+        let mut members = members.iter().collect::<Vec<&T>>();
+        members.sort_by_cached_key(|member| {
+            let name = if self.alphabetical {
+                member.name().to_lowercase()
+            } else {
+                String::new()
+            };
+
+            (self.rank(*member), name)
+        });
+
+        let mut groups: Vec<Vec<&T>> = vec![];
+        let mut previous = None;
+        for member in members {
+            let rank = self.rank(member);
+            match groups.last_mut() {
+                Some(group) if previous == Some(rank) => group.push(member),
+                _ => groups.push(vec![member]),
+            }
+
+            previous = Some(rank);
+        }
+
+        groups
+    }
+
+    fn rank<T: Member>(&self, member: &T) -> (bool, bool, u8) {
+        let visibility = match member.visibility() {
+            None | Some(VisibilityModifier::Public) => 0,
+            Some(VisibilityModifier::Protected) => 1,
+            Some(VisibilityModifier::Private) => 2,
+        };
+
+        (
+            self.constructor_first && !member.name().eq_ignore_ascii_case("__construct"),
+            self.statics_first && !member.is_static(),
+            if self.visibility { visibility } else { 0 },
+        )
+    }
+}
+
+/// A class member that [`MemberOrder`] can order.
+pub(crate) trait Member {
+    fn name(&self) -> &str;
+
+    fn visibility(&self) -> Option<VisibilityModifier>;
+
+    fn is_static(&self) -> bool;
+}
+
+impl Member for ClassConstant {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn visibility(&self) -> Option<VisibilityModifier> {
+        self.visibility
+    }
+
+    fn is_static(&self) -> bool {
+        false
+    }
+}
+
+impl Member for Property {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn visibility(&self) -> Option<VisibilityModifier> {
+        self.visibility
+    }
+
+    fn is_static(&self) -> bool {
+        self.modifiers.contains(&Modifier::Static)
+    }
+}
+
+impl Member for Method {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn visibility(&self) -> Option<VisibilityModifier> {
+        self.visibility
+    }
+
+    fn is_static(&self) -> bool {
+        self.modifiers.contains(&Modifier::Static)
+    }
+}
+
+/// The printed members of one kind, with the blank lines the crate has
+/// always put between them and after them.
+pub(crate) struct Kind {
+    pub groups: Vec<Vec<String>>,
+    pub between: usize,
+    pub after: usize,
+}
+
+/// Prints the members of a class-like body, spacing members and groups as
+/// `style` says.
+pub(crate) fn print(kinds: Vec<Kind>, style: &Style) -> String {
+    let separator = |blank_lines: usize| "\n".repeat(blank_lines + 1);
+
+    let mut code = String::new();
+    let mut after = None;
+    for kind in kinds {
+        if kind.groups.is_empty() {
+            continue;
+        }
+
+        if let Some(after) = after {
+            code.push_str(&separator(
+                style.blank_lines_between_groups.unwrap_or(after),
+            ));
+        }
+
+        let between = style.blank_lines_between_members.unwrap_or(kind.between);
+        code.push_str(
+            &kind
+                .groups
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|member| member.trim_end_matches('\n'))
+                        .collect::<Vec<&str>>()
+                        .join(&separator(between))
+                })
+                .collect::<Vec<String>>()
+                .join(&separator(
+                    style.blank_lines_between_groups.unwrap_or(between),
+                )),
+        );
+
+        after = Some(kind.after);
+    }
+
+    if !code.is_empty() {
+        code.push('\n');
+    }
+
+    code
+}
+
+/// Prints the trait uses, constants, properties and methods of a class or
+/// trait body.
+pub(crate) fn members(
+    usages: &[Usage],
+    constants: &[ClassConstant],
+    properties: &[Property],
+    methods: &[Method],
+    style: &Style,
+    indentation: Indentation,
+    level: usize,
+) -> String {
+    print(
+        vec![
+            self::usages(usages, indentation, level),
+            self::constants(constants, style, indentation, level),
+            kind(
+                properties,
+                |property| property.generate_styled(style, indentation, level),
+                style,
+                0,
+                0,
+            ),
+            self::methods(methods, style, indentation, level),
+        ],
+        style,
+    )
+}
+
+/// Prints the trait uses, constants, cases and methods of an enum body.
+/// Cases keep their declaration order, which `cases()` returns them in.
+pub(crate) fn enum_members(
+    usages: &[Usage],
+    constants: &[ClassConstant],
+    cases: &[EnumCase],
+    methods: &[Method],
+    style: &Style,
+    indentation: Indentation,
+    level: usize,
+) -> String {
+    let cases = Kind {
+        groups: if cases.is_empty() {
+            vec![]
+        } else {
+            vec![cases
+                .iter()
+                .map(|case| case.generate(indentation, level))
+                .collect()]
+        },
+        between: 1,
+        after: 1,
+    };
+
+    print(
+        vec![
+            self::usages(usages, indentation, level),
+            self::constants(constants, style, indentation, level),
+            cases,
+            self::methods(methods, style, indentation, level),
+        ],
+        style,
+    )
+}
+
+/// Prints the methods of an interface body.
+pub(crate) fn interface_members(
+    methods: &[Method],
+    style: &Style,
+    indentation: Indentation,
+    level: usize,
+) -> String {
+    print(
+        vec![self::methods(methods, style, indentation, level)],
+        style,
+    )
+}
+
+fn kind<T: Member>(
+    members: &[T],
+    print: impl Fn(&T) -> String,
+    style: &Style,
+    between: usize,
+    after: usize,
+) -> Kind {
+    Kind {
+        groups: style
+            .member_order
+            .groups(members)
+            .into_iter()
+            .map(|group| group.into_iter().map(&print).collect())
+            .collect(),
+        between,
+        after,
+    }
+}
+
+fn usages(usages: &[Usage], indentation: Indentation, level: usize) -> Kind {
+    Kind {
+        groups: if usages.is_empty() {
+            vec![]
+        } else {
+            vec![usages
+                .iter()
+                .map(|usage| usage.generate(indentation, level))
+                .collect()]
+        },
+        between: 0,
+        after: 1,
+    }
+}
+
+fn constants(
+    constants: &[ClassConstant],
+    style: &Style,
+    indentation: Indentation,
+    level: usize,
+) -> Kind {
+    kind(
+        constants,
+        |constant| constant.generate(indentation, level),
+        style,
+        1,
+        1,
+    )
+}
+
+fn methods(methods: &[Method], style: &Style, indentation: Indentation, level: usize) -> Kind {
+    kind(
+        methods,
+        |method| method.generate_styled(style, indentation, level),
+        style,
+        1,
+        1,
+    )
+}
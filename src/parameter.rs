@@ -1,35 +1,51 @@
 use crate::attribute::AttributeGroup;
 use crate::data_type::DataType;
+use crate::identifier::Context;
 use crate::literal::Value;
+use crate::modifiers;
 use crate::modifiers::Modifier;
 use crate::modifiers::VisibilityModifier;
+use crate::naming::Naming;
+use crate::style::Style;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Parameter {
     pub attributes: Vec<AttributeGroup>,
     pub name: String,
+    pub original_name: Option<String>,
     pub data_type: Option<DataType>,
     pub default: Option<Value>,
     pub modifiers: Vec<Modifier>,
     pub visibility: Option<VisibilityModifier>,
     pub variadic: bool,
+    pub by_reference: bool,
 }
 
 impl Parameter {
     pub fn new<T: ToString>(name: T) -> Self {
         Self {
             name: name.to_string(),
+            original_name: None,
             data_type: None,
             default: None,
             modifiers: vec![],
             attributes: vec![],
             visibility: None,
             variadic: false,
+            by_reference: false,
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut parameter = Self::new(naming.apply(&original, Context::Variable));
+        parameter.original_name = Some(original);
+
+        parameter
+    }
+
     pub fn attributes(mut self, attributes: AttributeGroup) -> Self {
         self.attributes.push(attributes);
 
@@ -48,6 +64,13 @@ impl Parameter {
         self
     }
 
+    /// Passes the argument by reference, as in `array &$items`.
+    pub fn reference(mut self) -> Self {
+        self.by_reference = true;
+
+        self
+    }
+
     pub fn default<T: Into<Value>>(mut self, default: T) -> Self {
         self.default = Some(default.into());
 
@@ -85,8 +108,8 @@ impl Parameter {
     }
 }
 
-impl Generator for Parameter {
-    fn generate(&self, indentation: Indentation, level: usize) -> String {
+impl Parameter {
+    fn print(&self, data_type: Option<String>, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
         for attribute in &self.attributes {
@@ -95,16 +118,14 @@ impl Generator for Parameter {
 
         code.push_str(&indentation.value(level));
 
-        if let Some(visibility) = &self.visibility {
-            code.push_str(&format!("{} ", visibility.generate(indentation, level)));
-        }
+        code.push_str(&modifiers::print(self.visibility.as_ref(), &self.modifiers));
 
-        for modifier in &self.modifiers {
-            code.push_str(&format!("{} ", modifier.generate(indentation, level)));
+        if let Some(data_type) = data_type {
+            code.push_str(&format!("{} ", data_type));
         }
 
-        if let Some(data_type) = &self.data_type {
-            code.push_str(&format!("{} ", data_type.generate(indentation, level)));
+        if self.by_reference {
+            code.push('&');
         }
 
         if self.variadic {
@@ -121,26 +142,113 @@ impl Generator for Parameter {
     }
 }
 
+impl Generator for Parameter {
+    fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    /// Breaks a union type over several lines when the parameter, followed
+    /// by a comma, is too long for the line width.
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
+        let data_type = self
+            .data_type
+            .as_ref()
+            .map(|data_type| data_type.generate_styled(style, indentation, level));
+        let union = data_type
+            .as_ref()
+            .is_some_and(|data_type| data_type.contains('|'));
+        let code = self.print(data_type, indentation, level);
+
+        match &self.data_type {
+            Some(data_type) if union && style.exceeds(width(&code) + 1) => self.print(
+                Some(data_type.print_wrapped(indentation, level)),
+                indentation,
+                level,
+            ),
+            _ => code,
+        }
+    }
+}
+
 impl Generator for Vec<Parameter> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
-        let mut code = String::new();
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
+        print(self, true, style, (0, 0), indentation, level)
+    }
+}
 
-        if self.is_empty() {
-            code.push_str("()");
-        } else {
-            code.push_str("(\n");
-            code.push_str(
-                &self
-                    .iter()
-                    .map(|parameter| parameter.generate(indentation, level + 1))
-                    .collect::<Vec<String>>()
-                    .join(",\n"),
-            );
-
-            code.push_str(",\n");
-            code.push_str(&indentation.indent(")", level));
+/// Prints a parameter list. With a line width, the list stays on one line
+/// if it fits between the `(before, after)` columns around it, unless a
+/// parameter is promoted or has attributes. Otherwise it prints one
+/// parameter per line. `trailing_comma` is false for targets older than
+/// PHP 8.0, which reject it.
+pub(crate) fn print(
+    parameters: &[Parameter],
+    trailing_comma: bool,
+    style: &Style,
+    (before, after): (usize, usize),
+    indentation: Indentation,
+    level: usize,
+) -> String {
+    let mut code = String::new();
+
+    if parameters.is_empty() {
+        return "()".to_string();
+    }
+
+    let simple = parameters.iter().all(|parameter| {
+        parameter.visibility.is_none()
+            && parameter.modifiers.is_empty()
+            && parameter.attributes.is_empty()
+    });
+
+    if simple && style.line_width.is_some() {
+        let code = format!(
+            "({})",
+            parameters
+                .iter()
+                .map(|parameter| {
+                    let data_type = parameter
+                        .data_type
+                        .as_ref()
+                        .map(|data_type| data_type.generate_styled(style, indentation, 0));
+
+                    parameter.print(data_type, indentation, 0)
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+
+        if !style.exceeds(before + code.chars().count() + after) {
+            return code;
         }
+    }
 
-        code
+    code.push_str("(\n");
+    code.push_str(
+        &parameters
+            .iter()
+            .map(|parameter| parameter.generate_styled(style, indentation, level + 1))
+            .collect::<Vec<String>>()
+            .join(",\n"),
+    );
+
+    if trailing_comma {
+        code.push(',');
     }
+
+    code.push('\n');
+    code.push_str(&indentation.indent(")", level));
+
+    code
+}
+
+/// Returns the number of columns in the last line of `code`.
+pub(crate) fn width(code: &str) -> usize {
+    code.rsplit('\n')
+        .next()
+        .map_or(0, |line| line.chars().count())
 }
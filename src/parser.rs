@@ -0,0 +1,1035 @@
+use crate::attribute::AttributeGroup;
+use crate::body::Body;
+use crate::class::Class;
+use crate::comment::Document;
+use crate::constant::ClassConstant;
+use crate::constant::Constant;
+use crate::data_type::DataType;
+use crate::enum_case::EnumCase;
+use crate::file::File;
+use crate::function::Function;
+use crate::import::Import;
+use crate::interface::Interface;
+use crate::lexer::tokenize;
+use crate::lexer::SyntaxError;
+use crate::lexer::Token;
+use crate::lexer::TokenKind;
+use crate::literal::Value;
+use crate::method::Method;
+use crate::modifiers::Modifier;
+use crate::modifiers::VisibilityModifier;
+use crate::name::Name;
+use crate::parameter::Parameter;
+use crate::property::Property;
+use crate::property::PropertyHook;
+use crate::property::PropertySetHookParameter;
+use crate::r#enum::Enum;
+use crate::r#enum::EnumBackingType;
+use crate::r#trait::Trait;
+use crate::usage::Usage;
+use crate::usage::UsageAdaptation;
+
+/// Parses the declarations in a PHP file into the model. Method, function
+/// and hook bodies are kept verbatim, and constant and default values become
+/// literals unless they are `null`, booleans, integers or double-quoted
+/// strings.
+///
+/// The round trip is not faithful to the source. Comments outside of
+/// bodies are dropped, except for docblocks. Group uses set
+/// [`File::group_imports`], which may also group imports that were written
+/// separately. Printing orders declarations and members as the model does,
+/// and lays them out in the file's style. Printing the result of parsing
+/// printed code gives the same code again.
+pub fn parse(code: &str) -> Result<File, SyntaxError> {
+    let mut parser = Parser {
+        tokens: tokenize(code)?,
+        position: 0,
+    };
+
+    parser.file()
+}
+
+/// The members of a class-like declaration, before they are sorted into the
+/// declaration they belong to.
+#[derive(Default)]
+struct Members {
+    usages: Vec<Usage>,
+    constants: Vec<ClassConstant>,
+    properties: Vec<Property>,
+    cases: Vec<EnumCase>,
+    methods: Vec<Method>,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens[self.position..]
+            .iter()
+            .filter(|token| !is_trivia(token))
+            .nth(offset)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.peek_at(0)
+    }
+
+    /// Skips comments and whitespace, returning the last docblock among them.
+    fn documentation(&mut self) -> Option<Document> {
+        let mut documentation = None;
+        while let Some(token) = self.tokens.get(self.position) {
+            if !is_trivia(token) {
+                break;
+            }
+
+            if token.kind == TokenKind::DocComment {
+                documentation = Some(document(&token.text));
+            }
+
+            self.position += 1;
+        }
+
+        documentation
+    }
+
+    fn next(&mut self) -> Result<Token, SyntaxError> {
+        self.documentation();
+
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                self.position += 1;
+
+                Ok(token.clone())
+            }
+            None => Err(self.unexpected()),
+        }
+    }
+
+    fn at(&self, text: &str) -> bool {
+        self.peek().is_some_and(|token| is(token, text))
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.at(text) {
+            self.position = self.position_of_next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn position_of_next(&self) -> usize {
+        self.tokens[self.position..]
+            .iter()
+            .position(|token| !is_trivia(token))
+            .map(|offset| self.position + offset + 1)
+            .unwrap_or(self.tokens.len())
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), SyntaxError> {
+        if self.eat(text) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("`{}`", text)))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, SyntaxError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Name => Ok(self.next()?.text),
+            _ => Err(self.expected("a name")),
+        }
+    }
+
+    fn variable(&mut self) -> Result<String, SyntaxError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Variable => {
+                Ok(self.next()?.text[1..].to_string())
+            }
+            _ => Err(self.expected("a variable")),
+        }
+    }
+
+    fn error<T: ToString>(&self, message: T) -> SyntaxError {
+        let (line, column) = match self.peek().or(self.tokens.last()) {
+            Some(token) => (token.line, token.column),
+            None => (1, 1),
+        };
+
+        SyntaxError {
+            message: message.to_string(),
+            line,
+            column,
+        }
+    }
+
+    fn unexpected(&self) -> SyntaxError {
+        match self.peek() {
+            Some(token) => self.error(format!("unexpected `{}`", token.text)),
+            None => self.error("unexpected end of file"),
+        }
+    }
+
+    fn expected(&self, expected: &str) -> SyntaxError {
+        match self.peek() {
+            Some(token) => self.error(format!("expected {}, found `{}`", expected, token.text)),
+            None => self.error(format!("expected {}, found end of file", expected)),
+        }
+    }
+
+    fn raw(&self, from: usize, to: usize) -> String {
+        self.tokens[from..to]
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect()
+    }
+
+    /// Reads code up to one of `stops` outside of any brackets, without
+    /// consuming the stop.
+    fn verbatim(&mut self, stops: &[&str]) -> Result<String, SyntaxError> {
+        self.documentation();
+
+        let start = self.position;
+        let mut depth = 0;
+        loop {
+            let Some(token) = self.peek() else {
+                return Err(self.unexpected());
+            };
+
+            if token.kind == TokenKind::Symbol {
+                match token.text.as_str() {
+                    text if depth == 0 && stops.contains(&text) => break,
+                    "(" | "[" | "{" | "#[" => depth += 1,
+                    ")" | "]" | "}" if depth == 0 => return Err(self.unexpected()),
+                    ")" | "]" | "}" => depth -= 1,
+                    _ => {}
+                }
+            }
+
+            self.next()?;
+        }
+
+        Ok(self.raw(start, self.position).trim().to_string())
+    }
+
+    /// Reads a `{ ... }` block, returning its contents without the common
+    /// indentation.
+    fn block(&mut self) -> Result<String, SyntaxError> {
+        self.expect("{")?;
+
+        let start = self.position;
+        let mut depth = 0;
+        loop {
+            let Some(token) = self.tokens.get(self.position) else {
+                return Err(self.unexpected());
+            };
+
+            self.position += 1;
+            if token.kind == TokenKind::Symbol {
+                match token.text.as_str() {
+                    "{" => depth += 1,
+                    "}" if depth == 0 => break,
+                    "}" => depth -= 1,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(dedent(&self.raw(start, self.position - 1)))
+    }
+
+    fn file(&mut self) -> Result<File, SyntaxError> {
+        let mut file = File::new();
+
+        if self
+            .peek()
+            .is_some_and(|token| token.kind == TokenKind::OpenTag)
+        {
+            self.next()?;
+        }
+
+        loop {
+            let documentation = self.documentation();
+            let Some(token) = self.peek() else {
+                break;
+            };
+
+            if token.kind == TokenKind::CloseTag {
+                self.next()?;
+                if self.tokens[self.position..]
+                    .iter()
+                    .any(|token| !token.text.trim().is_empty())
+                {
+                    return Err(self.error("inline HTML is not supported"));
+                }
+
+                break;
+            }
+
+            let attributes = self.attributes()?;
+            let keyword = self.peek().map(|token| token.text.to_ascii_lowercase());
+            match keyword.as_deref() {
+                Some("declare") => {
+                    self.next()?;
+                    self.expect("(")?;
+                    let name = self.identifier()?;
+                    self.expect("=")?;
+                    let value = value(&self.verbatim(&[")"])?);
+                    self.expect(")")?;
+                    self.expect(";")?;
+
+                    file.declares.push((name, value));
+                }
+                Some("namespace") => {
+                    if file.namespace.is_some() {
+                        return Err(self.error("multiple namespaces are not supported"));
+                    }
+
+                    self.next()?;
+                    file.namespace = Some(Name::new(self.identifier()?));
+                    if self.at("{") {
+                        return Err(self.error("braced namespaces are not supported"));
+                    }
+
+                    self.expect(";")?;
+                }
+                Some("use") => {
+                    self.next()?;
+                    self.imports(&mut file)?;
+                }
+                Some("const") => {
+                    self.next()?;
+                    loop {
+                        let mut constant = Constant::new(self.identifier()?);
+                        constant.documentation = documentation.clone();
+                        self.expect("=")?;
+                        constant.value = value(&self.verbatim(&[",", ";"])?);
+                        file.constants.push(constant);
+
+                        if !self.eat(",") {
+                            break;
+                        }
+                    }
+
+                    self.expect(";")?;
+                }
+                Some("function") => {
+                    let mut function = self.function()?;
+                    function.documentation = documentation;
+                    function.attributes = attributes;
+
+                    file.functions.push(function);
+                }
+                Some("abstract" | "final" | "readonly" | "class") => {
+                    let mut class = self.class()?;
+                    class.documentation = documentation;
+                    class.attributes = attributes;
+
+                    file.classes.push(class);
+                }
+                Some("interface") => {
+                    let mut interface = self.interface()?;
+                    interface.documentation = documentation;
+                    interface.attributes = attributes;
+
+                    file.interfaces.push(interface);
+                }
+                Some("trait") => {
+                    let mut r#trait = self.r#trait()?;
+                    r#trait.documentation = documentation;
+                    r#trait.attributes = attributes;
+
+                    file.traits.push(r#trait);
+                }
+                Some("enum") => {
+                    let mut r#enum = self.r#enum()?;
+                    r#enum.documentation = documentation;
+                    r#enum.attributes = attributes;
+
+                    file.enums.push(r#enum);
+                }
+                _ => return Err(self.expected("a declaration")),
+            }
+        }
+
+        Ok(file)
+    }
+
+    fn imports(&mut self, file: &mut File) -> Result<(), SyntaxError> {
+        let imports = if self.eat("function") {
+            &mut file.function_uses
+        } else if self.eat("const") {
+            &mut file.constant_uses
+        } else {
+            &mut file.uses
+        };
+
+        loop {
+            let name = self.identifier()?;
+            if name.ends_with('\\') {
+                self.expect("{")?;
+                let start = imports.len();
+                loop {
+                    if self.at("}") {
+                        break;
+                    }
+
+                    let member = self.identifier()?;
+                    imports.push(self.import(format!("{}{}", name, member))?);
+                    if !self.eat(",") {
+                        break;
+                    }
+                }
+
+                self.expect("}")?;
+
+                // Group uses print again once a namespace has as many
+                // imports as the smallest group in the file.
+                let size = imports.len() - start;
+                if size > 0 {
+                    file.group_imports = Some(file.group_imports.map_or(size, |s| s.min(size)));
+                }
+            } else {
+                imports.push(self.import(name)?);
+            }
+
+            if !self.eat(",") {
+                break;
+            }
+        }
+
+        self.expect(";")
+    }
+
+    fn import(&mut self, name: String) -> Result<Import, SyntaxError> {
+        let import = Import::new(name);
+        if self.eat("as") {
+            return Ok(import.aliased(self.identifier()?));
+        }
+
+        Ok(import)
+    }
+
+    fn attributes(&mut self) -> Result<Vec<AttributeGroup>, SyntaxError> {
+        let mut groups = vec![];
+        while self.eat("#[") {
+            let mut group = AttributeGroup::new();
+            loop {
+                if self.eat("]") {
+                    break;
+                }
+
+                let name = Name::new(self.identifier()?);
+                let arguments = if self.eat("(") {
+                    let arguments = self.verbatim(&[")"])?;
+                    self.expect(")")?;
+
+                    Some(arguments)
+                } else {
+                    None
+                };
+
+                group.members.push((name, arguments));
+                if !self.eat(",") {
+                    self.expect("]")?;
+                    break;
+                }
+            }
+
+            groups.push(group);
+        }
+
+        Ok(groups)
+    }
+
+    fn modifiers(&mut self) -> (Option<VisibilityModifier>, Vec<Modifier>) {
+        let mut visibility = None;
+        let mut modifiers = vec![];
+        loop {
+            let keyword = self.peek().map(|token| token.text.to_ascii_lowercase());
+            match keyword.as_deref() {
+                Some("public" | "var") => visibility = Some(VisibilityModifier::Public),
+                Some("protected") => visibility = Some(VisibilityModifier::Protected),
+                Some("private") => visibility = Some(VisibilityModifier::Private),
+                Some("abstract") => modifiers.push(Modifier::Abstract),
+                Some("final") => modifiers.push(Modifier::Final),
+                Some("static") => modifiers.push(Modifier::Static),
+                Some("readonly") => modifiers.push(Modifier::Readonly),
+                _ => return (visibility, modifiers),
+            }
+
+            self.position = self.position_of_next();
+        }
+    }
+
+    fn names(&mut self) -> Result<Vec<Name>, SyntaxError> {
+        let mut names = vec![Name::new(self.identifier()?)];
+        while self.eat(",") {
+            names.push(Name::new(self.identifier()?));
+        }
+
+        Ok(names)
+    }
+
+    fn class(&mut self) -> Result<Class, SyntaxError> {
+        let (visibility, modifiers) = self.modifiers();
+        if visibility.is_some() {
+            return Err(self.error("classes cannot have a visibility"));
+        }
+
+        self.expect("class")?;
+
+        let mut class = Class::new(self.identifier()?);
+        class.modifiers = modifiers;
+        if self.eat("extends") {
+            class.extends = Some(Name::new(self.identifier()?));
+        }
+
+        if self.eat("implements") {
+            class.implements = self.names()?;
+        }
+
+        let members = self.members()?;
+        if !members.cases.is_empty() {
+            return Err(self.error("classes cannot have enum cases"));
+        }
+
+        class.usages = members.usages;
+        class.constants = members.constants;
+        class.properties = members.properties;
+        class.methods = members.methods;
+
+        Ok(class)
+    }
+
+    fn interface(&mut self) -> Result<Interface, SyntaxError> {
+        self.expect("interface")?;
+
+        let mut interface = Interface::new(self.identifier()?);
+        if self.eat("extends") {
+            let mut extends = self.names()?;
+            if extends.len() > 1 {
+                return Err(
+                    self.error("interfaces extending more than one interface are not supported")
+                );
+            }
+
+            interface.extends = extends.pop();
+        }
+
+        let members = self.members()?;
+        if !members.constants.is_empty() {
+            return Err(self.error("interface constants are not supported"));
+        }
+
+        if !members.usages.is_empty() || !members.properties.is_empty() || !members.cases.is_empty()
+        {
+            return Err(self.error("interfaces can only declare methods"));
+        }
+
+        interface.methods = members.methods;
+
+        Ok(interface)
+    }
+
+    fn r#trait(&mut self) -> Result<Trait, SyntaxError> {
+        self.expect("trait")?;
+
+        let mut r#trait = Trait::new(self.identifier()?);
+        let members = self.members()?;
+        if !members.cases.is_empty() {
+            return Err(self.error("traits cannot have enum cases"));
+        }
+
+        r#trait.usages = members.usages;
+        r#trait.constants = members.constants;
+        r#trait.properties = members.properties;
+        r#trait.methods = members.methods;
+
+        Ok(r#trait)
+    }
+
+    fn r#enum(&mut self) -> Result<Enum, SyntaxError> {
+        self.expect("enum")?;
+
+        let mut r#enum = Enum::new(self.identifier()?);
+        if self.eat(":") {
+            r#enum.backing_type = match self.identifier()?.to_ascii_lowercase().as_str() {
+                "int" => Some(EnumBackingType::Int),
+                "string" => Some(EnumBackingType::String),
+                _ => return Err(self.error("enums can only be backed by `int` or `string`")),
+            };
+        }
+
+        if self.eat("implements") {
+            r#enum.implements = self.names()?;
+        }
+
+        let members = self.members()?;
+        if !members.properties.is_empty() {
+            return Err(self.error("enums cannot have properties"));
+        }
+
+        r#enum.usages = members.usages;
+        r#enum.constants = members.constants;
+        r#enum.cases = members.cases;
+        r#enum.methods = members.methods;
+
+        Ok(r#enum)
+    }
+
+    fn members(&mut self) -> Result<Members, SyntaxError> {
+        self.expect("{")?;
+
+        let mut members = Members::default();
+        loop {
+            let documentation = self.documentation();
+            if self.eat("}") {
+                return Ok(members);
+            }
+
+            let attributes = self.attributes()?;
+            if self.eat("use") {
+                members.usages.push(self.usage()?);
+
+                continue;
+            }
+
+            if self.eat("case") {
+                let mut case = EnumCase::new(self.identifier()?);
+                case.documentation = documentation;
+                case.attributes = attributes;
+                if self.eat("=") {
+                    case.value = Some(value(&self.verbatim(&[";"])?));
+                }
+
+                self.expect(";")?;
+                members.cases.push(case);
+
+                continue;
+            }
+
+            let (visibility, modifiers) = self.modifiers();
+            if self.eat("const") {
+                let data_type = match self.peek_at(1) {
+                    Some(token) if is(token, "=") => None,
+                    _ => Some(self.data_type()?),
+                };
+
+                loop {
+                    let mut constant = ClassConstant::new(self.identifier()?);
+                    constant.documentation = documentation.clone();
+                    constant.attributes = attributes.clone();
+                    constant.visibility = visibility;
+                    constant.modifiers = modifiers.clone();
+                    constant.data_type = data_type.clone();
+                    self.expect("=")?;
+                    constant.value = value(&self.verbatim(&[",", ";"])?);
+                    members.constants.push(constant);
+
+                    if !self.eat(",") {
+                        break;
+                    }
+                }
+
+                self.expect(";")?;
+            } else if self.eat("function") {
+                if self.at("&") {
+                    return Err(self.error("methods returning by reference are not supported"));
+                }
+
+                let mut method = Method::new(self.identifier()?);
+                method.documentation = documentation;
+                method.attributes = attributes;
+                method.visibility = visibility;
+                method.modifiers = modifiers;
+                method.parameters = self.parameters()?;
+                if self.eat(":") {
+                    method.return_type = Some(self.data_type()?);
+                }
+
+                if !self.eat(";") {
+                    method.body = body(self.block()?);
+                }
+
+                members.methods.push(method);
+            } else {
+                let data_type = match self.peek() {
+                    Some(token) if token.kind == TokenKind::Variable => None,
+                    _ => Some(self.data_type()?),
+                };
+
+                loop {
+                    let mut property = Property::new(self.variable()?);
+                    property.documentation = documentation.clone();
+                    property.attributes = attributes.clone();
+                    property.visibility = visibility;
+                    property.modifiers = modifiers.clone();
+                    property.data_type = data_type.clone();
+                    if self.eat("=") {
+                        property.default = Some(value(&self.verbatim(&[",", ";", "{"])?));
+                    }
+
+                    if self.at("{") {
+                        property.hooks = self.hooks(&property.name)?;
+                        members.properties.push(property);
+
+                        break;
+                    }
+
+                    members.properties.push(property);
+                    if !self.eat(",") {
+                        self.expect(";")?;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn usage(&mut self) -> Result<Usage, SyntaxError> {
+        let mut usage = Usage::new(self.names()?);
+        if self.eat(";") {
+            return Ok(usage);
+        }
+
+        self.expect("{")?;
+        while !self.eat("}") {
+            let mut method = self.identifier()?;
+            if self.eat("::") {
+                method = format!("{}::{}", method, self.identifier()?);
+            }
+
+            if self.eat("insteadof") {
+                usage.adaptations.push(UsageAdaptation::Precedence {
+                    method,
+                    insteadof: self.names()?,
+                });
+            } else {
+                self.expect("as")?;
+                let (visibility, modifiers) = self.modifiers();
+                if !modifiers.is_empty() {
+                    return Err(self.error("trait aliases can only change the visibility"));
+                }
+
+                let adaptation = match (visibility, self.at(";")) {
+                    (Some(visibility), true) => UsageAdaptation::Visibility { method, visibility },
+                    (visibility, _) => UsageAdaptation::Alias {
+                        method,
+                        alias: self.identifier()?,
+                        visibility,
+                    },
+                };
+
+                usage.adaptations.push(adaptation);
+            }
+
+            self.expect(";")?;
+        }
+
+        Ok(usage)
+    }
+
+    fn hooks(&mut self, property: &str) -> Result<Vec<PropertyHook>, SyntaxError> {
+        self.expect("{")?;
+
+        let mut hooks = vec![];
+        while !self.eat("}") {
+            self.documentation();
+            if !self.attributes()?.is_empty() {
+                return Err(self.error("attributes on property hooks are not supported"));
+            }
+
+            let by_reference = self.eat("&");
+            let hook = self.identifier()?.to_ascii_lowercase();
+            let parameter = if hook == "set" && self.eat("(") {
+                let attributes = self.attributes()?;
+                let data_type = match self.peek() {
+                    Some(token) if token.kind == TokenKind::Variable => None,
+                    _ => Some(self.data_type()?),
+                };
+                let mut parameter = PropertySetHookParameter::new(format!("${}", self.variable()?));
+                parameter.attributes = attributes;
+                parameter.data_type = data_type;
+                self.expect(")")?;
+
+                Some(parameter)
+            } else {
+                None
+            };
+
+            let body = if self.eat(";") {
+                Body::default()
+            } else if self.eat("=>") {
+                let expression = self.verbatim(&[";"])?;
+                self.expect(";")?;
+
+                if hook == "get" {
+                    Body::from(format!("return {};", expression))
+                } else {
+                    Body::from(format!("$this->{} = {};", property, expression))
+                }
+            } else {
+                body(self.block()?)
+            };
+
+            hooks.push(match hook.as_str() {
+                "get" => PropertyHook::Get(by_reference, body),
+                "set" => PropertyHook::Set(parameter, body),
+                _ => return Err(self.error(format!("unknown property hook `{}`", hook))),
+            });
+        }
+
+        Ok(hooks)
+    }
+
+    fn parameters(&mut self) -> Result<Vec<Parameter>, SyntaxError> {
+        self.expect("(")?;
+
+        let mut parameters = vec![];
+        while !self.eat(")") {
+            let attributes = self.attributes()?;
+            let (visibility, modifiers) = self.modifiers();
+
+            let data_type = match self.peek() {
+                Some(token) if token.kind == TokenKind::Variable => None,
+                Some(token) if is(token, "&") || is(token, "...") => None,
+                _ => Some(self.data_type()?),
+            };
+
+            let by_reference = self.eat("&");
+            let variadic = self.eat("...");
+
+            let mut parameter = Parameter::new(self.variable()?);
+            parameter.attributes = attributes;
+            parameter.visibility = visibility;
+            parameter.modifiers = modifiers;
+            parameter.data_type = data_type;
+            parameter.by_reference = by_reference;
+            parameter.variadic = variadic;
+            if self.eat("=") {
+                parameter.default = Some(value(&self.verbatim(&[",", ")"])?));
+            }
+
+            if self.at("{") {
+                return Err(self.error("hooks on promoted properties are not supported"));
+            }
+
+            parameters.push(parameter);
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+
+        Ok(parameters)
+    }
+
+    fn data_type(&mut self) -> Result<DataType, SyntaxError> {
+        if self.eat("?") {
+            return Ok(DataType::Nullable(Box::new(self.single_type()?)));
+        }
+
+        let mut types = vec![self.intersection()?];
+        while self.eat("|") {
+            types.push(self.intersection()?);
+        }
+
+        if types.len() == 1 {
+            return Ok(types.remove(0));
+        }
+
+        Ok(DataType::Union(types))
+    }
+
+    fn intersection(&mut self) -> Result<DataType, SyntaxError> {
+        let parenthesized = self.eat("(");
+
+        let mut types = vec![self.single_type()?];
+        // `&` is also how by-reference parameters start, as in `Foo &$bar`.
+        while self.at("&")
+            && self
+                .peek_at(1)
+                .is_some_and(|token| token.kind == TokenKind::Name || is(token, "("))
+        {
+            self.next()?;
+            types.push(self.single_type()?);
+        }
+
+        if parenthesized {
+            self.expect(")")?;
+        }
+
+        if types.len() == 1 {
+            return Ok(types.remove(0));
+        }
+
+        Ok(DataType::Intersection(types))
+    }
+
+    fn single_type(&mut self) -> Result<DataType, SyntaxError> {
+        let name = self.identifier()?;
+
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "null" => DataType::Null,
+            "true" => DataType::True,
+            "false" => DataType::False,
+            "void" => DataType::Void,
+            "never" => DataType::Never,
+            "float" => DataType::Float,
+            "bool" => DataType::Boolean,
+            "int" => DataType::Integer,
+            "string" => DataType::String,
+            "array" => DataType::Array,
+            "object" => DataType::Object,
+            "mixed" => DataType::Mixed,
+            "callable" => DataType::Callable,
+            "iterable" => DataType::Iterable,
+            "static" => DataType::StaticReference,
+            "self" => DataType::SelfReference,
+            "parent" => DataType::ParentReference,
+            _ => DataType::Named(Name::new(name)),
+        })
+    }
+
+    fn function(&mut self) -> Result<Function, SyntaxError> {
+        self.expect("function")?;
+        if self.at("&") {
+            return Err(self.error("functions returning by reference are not supported"));
+        }
+
+        let mut function = Function::new(self.identifier()?);
+        function.parameters = self.parameters()?;
+        if self.eat(":") {
+            function.return_type = Some(self.data_type()?);
+        }
+
+        function.body = body(self.block()?);
+
+        Ok(function)
+    }
+}
+
+fn is_trivia(token: &Token) -> bool {
+    matches!(
+        token.kind,
+        TokenKind::Whitespace | TokenKind::Comment | TokenKind::DocComment
+    )
+}
+
+fn is(token: &Token, text: &str) -> bool {
+    match token.kind {
+        TokenKind::Name => token.text.eq_ignore_ascii_case(text),
+        TokenKind::Symbol => token.text == text,
+        _ => false,
+    }
+}
+
+/// Creates a body, printing an empty block as `{}`.
+fn body(code: String) -> Body {
+    if code.is_empty() {
+        return Body::new().with_semicolon_for_empty(false);
+    }
+
+    Body::from(code)
+}
+
+/// Converts a docblock into a document, reading `@` lines as tags.
+fn document(text: &str) -> Document {
+    let text = text.trim_start_matches("/**").trim_end_matches("*/");
+    let mut lines = text
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix('*').unwrap_or(line);
+
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        })
+        .collect::<Vec<&str>>();
+
+    while lines.first().is_some_and(|line| line.is_empty()) {
+        lines.remove(0);
+    }
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    lines.into_iter().fold(Document::new(), |document, line| {
+        if line.is_empty() {
+            document.empty_line()
+        } else if let Some(tag) = line.strip_prefix('@') {
+            match tag.split_once(char::is_whitespace) {
+                Some((tag, description)) => document.tag(tag, description.trim()),
+                None => document.simple_tag(tag),
+            }
+        } else {
+            document.text(line)
+        }
+    })
+}
+
+/// Converts an expression into a value, keeping anything but plain scalars
+/// and simple strings as a literal.
+fn value(text: &str) -> Value {
+    match text.to_ascii_lowercase().as_str() {
+        "null" => return Value::Null,
+        "true" => return Value::True,
+        "false" => return Value::False,
+        _ => {}
+    }
+
+    if let Ok(integer) = text.parse::<i64>() {
+        if integer.to_string() == text {
+            return Value::Integer(integer);
+        }
+    }
+
+    let tokens = tokenize(text).unwrap_or_default();
+    if let [token] = tokens.as_slice() {
+        let inner = &token.text[1..token.text.len() - 1];
+        // Single-quoted strings stay literals, as `Value::String` prints
+        // double-quoted.
+        if token.kind == TokenKind::String && token.text.starts_with('"') {
+            return Value::String(inner.to_string());
+        }
+    }
+
+    Value::Literal(text.to_string())
+}
+
+/// Removes blank lines around `text` and the indentation its lines share.
+fn dedent(text: &str) -> String {
+    let lines = text.lines().collect::<Vec<&str>>();
+    let start = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(start, |end| end + 1);
+    let lines = &lines[start..end];
+
+    let indentation = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ""
+            } else {
+                line[indentation..].trim_end()
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
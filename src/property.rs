@@ -2,25 +2,31 @@ use crate::attribute::AttributeGroup;
 use crate::body::Body;
 use crate::comment::Document;
 use crate::data_type::DataType;
+use crate::identifier::Context;
 use crate::literal::Value;
+use crate::modifiers;
 use crate::modifiers::Modifier;
 use crate::modifiers::VisibilityModifier;
+use crate::naming::Naming;
+use crate::parameter;
+use crate::style::Style;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PropertySetHookParameter {
+    pub attributes: Vec<AttributeGroup>,
     pub data_type: Option<DataType>,
     pub name: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PropertyHook {
     Get(bool, Body),
     Set(Option<PropertySetHookParameter>, Body),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Property {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
@@ -28,6 +34,7 @@ pub struct Property {
     pub modifiers: Vec<Modifier>,
     pub data_type: Option<DataType>,
     pub name: String,
+    pub original_name: Option<String>,
     pub default: Option<Value>,
     pub hooks: Vec<PropertyHook>,
 }
@@ -36,10 +43,17 @@ impl PropertySetHookParameter {
     pub fn new<T: ToString>(name: T) -> Self {
         Self {
             name: name.to_string(),
+            attributes: vec![],
             data_type: None,
         }
     }
 
+    pub fn attributes(mut self, attributes: AttributeGroup) -> Self {
+        self.attributes.push(attributes);
+
+        self
+    }
+
     pub fn typed(mut self, data_type: DataType) -> Self {
         self.data_type = Some(data_type);
 
@@ -51,6 +65,7 @@ impl Property {
     pub fn new<T: ToString>(name: T) -> Self {
         Self {
             name: name.to_string(),
+            original_name: None,
             data_type: None,
             default: None,
             modifiers: vec![],
@@ -61,6 +76,14 @@ impl Property {
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut property = Self::new(naming.apply(&original, Context::Property));
+        property.original_name = Some(original);
+
+        property
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -126,6 +149,13 @@ impl Generator for PropertySetHookParameter {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
+        for attribute in &self.attributes {
+            code.push_str(&format!(
+                "{} ",
+                attribute.generate(indentation, 0).trim_end()
+            ));
+        }
+
         if let Some(data_type) = &self.data_type {
             code.push_str(&format!("{} ", data_type.generate(indentation, level)));
         }
@@ -138,6 +168,10 @@ impl Generator for PropertySetHookParameter {
 
 impl Generator for PropertyHook {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         match self {
             PropertyHook::Get(by_reference, body) => {
                 let mut code = String::new();
@@ -149,7 +183,7 @@ impl Generator for PropertyHook {
                     code.push_str("get");
                 }
 
-                code.push_str(&body.generate(indentation, level + 1));
+                code.push_str(&body.generate_styled(style, indentation, level + 1));
 
                 code
             }
@@ -165,7 +199,7 @@ impl Generator for PropertyHook {
                     code.push(')');
                 }
 
-                code.push_str(&body.generate(indentation, level + 1));
+                code.push_str(&body.generate_styled(style, indentation, level + 1));
 
                 code
             }
@@ -175,13 +209,17 @@ impl Generator for PropertyHook {
 
 impl Generator for Vec<PropertyHook> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         if self.is_empty() {
             return String::from(";");
         }
 
         let hooks = self
             .iter()
-            .map(|hook| hook.generate(indentation, level))
+            .map(|hook| hook.generate_styled(style, indentation, level))
             .collect::<Vec<String>>()
             .join("");
 
@@ -191,44 +229,40 @@ impl Generator for Vec<PropertyHook> {
 
 impl Generator for Property {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
-        let mut code = indentation.value(level);
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
+        let mut code = String::new();
 
         if let Some(document) = &self.documentation {
             code.push_str(&document.generate(indentation, level));
         }
 
-        if !self.attributes.is_empty() {
-            code.push_str(
-                &self
-                    .attributes
-                    .iter()
-                    .map(|attributes| attributes.generate(indentation, level))
-                    .collect::<Vec<String>>()
-                    .join("\n"),
-            );
-
-            code.push('\n');
+        for attribute in &self.attributes {
+            code.push_str(&attribute.generate(indentation, level));
         }
 
-        if let Some(visibility) = &self.visibility {
-            code.push_str(&format!("{} ", visibility.generate(indentation, level)));
-        }
+        code.push_str(&indentation.value(level));
+        code.push_str(&modifiers::print(self.visibility.as_ref(), &self.modifiers));
 
-        for modifier in &self.modifiers {
-            code.push_str(&format!("{} ", modifier.generate(indentation, level)));
+        let mut declaration = format!("${}", &self.name);
+        if let Some(default) = &self.default {
+            declaration.push_str(&format!(" = {}", default.generate(indentation, level)));
         }
 
         if let Some(data_type) = &self.data_type {
-            code.push_str(&format!("{} ", data_type.generate(indentation, level)));
-        }
-
-        code.push_str(&format!("${}", &self.name));
+            let mut printed = data_type.generate_styled(style, indentation, level);
+            let width = parameter::width(&code) + printed.chars().count() + 1;
+            if printed.contains('|') && style.exceeds(width + parameter::width(&declaration) + 1) {
+                printed = data_type.print_wrapped(indentation, level);
+            }
 
-        if let Some(default) = &self.default {
-            code.push_str(&format!(" = {}", default.generate(indentation, level)));
+            code.push_str(&format!("{} ", printed));
         }
 
-        code.push_str(&self.hooks.generate(indentation, level));
+        code.push_str(&declaration);
+        code.push_str(&self.hooks.generate_styled(style, indentation, level));
 
         code
     }
@@ -236,13 +270,17 @@ impl Generator for Property {
 
 impl Generator for Vec<Property> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
         if self.is_empty() {
             return code;
         }
 
         for property in self.iter() {
-            code.push_str(property.generate(indentation, level).as_str());
+            code.push_str(property.generate_styled(style, indentation, level).as_str());
             code.push('\n');
         }
 
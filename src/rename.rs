@@ -0,0 +1,248 @@
+use std::rc::Rc;
+
+use crate::body::Body;
+use crate::file::File;
+use crate::identifier;
+use crate::identifier::Context;
+use crate::import;
+use crate::name::Name;
+use crate::name::Scope;
+use crate::name::SymbolKind;
+use crate::visitor::VisitorMut;
+use crate::visitor::Walk;
+
+/// Moves namespaces and renames classes across a set of files.
+///
+/// References are resolved against the namespace and imports of the file
+/// they appear in, so a name is only changed when it refers to a symbol
+/// being moved or renamed.
+#[derive(Debug, Clone, Default)]
+pub struct Rename {
+    pub namespaces: Vec<(Name, Name)>,
+    pub classes: Vec<(Name, String)>,
+}
+
+impl Rename {
+    pub fn new() -> Self {
+        Self {
+            namespaces: vec![],
+            classes: vec![],
+        }
+    }
+
+    /// Moves everything in `from`, including nested namespaces, to `to`.
+    pub fn namespace<T: Into<Name>, U: Into<Name>>(mut self, from: T, to: U) -> Self {
+        self.namespaces
+            .push((absolute(from.into()), absolute(to.into())));
+
+        self
+    }
+
+    /// Renames the class, interface, trait or enum `from` to `to`, keeping
+    /// it in its namespace.
+    pub fn class<T: Into<Name>, U: ToString>(mut self, from: T, to: U) -> Self {
+        self.classes.push((absolute(from.into()), to.to_string()));
+
+        self
+    }
+
+    /// Returns the new fully qualified name of `name`, if it changes.
+    pub fn resolve(&self, name: &Name, kind: SymbolKind) -> Option<Name> {
+        let mut parts = name.parts.clone();
+
+        if kind == SymbolKind::Class {
+            if let Some((_, to)) = self
+                .classes
+                .iter()
+                .find(|(from, _)| from.matches(&absolute(name.clone())))
+            {
+                *parts.last_mut().unwrap() = to.clone();
+            }
+        }
+
+        let length = parts.len() - 1;
+        if let Some(namespace) = self.relocate(&parts[..length]) {
+            parts.splice(..length, namespace);
+        }
+
+        if parts == name.parts {
+            None
+        } else {
+            Some(Name::fully_qualified(parts))
+        }
+    }
+
+    fn relocate(&self, namespace: &[String]) -> Option<Vec<String>> {
+        let (from, to) = self
+            .namespaces
+            .iter()
+            .filter(|(from, _)| starts_with(namespace, &from.parts))
+            .max_by_key(|(from, _)| from.parts.len())?;
+
+        Some([to.parts.as_slice(), &namespace[from.parts.len()..]].concat())
+    }
+
+    pub fn apply(&self, files: &mut [File]) {
+        for file in files {
+            self.apply_to(file);
+        }
+    }
+
+    fn apply_to(&self, file: &mut File) {
+        let old = file.scope();
+        let declared = |name: &str| match &file.namespace {
+            Some(namespace) => {
+                Name::fully_qualified([namespace.parts.as_slice(), &[name.to_string()]].concat())
+            }
+            None => Name::fully_qualified(vec![name]),
+        };
+
+        let mut renamed = vec![];
+        for name in file
+            .classes
+            .iter()
+            .map(|class| &class.name)
+            .chain(file.interfaces.iter().map(|interface| &interface.name))
+            .chain(file.traits.iter().map(|r#trait| &r#trait.name))
+            .chain(file.enums.iter().map(|r#enum| &r#enum.name))
+        {
+            renamed.push(
+                self.resolve(&declared(name), SymbolKind::Class)
+                    .map(|name| name.last().to_string()),
+            );
+        }
+
+        let mut renamed = renamed.into_iter();
+        for name in file
+            .classes
+            .iter_mut()
+            .map(|class| &mut class.name)
+            .chain(
+                file.interfaces
+                    .iter_mut()
+                    .map(|interface| &mut interface.name),
+            )
+            .chain(file.traits.iter_mut().map(|r#trait| &mut r#trait.name))
+            .chain(file.enums.iter_mut().map(|r#enum| &mut r#enum.name))
+        {
+            if let Some(Some(new)) = renamed.next() {
+                *name = new;
+            }
+        }
+
+        if let Some(namespace) = &mut file.namespace {
+            if let Some(parts) = self.relocate(&namespace.parts) {
+                namespace.parts = parts;
+            }
+        }
+
+        for (kind, imports) in [
+            (SymbolKind::Class, &mut file.uses),
+            (SymbolKind::Function, &mut file.function_uses),
+            (SymbolKind::Constant, &mut file.constant_uses),
+        ] {
+            for import in imports {
+                if let Some(new) = self.resolve(&absolute(import.name.clone()), kind) {
+                    import.name = Name::new(new.unqualified());
+                }
+            }
+        }
+
+        let new = file.scope();
+        file.walk(&mut Renamer {
+            relocation: Rc::new(Relocation {
+                rename: self.clone(),
+                old,
+                new,
+            }),
+        });
+    }
+}
+
+struct Relocation {
+    rename: Rename,
+    old: Scope,
+    new: Scope,
+}
+
+impl Relocation {
+    /// Returns a spelling of `name` that resolves to its new symbol from the
+    /// new scope, preferring the original spelling, then the original
+    /// spelling with its last segment renamed, then the fully qualified name.
+    fn respell(&self, name: &Name, kind: SymbolKind) -> Option<Name> {
+        if name.is_unqualified() && identifier::is_reserved(name.first(), Context::Class) {
+            return None;
+        }
+
+        let resolve = |scope: &Scope, name: &Name| match kind {
+            SymbolKind::Class => scope.resolve_class(name),
+            SymbolKind::Function => scope.resolve_function(name),
+            SymbolKind::Constant => scope.resolve_constant(name),
+        };
+
+        let target = self.rename.resolve(&resolve(&self.old, name), kind)?;
+        let resolves = |candidate: &Name| {
+            import::key(&resolve(&self.new, candidate), kind) == import::key(&target, kind)
+        };
+
+        if resolves(name) {
+            return None;
+        }
+
+        let mut candidate = name.clone();
+        *candidate.parts.last_mut().unwrap() = target.last().to_string();
+        if resolves(&candidate) {
+            return Some(candidate);
+        }
+
+        Some(target)
+    }
+
+    fn rewrite(&self, code: &str) -> String {
+        import::rewrite(code, &|name, kind| {
+            if kind != SymbolKind::Class && !name.is_fully_qualified() {
+                return None;
+            }
+
+            self.respell(name, kind).map(|name| name.to_string())
+        })
+    }
+}
+
+struct Renamer {
+    relocation: Rc<Relocation>,
+}
+
+impl VisitorMut for Renamer {
+    fn name(&mut self, name: &mut Name, kind: SymbolKind) {
+        if let Some(new) = self.relocation.respell(name, kind) {
+            *name = new;
+        }
+    }
+
+    fn code(&mut self, code: &mut String) {
+        *code = self.relocation.rewrite(code);
+    }
+
+    fn body(&mut self, body: &mut Body) {
+        if let Some(factory) = body.factory.clone() {
+            let relocation = self.relocation.clone();
+
+            body.factory = Some(Rc::new(move |indentation, level| {
+                relocation.rewrite(&factory(indentation, level))
+            }));
+        }
+    }
+}
+
+fn absolute(name: Name) -> Name {
+    Name::fully_qualified(name.parts)
+}
+
+fn starts_with(parts: &[String], prefix: &[String]) -> bool {
+    parts.len() >= prefix.len()
+        && parts
+            .iter()
+            .zip(prefix)
+            .all(|(part, prefix)| part.eq_ignore_ascii_case(prefix))
+}
@@ -0,0 +1,328 @@
+use crate::lexer::tokenize;
+use crate::lexer::TokenKind;
+use crate::ordering::MemberOrder;
+use crate::Indentation;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BracePlacement {
+    SameLine,
+    NextLine,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EmptyBody {
+    /// `{}`, on the same line as the signature.
+    Collapsed,
+    /// `{`, then `}` on the next line.
+    Expanded,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// How generated code is laid out. The default keeps the crate's original
+/// layout; [`Style::per_cs`] and [`Style::psr12`] match the output of
+/// php-cs-fixer's `@PER-CS2.0` and `@PSR12` rule sets.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Style {
+    /// Braces of classes, traits, enums and interfaces.
+    pub class_braces: BracePlacement,
+    pub function_braces: BracePlacement,
+    pub method_braces: BracePlacement,
+    /// How bodies without code print. `None` prints a missing body as `{}`
+    /// and empty code as a block with a blank line.
+    pub empty_body: Option<EmptyBody>,
+    /// Blank lines between groups of class members: kinds of members, and
+    /// the groups [`MemberOrder`] forms within them. `None` keeps a blank
+    /// line after trait uses, constants and methods, but not properties.
+    pub blank_lines_between_groups: Option<usize>,
+    /// Blank lines between members of the same group. `None` keeps a blank
+    /// line between constants and methods, but not trait uses or properties.
+    pub blank_lines_between_members: Option<usize>,
+    pub member_order: MemberOrder,
+    /// Whether closures in bodies print as `function (` or `function(`.
+    /// `None` leaves bodies as written.
+    pub space_after_function: Option<bool>,
+    /// Whether arrow functions in bodies print as `fn (` or `fn(`. `None`
+    /// leaves bodies as written.
+    pub space_after_fn: Option<bool>,
+    /// Prints `declare(strict_types = 1)` instead of `declare(strict_types=1)`.
+    pub declare_spacing: bool,
+    /// Parameter lists, `implements` lists and union types that would make a
+    /// line longer than this break over several lines. `None` always breaks
+    /// parameter lists and never breaks the others.
+    pub line_width: Option<usize>,
+    /// Line endings of generated files. Bodies and documentation written with
+    /// other line endings are converted.
+    pub line_ending: LineEnding,
+    /// Ends generated files with a single line ending.
+    pub final_newline: bool,
+    /// Starts generated files with a UTF-8 byte order mark.
+    pub bom: bool,
+    /// Prints nullable types as `?T` instead of `null|T`.
+    pub short_nullable: bool,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self {
+            class_braces: BracePlacement::NextLine,
+            function_braces: BracePlacement::SameLine,
+            method_braces: BracePlacement::SameLine,
+            empty_body: None,
+            blank_lines_between_groups: None,
+            blank_lines_between_members: None,
+            member_order: MemberOrder::new(),
+            space_after_function: None,
+            space_after_fn: None,
+            declare_spacing: false,
+            line_width: None,
+            line_ending: LineEnding::Lf,
+            final_newline: true,
+            bom: false,
+            short_nullable: false,
+        }
+    }
+
+    /// PER Coding Style 2.0.
+    pub fn per_cs() -> Self {
+        Self {
+            class_braces: BracePlacement::NextLine,
+            function_braces: BracePlacement::NextLine,
+            method_braces: BracePlacement::NextLine,
+            empty_body: Some(EmptyBody::Collapsed),
+            blank_lines_between_groups: Some(1),
+            blank_lines_between_members: None,
+            member_order: MemberOrder::new(),
+            space_after_function: Some(true),
+            space_after_fn: Some(false),
+            declare_spacing: false,
+            line_width: Some(120),
+            line_ending: LineEnding::Lf,
+            final_newline: true,
+            bom: false,
+            short_nullable: false,
+        }
+    }
+
+    /// PSR-12, which has no abbreviated empty bodies.
+    pub fn psr12() -> Self {
+        Self {
+            empty_body: Some(EmptyBody::Expanded),
+            space_after_fn: Some(true),
+            ..Self::per_cs()
+        }
+    }
+
+    pub fn class_braces(mut self, placement: BracePlacement) -> Self {
+        self.class_braces = placement;
+
+        self
+    }
+
+    pub fn function_braces(mut self, placement: BracePlacement) -> Self {
+        self.function_braces = placement;
+
+        self
+    }
+
+    pub fn method_braces(mut self, placement: BracePlacement) -> Self {
+        self.method_braces = placement;
+
+        self
+    }
+
+    pub fn empty_body(mut self, empty_body: EmptyBody) -> Self {
+        self.empty_body = Some(empty_body);
+
+        self
+    }
+
+    pub fn blank_lines_between_groups(mut self, blank_lines: usize) -> Self {
+        self.blank_lines_between_groups = Some(blank_lines);
+
+        self
+    }
+
+    pub fn blank_lines_between_members(mut self, blank_lines: usize) -> Self {
+        self.blank_lines_between_members = Some(blank_lines);
+
+        self
+    }
+
+    pub fn member_order(mut self, order: MemberOrder) -> Self {
+        self.member_order = order;
+
+        self
+    }
+
+    pub fn space_after_function(mut self, space: bool) -> Self {
+        self.space_after_function = Some(space);
+
+        self
+    }
+
+    pub fn space_after_fn(mut self, space: bool) -> Self {
+        self.space_after_fn = Some(space);
+
+        self
+    }
+
+    pub fn declare_spacing(mut self, spacing: bool) -> Self {
+        self.declare_spacing = spacing;
+
+        self
+    }
+
+    pub fn line_width(mut self, width: usize) -> Self {
+        self.line_width = Some(width);
+
+        self
+    }
+
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+
+        self
+    }
+
+    pub fn final_newline(mut self, final_newline: bool) -> Self {
+        self.final_newline = final_newline;
+
+        self
+    }
+
+    pub fn bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+
+        self
+    }
+
+    pub fn short_nullable(mut self, short_nullable: bool) -> Self {
+        self.short_nullable = short_nullable;
+
+        self
+    }
+
+    /// Finishes a generated file: trims trailing whitespace, then applies the
+    /// final newline, line endings and byte order mark.
+    pub(crate) fn finish(&self, code: &str) -> String {
+        let mut code = normalize(code).trim_end().to_string();
+        if self.final_newline {
+            code.push('\n');
+        }
+
+        if self.line_ending != LineEnding::Lf {
+            code = code.replace('\n', self.line_ending.as_str());
+        }
+
+        if self.bom {
+            code.insert(0, '\u{feff}');
+        }
+
+        code
+    }
+
+    /// Returns whether a line of `width` columns is too long to keep.
+    pub(crate) fn exceeds(&self, width: usize) -> bool {
+        self.line_width.is_some_and(|limit| width > limit)
+    }
+
+    /// Opens a block after a signature, either ` {` or a `{` on its own line.
+    pub(crate) fn open(
+        &self,
+        placement: BracePlacement,
+        indentation: Indentation,
+        level: usize,
+    ) -> String {
+        match placement {
+            BracePlacement::SameLine => " {\n".to_string(),
+            BracePlacement::NextLine => format!("\n{}{{\n", indentation.value(level)),
+        }
+    }
+
+    /// Applies the closure spacing to code inside a body. Code that does not
+    /// tokenize is left as it is.
+    pub(crate) fn closures(&self, code: String) -> String {
+        if self.space_after_function.is_none() && self.space_after_fn.is_none() {
+            return code;
+        }
+
+        let Ok(tokens) = tokenize(&code) else {
+            return code;
+        };
+
+        let mut result = String::new();
+        let mut index = 0;
+        let mut previous: Option<&str> = None;
+        while index < tokens.len() {
+            let token = &tokens[index];
+            result.push_str(&token.text);
+            index += 1;
+
+            // `$this->fn()` and `Foo::function()` call methods.
+            let member = matches!(previous, Some("->" | "?->" | "::"));
+            if !matches!(
+                token.kind,
+                TokenKind::Whitespace | TokenKind::Comment | TokenKind::DocComment
+            ) {
+                previous = Some(&token.text);
+            }
+
+            if member {
+                continue;
+            }
+
+            let space = match token.kind {
+                TokenKind::Name if token.text.eq_ignore_ascii_case("function") => {
+                    self.space_after_function
+                }
+                TokenKind::Name if token.text.eq_ignore_ascii_case("fn") => self.space_after_fn,
+                _ => None,
+            };
+
+            let Some(space) = space else {
+                continue;
+            };
+
+            let whitespace = tokens.get(index).is_some_and(|token| {
+                token.kind == TokenKind::Whitespace && !token.text.contains('\n')
+            });
+            let next = tokens.get(if whitespace { index + 1 } else { index });
+            if next.is_some_and(|token| token.kind == TokenKind::Symbol && token.text == "(") {
+                if whitespace {
+                    index += 1;
+                }
+
+                if space {
+                    result.push(' ');
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Converts `\r\n` and lone `\r` line endings to `\n`.
+pub(crate) fn normalize(code: &str) -> String {
+    code.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self::new()
+    }
+}
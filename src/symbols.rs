@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+
+use crate::attribute::AttributeGroup;
+use crate::class::Class;
+use crate::constant::ClassConstant;
+use crate::data_type::DataType;
+use crate::file::File;
+use crate::function::Function;
+use crate::identifier;
+use crate::identifier::Context;
+use crate::import;
+use crate::interface::Interface;
+use crate::location::Location;
+use crate::method::Method;
+use crate::name::Name;
+use crate::name::Scope;
+use crate::name::SymbolKind;
+use crate::parameter::Parameter;
+use crate::property::Property;
+use crate::property::PropertyHook;
+use crate::r#enum::Enum;
+use crate::r#trait::Trait;
+use crate::usage::Usage;
+use crate::usage::UsageAdaptation;
+
+const BUILTIN_CLASSES: [&str; 118] = [
+    "AllowDynamicProperties",
+    "AppendIterator",
+    "ArgumentCountError",
+    "ArithmeticError",
+    "ArrayAccess",
+    "ArrayIterator",
+    "ArrayObject",
+    "AssertionError",
+    "Attribute",
+    "BackedEnum",
+    "BadFunctionCallException",
+    "BadMethodCallException",
+    "CachingIterator",
+    "CallbackFilterIterator",
+    "Closure",
+    "CompileError",
+    "Countable",
+    "DateInterval",
+    "DatePeriod",
+    "DateTime",
+    "DateTimeImmutable",
+    "DateTimeInterface",
+    "DateTimeZone",
+    "Deprecated",
+    "Directory",
+    "DirectoryIterator",
+    "DivisionByZeroError",
+    "DomainException",
+    "EmptyIterator",
+    "Error",
+    "ErrorException",
+    "Exception",
+    "Fiber",
+    "FiberError",
+    "FilesystemIterator",
+    "FilterIterator",
+    "Generator",
+    "GlobIterator",
+    "InfiniteIterator",
+    "InvalidArgumentException",
+    "Iterator",
+    "IteratorAggregate",
+    "IteratorIterator",
+    "JsonException",
+    "JsonSerializable",
+    "LengthException",
+    "LimitIterator",
+    "LogicException",
+    "MultipleIterator",
+    "NoRewindIterator",
+    "OutOfBoundsException",
+    "OutOfRangeException",
+    "OuterIterator",
+    "OverflowException",
+    "Override",
+    "ParentIterator",
+    "ParseError",
+    "PDO",
+    "PDOException",
+    "PDORow",
+    "PDOStatement",
+    "RangeException",
+    "RecursiveArrayIterator",
+    "RecursiveCallbackFilterIterator",
+    "RecursiveDirectoryIterator",
+    "RecursiveFilterIterator",
+    "RecursiveIterator",
+    "RecursiveIteratorIterator",
+    "RecursiveRegexIterator",
+    "ReflectionClass",
+    "ReflectionClassConstant",
+    "ReflectionEnum",
+    "ReflectionException",
+    "ReflectionFunction",
+    "ReflectionMethod",
+    "ReflectionNamedType",
+    "ReflectionObject",
+    "ReflectionParameter",
+    "ReflectionProperty",
+    "ReflectionType",
+    "RegexIterator",
+    "ReturnTypeWillChange",
+    "RuntimeException",
+    "SeekableIterator",
+    "SensitiveParameter",
+    "SensitiveParameterValue",
+    "Serializable",
+    "SplDoublyLinkedList",
+    "SplFileInfo",
+    "SplFileObject",
+    "SplFixedArray",
+    "SplHeap",
+    "SplMaxHeap",
+    "SplMinHeap",
+    "SplObjectStorage",
+    "SplObserver",
+    "SplPriorityQueue",
+    "SplQueue",
+    "SplStack",
+    "SplSubject",
+    "SplTempFileObject",
+    "Stringable",
+    "Throwable",
+    "Traversable",
+    "TypeError",
+    "UnderflowException",
+    "UnexpectedValueException",
+    "UnhandledMatchError",
+    "UnitEnum",
+    "ValueError",
+    "WeakMap",
+    "WeakReference",
+    "__PHP_Incomplete_Class",
+    "stdClass",
+    "php_user_filter",
+    "Random\\Randomizer",
+    "Random\\Engine",
+    "Random\\RandomException",
+];
+
+/// Namespaces that PHP's bundled extensions declare classes in.
+const EXTENSION_NAMESPACES: [&str; 4] = ["Dom", "FFI", "Pdo", "Random"];
+
+/// The classes, functions and constants known to exist, used to find
+/// references to symbols that are not declared anywhere.
+///
+/// PHP and its extensions declare more classes than can be listed, so any
+/// class in the global namespace or in an extension's namespace, such as
+/// `Random\Engine\Mt19937`, is taken to exist.
+#[derive(Debug, Clone)]
+pub struct SymbolTable {
+    pub classes: HashMap<String, Name>,
+    pub functions: HashMap<String, Name>,
+    pub constants: HashMap<String, Name>,
+}
+
+/// A reference that does not resolve to any known symbol. `file` is the
+/// index of the file in the slice that was checked.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UndefinedReference {
+    pub file: usize,
+    pub name: Name,
+    pub kind: SymbolKind,
+    pub location: Location,
+}
+
+impl SymbolTable {
+    /// Creates a table holding PHP's builtin classes and interfaces.
+    pub fn new() -> Self {
+        let mut table = Self {
+            classes: HashMap::new(),
+            functions: HashMap::new(),
+            constants: HashMap::new(),
+        };
+
+        for class in BUILTIN_CLASSES {
+            table.insert(Name::new(format!("\\{}", class)), SymbolKind::Class);
+        }
+
+        table
+    }
+
+    pub fn from_files(files: &[File]) -> Self {
+        files
+            .iter()
+            .fold(Self::new(), |table, file| table.file(file))
+    }
+
+    /// Adds the symbols declared in `file`.
+    pub fn file(mut self, file: &File) -> Self {
+        let declared = |name: &str| match &file.namespace {
+            Some(namespace) => Name::fully_qualified(
+                namespace
+                    .parts
+                    .iter()
+                    .map(String::as_str)
+                    .chain([name])
+                    .collect(),
+            ),
+            None => Name::fully_qualified(vec![name]),
+        };
+
+        let classes = file
+            .classes
+            .iter()
+            .map(|class| &class.name)
+            .chain(file.interfaces.iter().map(|interface| &interface.name))
+            .chain(file.traits.iter().map(|r#trait| &r#trait.name))
+            .chain(file.enums.iter().map(|r#enum| &r#enum.name));
+        for class in classes {
+            self.insert(declared(class), SymbolKind::Class);
+        }
+
+        for function in &file.functions {
+            self.insert(declared(&function.name), SymbolKind::Function);
+        }
+
+        for constant in &file.constants {
+            self.insert(declared(&constant.name), SymbolKind::Constant);
+        }
+
+        self
+    }
+
+    /// Adds a symbol declared outside the generated files, such as a class
+    /// from a dependency.
+    pub fn external<T: Into<Name>>(mut self, name: T, kind: SymbolKind) -> Self {
+        let name = name.into();
+        self.insert(Name::fully_qualified(name.parts), kind);
+
+        self
+    }
+
+    pub fn contains(&self, name: &Name, kind: SymbolKind) -> bool {
+        let key = import::key(name, kind);
+
+        match kind {
+            SymbolKind::Class => {
+                self.classes.contains_key(&key)
+                    || name.parts.len() == 1
+                    || EXTENSION_NAMESPACES
+                        .iter()
+                        .any(|namespace| name.first().eq_ignore_ascii_case(namespace))
+            }
+            SymbolKind::Function => self.functions.contains_key(&key),
+            SymbolKind::Constant => self.constants.contains_key(&key),
+        }
+    }
+
+    /// Resolves every class reference in `files` against its file's
+    /// namespace and imports, and returns those that are not in the table.
+    pub fn undefined(&self, files: &[File]) -> Vec<UndefinedReference> {
+        let mut checker = Checker {
+            table: self,
+            file: 0,
+            scope: Scope::default(),
+            undefined: vec![],
+        };
+
+        for (index, file) in files.iter().enumerate() {
+            checker.file = index;
+            checker.scope = file.scope();
+            checker.check_file(file, &Location::new().child("File"));
+        }
+
+        checker.undefined
+    }
+
+    fn insert(&mut self, name: Name, kind: SymbolKind) {
+        let key = import::key(&name, kind);
+
+        match kind {
+            SymbolKind::Class => self.classes.insert(key, name),
+            SymbolKind::Function => self.functions.insert(key, name),
+            SymbolKind::Constant => self.constants.insert(key, name),
+        };
+    }
+}
+
+struct Checker<'a> {
+    table: &'a SymbolTable,
+    file: usize,
+    scope: Scope,
+    undefined: Vec<UndefinedReference>,
+}
+
+impl Checker<'_> {
+    fn check_file(&mut self, file: &File, location: &Location) {
+        for function in &file.functions {
+            self.check_function(
+                function,
+                &location.child(format!("Function {}", function.name)),
+            );
+        }
+
+        for class in &file.classes {
+            self.check_class(class, &location.child(format!("Class {}", class.name)));
+        }
+
+        for r#trait in &file.traits {
+            self.check_trait(r#trait, &location.child(format!("Trait {}", r#trait.name)));
+        }
+
+        for r#enum in &file.enums {
+            self.check_enum(r#enum, &location.child(format!("Enum {}", r#enum.name)));
+        }
+
+        for interface in &file.interfaces {
+            self.check_interface(
+                interface,
+                &location.child(format!("Interface {}", interface.name)),
+            );
+        }
+    }
+
+    fn check_class(&mut self, class: &Class, location: &Location) {
+        self.check_attributes(&class.attributes, location);
+        if let Some(extends) = &class.extends {
+            self.check(extends, location);
+        }
+
+        for implements in &class.implements {
+            self.check(implements, location);
+        }
+
+        self.check_usages(&class.usages, location);
+        self.check_constants(&class.constants, location);
+        self.check_properties(&class.properties, location);
+        self.check_methods(&class.methods, location);
+    }
+
+    fn check_interface(&mut self, interface: &Interface, location: &Location) {
+        self.check_attributes(&interface.attributes, location);
+        if let Some(extends) = &interface.extends {
+            self.check(extends, location);
+        }
+
+        self.check_methods(&interface.methods, location);
+    }
+
+    fn check_trait(&mut self, r#trait: &Trait, location: &Location) {
+        self.check_attributes(&r#trait.attributes, location);
+        self.check_usages(&r#trait.usages, location);
+        self.check_constants(&r#trait.constants, location);
+        self.check_properties(&r#trait.properties, location);
+        self.check_methods(&r#trait.methods, location);
+    }
+
+    fn check_enum(&mut self, r#enum: &Enum, location: &Location) {
+        self.check_attributes(&r#enum.attributes, location);
+        for implements in &r#enum.implements {
+            self.check(implements, location);
+        }
+
+        self.check_usages(&r#enum.usages, location);
+        self.check_constants(&r#enum.constants, location);
+        for case in &r#enum.cases {
+            self.check_attributes(
+                &case.attributes,
+                &location.child(format!("case {}", case.name)),
+            );
+        }
+
+        self.check_methods(&r#enum.methods, location);
+    }
+
+    fn check_function(&mut self, function: &Function, location: &Location) {
+        self.check_attributes(&function.attributes, location);
+        self.check_parameters(&function.parameters, location);
+        if let Some(return_type) = &function.return_type {
+            self.check_type(return_type, location);
+        }
+    }
+
+    fn check_methods(&mut self, methods: &[Method], location: &Location) {
+        for method in methods {
+            let location = location.child(format!("method {}", method.name));
+
+            self.check_attributes(&method.attributes, &location);
+            self.check_parameters(&method.parameters, &location);
+            if let Some(return_type) = &method.return_type {
+                self.check_type(return_type, &location);
+            }
+        }
+    }
+
+    fn check_parameters(&mut self, parameters: &[Parameter], location: &Location) {
+        for parameter in parameters {
+            let location = location.child(format!("param ${}", parameter.name));
+
+            self.check_attributes(&parameter.attributes, &location);
+            if let Some(data_type) = &parameter.data_type {
+                self.check_type(data_type, &location);
+            }
+        }
+    }
+
+    fn check_properties(&mut self, properties: &[Property], location: &Location) {
+        for property in properties {
+            let location = location.child(format!("property ${}", property.name));
+
+            self.check_attributes(&property.attributes, &location);
+            if let Some(data_type) = &property.data_type {
+                self.check_type(data_type, &location);
+            }
+
+            for hook in &property.hooks {
+                if let PropertyHook::Set(Some(parameter), _) = hook {
+                    if let Some(data_type) = &parameter.data_type {
+                        self.check_type(data_type, &location.child("set"));
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_constants(&mut self, constants: &[ClassConstant], location: &Location) {
+        for constant in constants {
+            let location = location.child(format!("constant {}", constant.name));
+
+            self.check_attributes(&constant.attributes, &location);
+            if let Some(data_type) = &constant.data_type {
+                self.check_type(data_type, &location);
+            }
+        }
+    }
+
+    fn check_usages(&mut self, usages: &[Usage], location: &Location) {
+        for usage in usages {
+            for name in &usage.traits {
+                self.check(name, location);
+            }
+
+            for adaptation in &usage.adaptations {
+                if let UsageAdaptation::Precedence { insteadof, .. } = adaptation {
+                    for name in insteadof {
+                        self.check(name, location);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_attributes(&mut self, attributes: &[AttributeGroup], location: &Location) {
+        for group in attributes {
+            for (name, _) in &group.members {
+                self.check(name, location);
+            }
+        }
+    }
+
+    fn check_type(&mut self, data_type: &DataType, location: &Location) {
+        match data_type {
+            DataType::Named(name) => self.check(name, location),
+            DataType::Nullable(inner) => self.check_type(inner, location),
+            DataType::Union(inner) | DataType::Intersection(inner) => {
+                for data_type in inner {
+                    self.check_type(data_type, location);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check(&mut self, name: &Name, location: &Location) {
+        if name.is_unqualified() && identifier::is_reserved(name.first(), Context::Class) {
+            return;
+        }
+
+        let resolved = name.resolve(&self.scope);
+        if !self.table.contains(&resolved, SymbolKind::Class) {
+            self.undefined.push(UndefinedReference {
+                file: self.file,
+                name: resolved,
+                kind: SymbolKind::Class,
+                location: location.clone(),
+            });
+        }
+    }
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
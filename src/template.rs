@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::body::Body;
+use crate::data_type::DataType;
+use crate::interpolation;
+use crate::literal::Value;
+use crate::Generator;
+use crate::Indentation;
+
+#[derive(Debug, Clone)]
+pub enum Argument {
+    Name(String),
+    Type(DataType),
+    Value(Value),
+    Body(Body),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TemplateError {
+    MissingArgument(String),
+    UnusedArgument(String),
+    DuplicateArgument(String),
+    InvalidName { placeholder: String, name: String },
+    MisplacedBody { placeholder: String, line: usize },
+}
+
+/// A body written as PHP source with `{placeholder}` markers.
+///
+/// Leading whitespace is read in steps of four spaces or one tab and
+/// re-emitted using the generator's indentation. `{{` produces a literal `{`.
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub source: String,
+    pub arguments: Vec<(String, Argument)>,
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Text(String),
+    Placeholder(String),
+}
+
+#[derive(Debug, Clone)]
+struct Line {
+    depth: usize,
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub fn new<T: ToString>(source: T) -> Self {
+        Self {
+            source: source.to_string(),
+            arguments: vec![],
+        }
+    }
+
+    pub fn argument<T: ToString>(mut self, placeholder: T, argument: Argument) -> Self {
+        self.arguments.push((placeholder.to_string(), argument));
+
+        self
+    }
+
+    pub fn name<T: ToString, Tn: ToString>(self, placeholder: T, name: Tn) -> Self {
+        self.argument(placeholder, Argument::Name(name.to_string()))
+    }
+
+    pub fn typed<T: ToString>(self, placeholder: T, data_type: DataType) -> Self {
+        self.argument(placeholder, Argument::Type(data_type))
+    }
+
+    pub fn value<T: ToString, Tv: Into<Value>>(self, placeholder: T, value: Tv) -> Self {
+        self.argument(placeholder, Argument::Value(value.into()))
+    }
+
+    pub fn body<T: ToString, Tb: Into<Body>>(self, placeholder: T, body: Tb) -> Self {
+        self.argument(placeholder, Argument::Body(body.into()))
+    }
+
+    pub fn render(self) -> Result<Body, TemplateError> {
+        let lines = parse(&self.source);
+
+        let mut arguments = HashMap::new();
+        for (placeholder, argument) in self.arguments {
+            if let Argument::Name(name) = &argument {
+                if !is_valid_name(name) {
+                    return Err(TemplateError::InvalidName {
+                        placeholder,
+                        name: name.to_string(),
+                    });
+                }
+            }
+
+            if arguments.contains_key(&placeholder) {
+                return Err(TemplateError::DuplicateArgument(placeholder));
+            }
+
+            arguments.insert(placeholder, argument);
+        }
+
+        let mut used = vec![];
+        for (number, line) in lines.iter().enumerate() {
+            for part in &line.parts {
+                let Part::Placeholder(placeholder) = part else {
+                    continue;
+                };
+
+                match arguments.get(placeholder) {
+                    None => return Err(TemplateError::MissingArgument(placeholder.clone())),
+                    Some(Argument::Body(_)) if line.parts.len() > 1 => {
+                        return Err(TemplateError::MisplacedBody {
+                            placeholder: placeholder.clone(),
+                            line: number + 1,
+                        })
+                    }
+                    _ => used.push(placeholder.clone()),
+                }
+            }
+        }
+
+        let mut unused = arguments
+            .keys()
+            .filter(|placeholder| !used.contains(placeholder))
+            .collect::<Vec<&String>>();
+        unused.sort();
+        if let Some(placeholder) = unused.first() {
+            return Err(TemplateError::UnusedArgument(placeholder.to_string()));
+        }
+
+        Ok(Body::with_factory(move |indentation, level| {
+            render(&lines, &arguments, indentation, level)
+        }))
+    }
+}
+
+impl From<DataType> for Argument {
+    fn from(data_type: DataType) -> Self {
+        Argument::Type(data_type)
+    }
+}
+
+impl From<Value> for Argument {
+    fn from(value: Value) -> Self {
+        Argument::Value(value)
+    }
+}
+
+impl From<Body> for Argument {
+    fn from(body: Body) -> Self {
+        Argument::Body(body)
+    }
+}
+
+impl From<String> for Argument {
+    fn from(name: String) -> Self {
+        Argument::Name(name)
+    }
+}
+
+impl From<&str> for Argument {
+    fn from(name: &str) -> Self {
+        Argument::Name(name.to_string())
+    }
+}
+
+impl TryFrom<Template> for Body {
+    type Error = TemplateError;
+
+    fn try_from(template: Template) -> Result<Self, Self::Error> {
+        template.render()
+    }
+}
+
+fn render(
+    lines: &[Line],
+    arguments: &HashMap<String, Argument>,
+    indentation: Indentation,
+    level: usize,
+) -> String {
+    let mut code = vec![];
+
+    for line in lines {
+        if let [Part::Placeholder(placeholder)] = line.parts.as_slice() {
+            if let Argument::Body(body) = &arguments[placeholder] {
+                if let Some(factory) = &body.factory {
+                    code.push(factory(indentation, level + line.depth));
+                }
+
+                continue;
+            }
+        }
+
+        let text = line
+            .parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.to_string(),
+                Part::Placeholder(placeholder) => match &arguments[placeholder] {
+                    Argument::Name(name) => name.to_string(),
+                    Argument::Type(data_type) => data_type.generate(indentation, 0),
+                    Argument::Value(value) => escaped(value).generate(indentation, 0),
+                    Argument::Body(_) => unreachable!(),
+                },
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        code.push(indentation.indent(text, level + line.depth));
+    }
+
+    code.join("\n")
+}
+
+/// Escapes the strings in `value`, which print as written otherwise.
+fn escaped(value: &Value) -> Value {
+    match value {
+        Value::String(string) => {
+            Value::Literal(format!("\"{}\"", interpolation::escape(string, false)))
+        }
+        Value::List(values) => Value::List(values.iter().map(escaped).collect()),
+        Value::HashMap(entries) => Value::HashMap(
+            entries
+                .iter()
+                .map(|(key, value)| (escaped(key), escaped(value)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+fn parse(source: &str) -> Vec<Line> {
+    let mut lines = vec![];
+
+    for line in source.trim_matches('\n').lines() {
+        let mut depth = 0;
+        let mut rest = line;
+        loop {
+            if let Some(stripped) = rest.strip_prefix('\t') {
+                rest = stripped;
+            } else if let Some(stripped) = rest.strip_prefix("    ") {
+                rest = stripped;
+            } else {
+                break;
+            }
+
+            depth += 1;
+        }
+
+        lines.push(Line {
+            depth,
+            parts: parse_parts(rest),
+        });
+    }
+
+    lines
+}
+
+fn parse_parts(line: &str) -> Vec<Part> {
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut rest = line;
+
+    while let Some(position) = rest.find('{') {
+        text.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        if let Some(stripped) = rest.strip_prefix("{{") {
+            text.push('{');
+            rest = stripped;
+
+            continue;
+        }
+
+        let length = rest[1..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len() - 1);
+        let placeholder = &rest[1..1 + length];
+        let closed = rest[1 + length..].starts_with('}');
+
+        if closed
+            && !placeholder.is_empty()
+            && !placeholder.starts_with(|c: char| c.is_ascii_digit())
+        {
+            if !text.is_empty() {
+                parts.push(Part::Text(std::mem::take(&mut text)));
+            }
+
+            parts.push(Part::Placeholder(placeholder.to_string()));
+            rest = &rest[length + 2..];
+        } else {
+            text.push('{');
+            rest = &rest[1..];
+        }
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+
+    parts
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let name = name.strip_prefix('\\').unwrap_or(name);
+
+    !name.is_empty()
+        && name.split('\\').all(|segment| {
+            let mut characters = segment.chars();
+
+            matches!(characters.next(), Some(c) if c == '_' || c.is_alphabetic())
+                && characters.all(|c| c == '_' || c.is_alphanumeric())
+        })
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::MissingArgument(placeholder) => {
+                write!(f, "no argument given for placeholder `{{{}}}`", placeholder)
+            }
+            TemplateError::UnusedArgument(placeholder) => write!(
+                f,
+                "argument `{}` does not match any placeholder in the template",
+                placeholder
+            ),
+            TemplateError::DuplicateArgument(placeholder) => {
+                write!(f, "argument `{}` is given more than once", placeholder)
+            }
+            TemplateError::InvalidName { placeholder, name } => write!(
+                f,
+                "argument `{}` is not a valid PHP name: `{}`",
+                placeholder, name
+            ),
+            TemplateError::MisplacedBody { placeholder, line } => write!(
+                f,
+                "body placeholder `{{{}}}` on line {} must be on a line of its own",
+                placeholder, line
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
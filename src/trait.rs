@@ -1,17 +1,22 @@
 use crate::attribute::AttributeGroup;
 use crate::comment::Document;
 use crate::constant::ClassConstant;
+use crate::identifier::Context;
 use crate::method::Method;
+use crate::naming::Naming;
+use crate::ordering;
 use crate::property::Property;
+use crate::style::Style;
 use crate::usage::Usage;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Trait {
     pub documentation: Option<Document>,
     pub attributes: Vec<AttributeGroup>,
     pub name: String,
+    pub original_name: Option<String>,
     pub usages: Vec<Usage>,
     pub constants: Vec<ClassConstant>,
     pub properties: Vec<Property>,
@@ -24,6 +29,7 @@ impl Trait {
             documentation: None,
             attributes: vec![],
             name: name.to_string(),
+            original_name: None,
             usages: vec![],
             constants: vec![],
             properties: vec![],
@@ -31,6 +37,14 @@ impl Trait {
         }
     }
 
+    pub fn named<T: ToString>(name: T, naming: &Naming) -> Self {
+        let original = name.to_string();
+        let mut r#trait = Self::new(naming.apply(&original, Context::Class));
+        r#trait.original_name = Some(original);
+
+        r#trait
+    }
+
     pub fn document(mut self, documentation: Document) -> Self {
         self.documentation = Some(documentation);
 
@@ -70,6 +84,10 @@ impl Trait {
 
 impl Generator for Trait {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
 
         if let Some(documentation) = &self.documentation {
@@ -82,12 +100,17 @@ impl Generator for Trait {
 
         code.push_str(format!("trait {}", self.name).as_str());
 
-        code.push_str("\n{\n");
+        code.push_str(&style.open(style.class_braces, indentation, level));
 
-        code.push_str(self.usages.generate(indentation, level + 1).as_str());
-        code.push_str(self.constants.generate(indentation, level + 1).as_str());
-        code.push_str(self.properties.generate(indentation, level + 1).as_str());
-        code.push_str(self.methods.generate(indentation, level + 1).as_str());
+        code.push_str(&ordering::members(
+            &self.usages,
+            &self.constants,
+            &self.properties,
+            &self.methods,
+            style,
+            indentation,
+            level + 1,
+        ));
 
         code = code.trim_end().to_string();
         code.push_str("\n}\n");
@@ -98,13 +121,17 @@ impl Generator for Trait {
 
 impl Generator for Vec<Trait> {
     fn generate(&self, indentation: Indentation, level: usize) -> String {
+        self.generate_styled(&Style::default(), indentation, level)
+    }
+
+    fn generate_styled(&self, style: &Style, indentation: Indentation, level: usize) -> String {
         let mut code = String::new();
         if self.is_empty() {
             return code;
         }
 
         for r#trait in self {
-            code.push_str(r#trait.generate(indentation, level).as_str());
+            code.push_str(r#trait.generate_styled(style, indentation, level).as_str());
             code.push('\n');
         }
 
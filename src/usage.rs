@@ -1,14 +1,15 @@
 use crate::modifiers::VisibilityModifier;
+use crate::name::Name;
 use crate::Generator;
 use crate::Indentation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Usage {
-    pub traits: Vec<String>,
+    pub traits: Vec<Name>,
     pub adaptations: Vec<UsageAdaptation>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum UsageAdaptation {
     Alias {
         method: String,
@@ -21,20 +22,20 @@ pub enum UsageAdaptation {
     },
     Precedence {
         method: String,
-        insteadof: Vec<String>,
+        insteadof: Vec<Name>,
     },
 }
 
 impl Usage {
-    pub fn new(traits: Vec<String>) -> Self {
+    pub fn new<T: Into<Name>>(traits: Vec<T>) -> Self {
         Self {
-            traits,
+            traits: traits.into_iter().map(|r#trait| r#trait.into()).collect(),
             adaptations: vec![],
         }
     }
 
-    pub fn with<T: ToString>(mut self, r#trait: T) -> Self {
-        self.traits.push(r#trait.to_string());
+    pub fn with<T: Into<Name>>(mut self, r#trait: T) -> Self {
+        self.traits.push(r#trait.into());
 
         self
     }
@@ -100,12 +101,12 @@ impl Usage {
         self
     }
 
-    pub fn precede<T: ToString>(mut self, method: T, insteadof: Vec<T>) -> Self {
+    pub fn precede<T: ToString + Into<Name>>(mut self, method: T, insteadof: Vec<T>) -> Self {
         self.adaptations.push(UsageAdaptation::Precedence {
             method: method.to_string(),
             insteadof: insteadof
                 .into_iter()
-                .map(|insteadof| insteadof.to_string())
+                .map(|insteadof| insteadof.into())
                 .collect(),
         });
 
@@ -118,7 +119,14 @@ impl Generator for Usage {
         let mut code = indentation.indent("use", level);
 
         code.push(' ');
-        code.push_str(&self.traits.join(", "));
+        code.push_str(
+            &self
+                .traits
+                .iter()
+                .map(|r#trait| r#trait.to_string())
+                .collect::<Vec<String>>()
+                .join(", "),
+        );
 
         if !self.adaptations.is_empty() {
             code.push_str(" {\n");
@@ -142,7 +150,15 @@ impl Generator for Usage {
                         level + 1,
                     ),
                     UsageAdaptation::Precedence { method, insteadof } => indentation.indent(
-                        format!("{} insteadof {}", method, insteadof.join(", ")),
+                        format!(
+                            "{} insteadof {}",
+                            method,
+                            insteadof
+                                .iter()
+                                .map(|r#trait| r#trait.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
                         level + 1,
                     ),
                 };
@@ -184,14 +200,22 @@ impl From<String> for Usage {
 
 impl From<&str> for Usage {
     fn from(r#trait: &str) -> Self {
-        Self::new(vec![r#trait.to_string()])
+        Self::new(vec![r#trait])
+    }
+}
+
+impl From<Name> for Usage {
+    fn from(r#trait: Name) -> Self {
+        Self::new(vec![r#trait])
     }
 }
 
-impl<T: ToString> From<Vec<T>> for Usage {
+impl<T: Into<Name>> From<Vec<T>> for Usage {
     fn from(traits: Vec<T>) -> Self {
         traits
             .into_iter()
-            .fold(Self::new(vec![]), |usage, r#trait| usage.with(r#trait))
+            .fold(Self::new(Vec::<Name>::new()), |usage, r#trait| {
+                usage.with(r#trait)
+            })
     }
 }
@@ -0,0 +1,954 @@
+use std::fmt::Display;
+
+use crate::class::Class;
+use crate::constant::ClassConstant;
+use crate::constant::Constant;
+use crate::data_type::DataType;
+use crate::downlevel;
+use crate::enum_case::EnumCase;
+use crate::file::File;
+use crate::function::Function;
+use crate::identifier;
+use crate::identifier::Context;
+use crate::identifier::IdentifierError;
+use crate::interface::Interface;
+use crate::literal::Value;
+use crate::location::Location;
+use crate::method::Method;
+use crate::modifiers;
+use crate::modifiers::Modifier;
+use crate::modifiers::ModifierSite;
+use crate::modifiers::VisibilityModifier;
+use crate::name::Name;
+use crate::name::Scope;
+use crate::parameter::Parameter;
+use crate::property::Property;
+use crate::r#enum;
+use crate::r#enum::Enum;
+use crate::r#enum::EnumBackingType;
+use crate::r#trait::Trait;
+use crate::usage::Usage;
+use crate::usage::UsageAdaptation;
+use crate::Generator;
+use crate::Indentation;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem found in a model, such as an abstract method with a body.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub rule: &'static str,
+    pub message: String,
+    pub location: Location,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ValidationError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+pub trait Validator {
+    /// Appends the problems found in `self` to `diagnostics`, with paths
+    /// relative to `location`.
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>);
+
+    fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        self.validate_at(&Location::new(), &mut diagnostics);
+
+        diagnostics
+    }
+
+    /// Generates code only when validation finds no errors. Warnings do not
+    /// prevent generation.
+    fn try_generate(
+        &self,
+        indentation: Indentation,
+        level: usize,
+    ) -> Result<String, ValidationError>
+    where
+        Self: Generator,
+    {
+        let diagnostics = self.validate();
+        if diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+        {
+            return Err(ValidationError { diagnostics });
+        }
+
+        Ok(self.generate(indentation, level))
+    }
+}
+
+impl Diagnostic {
+    pub fn error<T: ToString>(rule: &'static str, location: &Location, message: T) -> Self {
+        Self {
+            severity: Severity::Error,
+            rule,
+            message: message.to_string(),
+            location: location.clone(),
+        }
+    }
+
+    pub fn warning<T: ToString>(rule: &'static str, location: &Location, message: T) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(rule, location, message)
+        }
+    }
+}
+
+impl Validator for File {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child("File");
+
+        for constant in &self.constants {
+            constant.validate_at(&location, diagnostics);
+        }
+
+        for function in &self.functions {
+            function.validate_at(&location, diagnostics);
+        }
+
+        let scope = self.scope();
+        let traits = self.traits.iter().collect::<Vec<&Trait>>();
+
+        for class in &self.classes {
+            class.validate_at(&location, diagnostics);
+            check_trait_conflicts(
+                &class.usages,
+                &class.methods,
+                &scope,
+                &traits,
+                &location.child(format!("Class {}", class.name)),
+                diagnostics,
+            );
+        }
+
+        for r#trait in &self.traits {
+            r#trait.validate_at(&location, diagnostics);
+            check_trait_conflicts(
+                &r#trait.usages,
+                &r#trait.methods,
+                &scope,
+                &traits,
+                &location.child(format!("Trait {}", r#trait.name)),
+                diagnostics,
+            );
+        }
+
+        for r#enum in &self.enums {
+            r#enum.validate_at(&location, diagnostics);
+            check_trait_conflicts(
+                &r#enum.usages,
+                &r#enum.methods,
+                &scope,
+                &traits,
+                &location.child(format!("Enum {}", r#enum.name)),
+                diagnostics,
+            );
+        }
+
+        for interface in &self.interfaces {
+            interface.validate_at(&location, diagnostics);
+        }
+
+        if let Some(target) = self.target {
+            let mut file = self.clone();
+            diagnostics.extend(downlevel::lower_at(&mut file, target, &location));
+        }
+    }
+}
+
+impl Validator for Class {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("Class {}", self.name));
+        check_identifier(&self.name, Context::Class, &location, diagnostics);
+        check_members(
+            &self.methods,
+            &self.properties,
+            &self.constants,
+            &location,
+            diagnostics,
+        );
+        check_modifiers(
+            ModifierSite::Class,
+            None,
+            &self.modifiers,
+            &location,
+            diagnostics,
+        );
+
+        let r#abstract = self.modifiers.contains(&Modifier::Abstract);
+        for constant in &self.constants {
+            validate_constant(
+                constant,
+                ModifierSite::ClassConstant,
+                &location,
+                diagnostics,
+            );
+        }
+
+        for property in &self.properties {
+            property.validate_at(&location, diagnostics);
+        }
+
+        for method in &self.methods {
+            validate_method(method, ModifierSite::Method, &location, diagnostics);
+
+            let method_location = location.child(format!("method {}", method.name));
+            if is_abstract(method) && !r#abstract {
+                diagnostics.push(Diagnostic::error(
+                    "abstract-method-in-concrete-class",
+                    &method_location,
+                    format!(
+                        "class `{}` must be abstract to declare abstract method `{}`",
+                        self.name, method.name
+                    ),
+                ));
+            }
+
+            check_method_body(method, &method_location, diagnostics);
+        }
+    }
+}
+
+impl Validator for Trait {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("Trait {}", self.name));
+        check_identifier(&self.name, Context::Class, &location, diagnostics);
+        check_members(
+            &self.methods,
+            &self.properties,
+            &self.constants,
+            &location,
+            diagnostics,
+        );
+
+        for constant in &self.constants {
+            validate_constant(
+                constant,
+                ModifierSite::ClassConstant,
+                &location,
+                diagnostics,
+            );
+        }
+
+        for property in &self.properties {
+            property.validate_at(&location, diagnostics);
+        }
+
+        for method in &self.methods {
+            validate_method(method, ModifierSite::TraitMethod, &location, diagnostics);
+            check_method_body(
+                method,
+                &location.child(format!("method {}", method.name)),
+                diagnostics,
+            );
+        }
+    }
+}
+
+impl Validator for Interface {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("Interface {}", self.name));
+        check_identifier(&self.name, Context::Class, &location, diagnostics);
+        check_members(&self.methods, &[], &[], &location, diagnostics);
+
+        for method in &self.methods {
+            validate_method(
+                method,
+                ModifierSite::InterfaceMethod,
+                &location,
+                diagnostics,
+            );
+
+            let method_location = location.child(format!("method {}", method.name));
+            if method.body.factory.is_some() {
+                diagnostics.push(Diagnostic::error(
+                    "interface-method-body",
+                    &method_location,
+                    format!("interface method `{}` cannot have a body", method.name),
+                ));
+            }
+        }
+    }
+}
+
+impl Validator for Enum {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("Enum {}", self.name));
+        check_identifier(&self.name, Context::Class, &location, diagnostics);
+        check_members(&self.methods, &[], &self.constants, &location, diagnostics);
+        check_duplicates(
+            self.cases.iter().map(|case| case.name.as_str()),
+            true,
+            "duplicate-enum-case",
+            &|name| format!("case {}", name),
+            &location,
+            diagnostics,
+        );
+
+        for constant in &self.constants {
+            validate_constant(constant, ModifierSite::EnumConstant, &location, diagnostics);
+        }
+
+        for (index, case) in self.cases.iter().enumerate() {
+            case.validate_at(&location, diagnostics);
+
+            let case_location = location.child(format!("case {}", case.name));
+            match (&self.backing_type, &case.value) {
+                (Some(_), None) => diagnostics.push(Diagnostic::error(
+                    "enum-case-missing-value",
+                    &case_location,
+                    format!(
+                        "case `{}` of backed enum `{}` must have a value",
+                        case.name, self.name
+                    ),
+                )),
+                (None, Some(_)) => diagnostics.push(Diagnostic::error(
+                    "enum-case-unexpected-value",
+                    &case_location,
+                    format!(
+                        "case `{}` of pure enum `{}` cannot have a value",
+                        case.name, self.name
+                    ),
+                )),
+                // Raw literals may be any constant expression, so only their
+                // presence is checked.
+                (Some(_), Some(Value::Literal(_))) => {}
+                (Some(backing_type), Some(value)) => {
+                    if EnumBackingType::of(value) != Some(*backing_type) {
+                        diagnostics.push(Diagnostic::error(
+                            "enum-case-value-type",
+                            &case_location,
+                            format!(
+                                "case `{}` of enum `{}` must have a value of type `{}`, found `{}`",
+                                case.name,
+                                self.name,
+                                backing_type,
+                                value.generate(Indentation::default(), 0)
+                            ),
+                        ));
+                    }
+
+                    let duplicate = self.cases.iter().take(index).find(|other| {
+                        other
+                            .value
+                            .as_ref()
+                            .is_some_and(|other| r#enum::same_value(other, value))
+                    });
+
+                    if let Some(other) = duplicate {
+                        diagnostics.push(Diagnostic::error(
+                            "duplicate-enum-value",
+                            &case_location,
+                            format!(
+                                "case `{}` has the same value as case `{}`",
+                                case.name, other.name
+                            ),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+
+            if self
+                .constants
+                .iter()
+                .any(|constant| constant.name == case.name)
+            {
+                diagnostics.push(Diagnostic::error(
+                    "enum-case-constant-clash",
+                    &case_location,
+                    format!(
+                        "case `{}` clashes with a constant of the same name in enum `{}`",
+                        case.name, self.name
+                    ),
+                ));
+            }
+        }
+
+        for method in &self.methods {
+            validate_method(method, ModifierSite::EnumMethod, &location, diagnostics);
+
+            let method_location = location.child(format!("method {}", method.name));
+            if is_abstract(method) {
+                diagnostics.push(Diagnostic::error(
+                    "abstract-method-in-enum",
+                    &method_location,
+                    format!("enum `{}` cannot declare abstract methods", self.name),
+                ));
+            }
+
+            check_method_body(method, &method_location, diagnostics);
+        }
+    }
+}
+
+impl Validator for Function {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("Function {}", self.name));
+        check_identifier(&self.name, Context::Function, &location, diagnostics);
+        check_parameters(&self.parameters, false, &location, diagnostics);
+
+        if let Some(return_type) = &self.return_type {
+            check_type(return_type, &location, diagnostics);
+        }
+    }
+}
+
+impl Validator for Method {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        validate_method(self, ModifierSite::Method, location, diagnostics);
+    }
+}
+
+impl Validator for Parameter {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("param ${}", self.name));
+        check_identifier(&self.name, Context::Variable, &location, diagnostics);
+        if self.visibility.is_some() || !self.modifiers.is_empty() {
+            check_modifiers(
+                ModifierSite::PromotedParameter,
+                self.visibility.as_ref(),
+                &self.modifiers,
+                &location,
+                diagnostics,
+            );
+        }
+
+        if let Some(data_type) = &self.data_type {
+            check_type(data_type, &location, diagnostics);
+
+            if returns_only(data_type) {
+                diagnostics.push(Diagnostic::error(
+                    "invalid-parameter-type",
+                    &location,
+                    "`void` and `never` can only be used as return types",
+                ));
+            }
+        }
+
+        if self.variadic && self.default.is_some() {
+            diagnostics.push(Diagnostic::error(
+                "variadic-parameter-default",
+                &location,
+                "variadic parameters cannot have a default value",
+            ));
+        }
+    }
+}
+
+impl Validator for Property {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("property ${}", self.name));
+        check_identifier(&self.name, Context::Property, &location, diagnostics);
+        check_modifiers(
+            ModifierSite::Property,
+            self.visibility.as_ref(),
+            &self.modifiers,
+            &location,
+            diagnostics,
+        );
+
+        if let Some(data_type) = &self.data_type {
+            check_type(data_type, &location, diagnostics);
+
+            if returns_only(data_type) || contains(data_type, &|t| matches!(t, DataType::Callable))
+            {
+                diagnostics.push(Diagnostic::error(
+                    "invalid-property-type",
+                    &location,
+                    "properties cannot be typed `void`, `never` or `callable`",
+                ));
+            }
+        }
+
+        if self.modifiers.contains(&Modifier::Readonly) {
+            if self.data_type.is_none() {
+                diagnostics.push(Diagnostic::error(
+                    "readonly-property-without-type",
+                    &location,
+                    format!("readonly property `${}` must have a type", self.name),
+                ));
+            }
+
+            if self.default.is_some() {
+                diagnostics.push(Diagnostic::error(
+                    "readonly-property-default",
+                    &location,
+                    format!(
+                        "readonly property `${}` cannot have a default value",
+                        self.name
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+impl Validator for ClassConstant {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        validate_constant(self, ModifierSite::ClassConstant, location, diagnostics);
+    }
+}
+
+impl Validator for Constant {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("constant {}", self.name));
+        check_identifier(&self.name, Context::Constant, &location, diagnostics);
+    }
+}
+
+impl Validator for EnumCase {
+    fn validate_at(&self, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+        let location = location.child(format!("case {}", self.name));
+        check_identifier(&self.name, Context::EnumCase, &location, diagnostics);
+    }
+}
+
+fn validate_method(
+    method: &Method,
+    site: ModifierSite,
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let location = location.child(format!("method {}", method.name));
+    check_identifier(&method.name, Context::Method, &location, diagnostics);
+
+    let constructor = method.name.eq_ignore_ascii_case("__construct");
+    let site = match site {
+        ModifierSite::Method | ModifierSite::TraitMethod if constructor => {
+            ModifierSite::Constructor
+        }
+        site => site,
+    };
+    check_modifiers(
+        site,
+        method.visibility.as_ref(),
+        &method.modifiers,
+        &location,
+        diagnostics,
+    );
+
+    check_parameters(&method.parameters, constructor, &location, diagnostics);
+
+    if let Some(return_type) = &method.return_type {
+        check_type(return_type, &location, diagnostics);
+
+        if constructor {
+            diagnostics.push(Diagnostic::error(
+                "constructor-return-type",
+                &location,
+                "constructors cannot declare a return type",
+            ));
+        }
+    }
+
+    if is_abstract(method) && method.body.factory.is_some() {
+        diagnostics.push(Diagnostic::error(
+            "abstract-method-body",
+            &location,
+            format!("abstract method `{}` cannot have a body", method.name),
+        ));
+    }
+}
+
+fn validate_constant(
+    constant: &ClassConstant,
+    site: ModifierSite,
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let location = location.child(format!("constant {}", constant.name));
+    check_identifier(
+        &constant.name,
+        Context::ClassConstant,
+        &location,
+        diagnostics,
+    );
+    check_modifiers(
+        site,
+        constant.visibility.as_ref(),
+        &constant.modifiers,
+        &location,
+        diagnostics,
+    );
+
+    if let Some(data_type) = &constant.data_type {
+        check_type(data_type, &location, diagnostics);
+    }
+}
+
+fn check_modifiers(
+    site: ModifierSite,
+    visibility: Option<&VisibilityModifier>,
+    modifiers: &[Modifier],
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let Err(error) = modifiers::validate(site, visibility, modifiers) {
+        diagnostics.push(Diagnostic::error("invalid-modifiers", location, error));
+    }
+}
+
+/// Reports names declared more than once, reporting each at its second
+/// declaration. `segment` renders a name as a location segment.
+fn check_duplicates<'a>(
+    names: impl Iterator<Item = &'a str>,
+    case_sensitive: bool,
+    rule: &'static str,
+    segment: &dyn Fn(&str) -> String,
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut seen: Vec<&str> = vec![];
+
+    for name in names {
+        let previous = seen.iter().find(|previous| match case_sensitive {
+            true => **previous == name,
+            false => previous.eq_ignore_ascii_case(name),
+        });
+
+        match previous {
+            Some(previous) if *previous == name => diagnostics.push(Diagnostic::error(
+                rule,
+                &location.child(segment(name)),
+                format!("{} is declared more than once", segment(name)),
+            )),
+            Some(previous) => diagnostics.push(Diagnostic::error(
+                rule,
+                &location.child(segment(name)),
+                format!(
+                    "{} conflicts with {}, as names are case-insensitive",
+                    segment(name),
+                    segment(previous)
+                ),
+            )),
+            None => seen.push(name),
+        }
+    }
+}
+
+fn check_members(
+    methods: &[Method],
+    properties: &[Property],
+    constants: &[ClassConstant],
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    check_duplicates(
+        methods.iter().map(|method| method.name.as_str()),
+        false,
+        "duplicate-method",
+        &|name| format!("method {}", name),
+        location,
+        diagnostics,
+    );
+    check_duplicates(
+        properties.iter().map(|property| property.name.as_str()),
+        true,
+        "duplicate-property",
+        &|name| format!("property ${}", name),
+        location,
+        diagnostics,
+    );
+    check_duplicates(
+        constants.iter().map(|constant| constant.name.as_str()),
+        true,
+        "duplicate-constant",
+        &|name| format!("constant {}", name),
+        location,
+        diagnostics,
+    );
+}
+
+/// Reports trait methods that reach a class-like from more than one trait
+/// without being resolved by `insteadof`, by the class-like declaring the
+/// method itself, or by one of them being abstract. Only traits in `traits`
+/// are known; other traits are skipped.
+fn check_trait_conflicts(
+    usages: &[Usage],
+    methods: &[Method],
+    scope: &Scope,
+    traits: &[&Trait],
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let resolve = |name: &Name| {
+        let name = scope.resolve_class(name);
+
+        traits
+            .iter()
+            .find(|r#trait| {
+                scope
+                    .resolve_class(&Name::new(&r#trait.name))
+                    .matches(&name)
+            })
+            .copied()
+    };
+
+    let mut imported: Vec<(String, &str)> = vec![];
+    for usage in usages {
+        let adaptations = &usage.adaptations;
+
+        for r#trait in usage.traits.iter().filter_map(&resolve) {
+            for method in &r#trait.methods {
+                let excluded = adaptations.iter().any(|adaptation| match adaptation {
+                    UsageAdaptation::Precedence {
+                        method: target,
+                        insteadof,
+                    } => {
+                        split_method(target).1.eq_ignore_ascii_case(&method.name)
+                            && insteadof.iter().any(|name| {
+                                resolve(name).is_some_and(|other| std::ptr::eq(other, r#trait))
+                            })
+                    }
+                    _ => false,
+                });
+
+                if !excluded && !is_abstract(method) {
+                    imported.push((method.name.clone(), r#trait.name.as_str()));
+                }
+            }
+        }
+
+        for adaptation in adaptations {
+            if let UsageAdaptation::Alias { method, alias, .. } = adaptation {
+                let (target, method) = split_method(method);
+                let source = match target {
+                    Some(target) => resolve(&Name::new(target)),
+                    None => usage.traits.iter().filter_map(&resolve).find(|r#trait| {
+                        r#trait
+                            .methods
+                            .iter()
+                            .any(|candidate| candidate.name.eq_ignore_ascii_case(method))
+                    }),
+                };
+
+                if let Some(source) = source {
+                    imported.push((alias.clone(), source.name.as_str()));
+                }
+            }
+        }
+    }
+
+    let mut reported: Vec<String> = vec![];
+    for (index, (name, source)) in imported.iter().enumerate() {
+        let lowercase = name.to_ascii_lowercase();
+        if reported.contains(&lowercase)
+            || methods
+                .iter()
+                .any(|method| method.name.eq_ignore_ascii_case(name))
+        {
+            continue;
+        }
+
+        if let Some((_, other)) = imported[index + 1..]
+            .iter()
+            .find(|(other, _)| other.eq_ignore_ascii_case(name))
+        {
+            reported.push(lowercase);
+            diagnostics.push(Diagnostic::error(
+                "trait-method-conflict",
+                &location.child(format!("method {}", name)),
+                format!(
+                    "method `{}` is inherited from both `{}` and `{}`; resolve it with `insteadof` or declare it",
+                    name, source, other
+                ),
+            ));
+        }
+    }
+}
+
+/// Splits `Trait::method` into its parts; a bare method has no trait.
+fn split_method(method: &str) -> (Option<&str>, &str) {
+    match method.rsplit_once("::") {
+        Some((r#trait, method)) => (Some(r#trait), method),
+        None => (None, method),
+    }
+}
+
+fn is_abstract(method: &Method) -> bool {
+    method.modifiers.contains(&Modifier::Abstract)
+}
+
+fn check_method_body(method: &Method, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+    if !is_abstract(method) && method.body.factory.is_none() {
+        diagnostics.push(Diagnostic::error(
+            "method-without-body",
+            location,
+            format!("non-abstract method `{}` must have a body", method.name),
+        ));
+    }
+}
+
+fn check_identifier(
+    name: &str,
+    context: Context,
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match identifier::validate(name, context) {
+        Ok(()) => {}
+        Err(error @ IdentifierError::SoftReserved { .. }) => {
+            diagnostics.push(Diagnostic::warning("reserved-identifier", location, error))
+        }
+        Err(error @ IdentifierError::Reserved { .. }) => {
+            diagnostics.push(Diagnostic::error("reserved-identifier", location, error))
+        }
+        Err(error) => diagnostics.push(Diagnostic::error("invalid-identifier", location, error)),
+    }
+}
+
+fn check_parameters(
+    parameters: &[Parameter],
+    constructor: bool,
+    location: &Location,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (index, parameter) in parameters.iter().enumerate() {
+        parameter.validate_at(location, diagnostics);
+
+        let location = location.child(format!("param ${}", parameter.name));
+        if parameters[..index]
+            .iter()
+            .any(|previous| previous.name == parameter.name)
+        {
+            diagnostics.push(Diagnostic::error(
+                "duplicate-parameter",
+                &location,
+                format!("parameter `${}` is declared more than once", parameter.name),
+            ));
+        }
+
+        if parameter.variadic && index + 1 < parameters.len() {
+            diagnostics.push(Diagnostic::error(
+                "variadic-parameter-not-last",
+                &location,
+                "only the last parameter can be variadic",
+            ));
+        }
+
+        if parameter.default.is_none()
+            && !parameter.variadic
+            && parameters[..index]
+                .iter()
+                .any(|previous| previous.default.is_some())
+        {
+            diagnostics.push(Diagnostic::warning(
+                "required-parameter-after-optional",
+                &location,
+                format!(
+                    "required parameter `${}` follows an optional parameter",
+                    parameter.name
+                ),
+            ));
+        }
+
+        let promoted = parameter.visibility.is_some() || !parameter.modifiers.is_empty();
+        if promoted && !constructor {
+            diagnostics.push(Diagnostic::error(
+                "promoted-parameter-outside-constructor",
+                &location,
+                "only constructor parameters can be promoted to properties",
+            ));
+        }
+
+        if promoted && parameter.variadic {
+            diagnostics.push(Diagnostic::error(
+                "promoted-variadic-parameter",
+                &location,
+                "variadic parameters cannot be promoted to properties",
+            ));
+        }
+    }
+}
+
+fn check_type(data_type: &DataType, location: &Location, diagnostics: &mut Vec<Diagnostic>) {
+    let standalone = |data_type: &DataType| {
+        matches!(
+            data_type,
+            DataType::Void | DataType::Never | DataType::Mixed
+        )
+    };
+
+    let message = match data_type {
+        DataType::Nullable(inner) if standalone(inner) || matches!(**inner, DataType::Null) => {
+            Some(format!(
+                "`{}` cannot be nullable",
+                inner.generate(Indentation::default(), 0)
+            ))
+        }
+        DataType::Union(inner) | DataType::Intersection(inner) => {
+            inner.iter().find(|inner| standalone(inner)).map(|inner| {
+                format!(
+                    "`{}` cannot be combined with other types",
+                    inner.generate(Indentation::default(), 0)
+                )
+            })
+        }
+        _ => None,
+    };
+
+    if let Some(message) = message {
+        diagnostics.push(Diagnostic::error("standalone-type", location, message));
+    }
+}
+
+fn contains(data_type: &DataType, predicate: &dyn Fn(&DataType) -> bool) -> bool {
+    predicate(data_type)
+        || match data_type {
+            DataType::Nullable(inner) => contains(inner, predicate),
+            DataType::Union(inner) | DataType::Intersection(inner) => {
+                inner.iter().any(|data_type| contains(data_type, predicate))
+            }
+            _ => false,
+        }
+}
+
+fn returns_only(data_type: &DataType) -> bool {
+    contains(data_type, &|data_type| {
+        matches!(data_type, DataType::Void | DataType::Never)
+    })
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity, self.rule, self.location, self.message
+        )
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let diagnostics = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", diagnostics.join("\n"))
+    }
+}
+
+impl std::error::Error for ValidationError {}
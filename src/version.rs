@@ -0,0 +1,380 @@
+use std::fmt::Display;
+
+use crate::attribute::AttributeGroup;
+use crate::body::Body;
+use crate::class::Class;
+use crate::constant::ClassConstant;
+use crate::data_type::DataType;
+use crate::file::File;
+use crate::function::Function;
+use crate::interface::Interface;
+use crate::location::Location;
+use crate::method::Method;
+use crate::modifiers::Modifier;
+use crate::parameter::Parameter;
+use crate::property::Property;
+use crate::r#enum::Enum;
+use crate::r#trait::Trait;
+use crate::style::Style;
+use crate::Generator;
+use crate::Indentation;
+
+/// A PHP release, ordered by major and then minor version.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct PhpVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+/// A construct in a model that needs at least `version` to run.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Requirement {
+    pub version: PhpVersion,
+    pub feature: &'static str,
+    pub location: Location,
+}
+
+impl PhpVersion {
+    pub const PHP_7_0: PhpVersion = PhpVersion::new(7, 0);
+    pub const PHP_7_1: PhpVersion = PhpVersion::new(7, 1);
+    pub const PHP_7_2: PhpVersion = PhpVersion::new(7, 2);
+    pub const PHP_7_3: PhpVersion = PhpVersion::new(7, 3);
+    pub const PHP_7_4: PhpVersion = PhpVersion::new(7, 4);
+    pub const PHP_8_0: PhpVersion = PhpVersion::new(8, 0);
+    pub const PHP_8_1: PhpVersion = PhpVersion::new(8, 1);
+    pub const PHP_8_2: PhpVersion = PhpVersion::new(8, 2);
+    pub const PHP_8_3: PhpVersion = PhpVersion::new(8, 3);
+    pub const PHP_8_4: PhpVersion = PhpVersion::new(8, 4);
+
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Display for PhpVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PHP {} {}: {}",
+            self.version, self.location, self.feature
+        )
+    }
+}
+
+/// Returns every construct in `file` that needs a newer PHP version than
+/// 7.0, the oldest version the generated code can target.
+pub fn requirements(file: &File) -> Vec<Requirement> {
+    let mut detector = Detector {
+        requirements: vec![],
+        style: file.style,
+    };
+    detector.file(file, &Location::new().child("File"));
+
+    detector.requirements
+}
+
+/// Returns the oldest PHP version that can run the code generated for
+/// `file`.
+pub fn minimum_version(file: &File) -> PhpVersion {
+    requirements(file)
+        .iter()
+        .map(|requirement| requirement.version)
+        .max()
+        .unwrap_or(PhpVersion::PHP_7_0)
+}
+
+/// Returns the oldest PHP version that supports `data_type` as a declared
+/// type when printed in `style`. `standalone` is false for the members of a
+/// union or intersection.
+pub(crate) fn type_version(data_type: &DataType, standalone: bool, style: &Style) -> PhpVersion {
+    let mut detector = Detector {
+        requirements: vec![],
+        style: *style,
+    };
+    detector.data_type(data_type, &Location::new(), standalone);
+
+    detector
+        .requirements
+        .iter()
+        .map(|requirement| requirement.version)
+        .max()
+        .unwrap_or(PhpVersion::PHP_7_0)
+}
+
+struct Detector {
+    requirements: Vec<Requirement>,
+    style: Style,
+}
+
+impl Detector {
+    fn require(&mut self, version: PhpVersion, feature: &'static str, location: &Location) {
+        let requirement = Requirement {
+            version,
+            feature,
+            location: location.clone(),
+        };
+
+        if !self.requirements.contains(&requirement) {
+            self.requirements.push(requirement);
+        }
+    }
+
+    fn file(&mut self, file: &File, location: &Location) {
+        for function in &file.functions {
+            self.function(
+                function,
+                &location.child(format!("Function {}", function.name)),
+            );
+        }
+
+        for class in &file.classes {
+            self.class(class, &location.child(format!("Class {}", class.name)));
+        }
+
+        for r#trait in &file.traits {
+            self.r#trait(r#trait, &location.child(format!("Trait {}", r#trait.name)));
+        }
+
+        for r#enum in &file.enums {
+            self.r#enum(r#enum, &location.child(format!("Enum {}", r#enum.name)));
+        }
+
+        for interface in &file.interfaces {
+            self.interface(
+                interface,
+                &location.child(format!("Interface {}", interface.name)),
+            );
+        }
+    }
+
+    fn class(&mut self, class: &Class, location: &Location) {
+        self.attributes(&class.attributes, location);
+        if class.modifiers.contains(&Modifier::Readonly) {
+            self.require(PhpVersion::PHP_8_2, "readonly classes", location);
+        }
+
+        self.constants(&class.constants, location);
+        self.properties(&class.properties, location);
+        self.methods(&class.methods, location);
+    }
+
+    fn r#trait(&mut self, r#trait: &Trait, location: &Location) {
+        self.attributes(&r#trait.attributes, location);
+        for constant in &r#trait.constants {
+            self.require(
+                PhpVersion::PHP_8_2,
+                "constants in traits",
+                &location.child(format!("constant {}", constant.name)),
+            );
+        }
+
+        self.constants(&r#trait.constants, location);
+        self.properties(&r#trait.properties, location);
+        self.methods(&r#trait.methods, location);
+    }
+
+    fn r#enum(&mut self, r#enum: &Enum, location: &Location) {
+        self.require(PhpVersion::PHP_8_1, "enums", location);
+        self.attributes(&r#enum.attributes, location);
+        self.constants(&r#enum.constants, location);
+
+        for case in &r#enum.cases {
+            self.attributes(
+                &case.attributes,
+                &location.child(format!("case {}", case.name)),
+            );
+        }
+
+        self.methods(&r#enum.methods, location);
+    }
+
+    fn interface(&mut self, interface: &Interface, location: &Location) {
+        self.attributes(&interface.attributes, location);
+        self.methods(&interface.methods, location);
+    }
+
+    fn constants(&mut self, constants: &[ClassConstant], location: &Location) {
+        for constant in constants {
+            let location = location.child(format!("constant {}", constant.name));
+            self.attributes(&constant.attributes, &location);
+
+            if constant.visibility.is_some() {
+                self.require(PhpVersion::PHP_7_1, "class constant visibility", &location);
+            }
+
+            if constant.modifiers.contains(&Modifier::Final) {
+                self.require(PhpVersion::PHP_8_1, "final class constants", &location);
+            }
+
+            if let Some(data_type) = &constant.data_type {
+                self.require(PhpVersion::PHP_8_3, "typed class constants", &location);
+                self.data_type(data_type, &location, true);
+            }
+        }
+    }
+
+    fn properties(&mut self, properties: &[Property], location: &Location) {
+        for property in properties {
+            let location = location.child(format!("property ${}", property.name));
+            self.attributes(&property.attributes, &location);
+
+            if let Some(data_type) = &property.data_type {
+                self.require(PhpVersion::PHP_7_4, "typed properties", &location);
+                self.data_type(data_type, &location, true);
+            }
+
+            if property.modifiers.contains(&Modifier::Readonly) {
+                self.require(PhpVersion::PHP_8_1, "readonly properties", &location);
+            }
+
+            if property.modifiers.contains(&Modifier::Final) {
+                self.require(PhpVersion::PHP_8_4, "final properties", &location);
+            }
+
+            if property.modifiers.contains(&Modifier::Abstract) {
+                self.require(PhpVersion::PHP_8_4, "abstract properties", &location);
+            }
+
+            if !property.hooks.is_empty() {
+                self.require(PhpVersion::PHP_8_4, "property hooks", &location);
+            }
+        }
+    }
+
+    fn methods(&mut self, methods: &[Method], location: &Location) {
+        for method in methods {
+            let location = location.child(format!("method {}", method.name));
+            self.attributes(&method.attributes, &location);
+
+            let mut header = method.clone();
+            header.body = Body::new();
+            self.signature(
+                &method.parameters,
+                method.return_type.as_ref(),
+                &header.generate_styled(&self.style, Indentation::default(), 1),
+                &location,
+            );
+        }
+    }
+
+    fn function(&mut self, function: &Function, location: &Location) {
+        self.attributes(&function.attributes, location);
+
+        let mut header = function.clone();
+        header.body = Body::new();
+        self.signature(
+            &function.parameters,
+            function.return_type.as_ref(),
+            &header.generate_styled(&self.style, Indentation::default(), 0),
+            location,
+        );
+    }
+
+    fn signature(
+        &mut self,
+        parameters: &[Parameter],
+        return_type: Option<&DataType>,
+        header: &str,
+        location: &Location,
+    ) {
+        // Whether the list has a trailing comma depends on the style it is
+        // printed in, so look at the printed signature.
+        let lines = header.lines().collect::<Vec<&str>>();
+        if lines
+            .windows(2)
+            .any(|pair| pair[0].ends_with(',') && pair[1].trim_start().starts_with(')'))
+        {
+            self.require(
+                PhpVersion::PHP_8_0,
+                "trailing commas in parameter lists",
+                location,
+            );
+        }
+
+        for parameter in parameters {
+            let location = location.child(format!("param ${}", parameter.name));
+            self.attributes(&parameter.attributes, &location);
+
+            if parameter.visibility.is_some() || !parameter.modifiers.is_empty() {
+                self.require(
+                    PhpVersion::PHP_8_0,
+                    "constructor property promotion",
+                    &location,
+                );
+            }
+
+            if parameter.modifiers.contains(&Modifier::Readonly) {
+                self.require(PhpVersion::PHP_8_1, "readonly properties", &location);
+            }
+
+            if let Some(data_type) = &parameter.data_type {
+                self.data_type(data_type, &location, true);
+            }
+        }
+
+        if let Some(return_type) = return_type {
+            self.data_type(return_type, location, true);
+        }
+    }
+
+    fn attributes(&mut self, attributes: &[AttributeGroup], location: &Location) {
+        if !attributes.is_empty() {
+            self.require(PhpVersion::PHP_8_0, "attributes", location);
+        }
+    }
+
+    /// Records the requirements of a type. `standalone` is false for the
+    /// members of a union or intersection.
+    fn data_type(&mut self, data_type: &DataType, location: &Location, standalone: bool) {
+        match data_type {
+            DataType::Void => self.require(PhpVersion::PHP_7_1, "void type", location),
+            DataType::Iterable => self.require(PhpVersion::PHP_7_1, "iterable type", location),
+            DataType::Object => self.require(PhpVersion::PHP_7_2, "object type", location),
+            DataType::Mixed => self.require(PhpVersion::PHP_8_0, "mixed type", location),
+            DataType::StaticReference => {
+                self.require(PhpVersion::PHP_8_0, "static return type", location)
+            }
+            DataType::Never => self.require(PhpVersion::PHP_8_1, "never type", location),
+            DataType::True => self.require(PhpVersion::PHP_8_2, "true type", location),
+            DataType::Null | DataType::False if standalone => self.require(
+                PhpVersion::PHP_8_2,
+                "standalone null and false types",
+                location,
+            ),
+            DataType::Nullable(inner) if self.style.short_nullable && inner.nullable() => {
+                self.require(PhpVersion::PHP_7_1, "nullable types", location);
+                self.data_type(inner, location, true);
+            }
+            DataType::Nullable(inner) => {
+                self.require(PhpVersion::PHP_8_0, "union types", location);
+                self.data_type(inner, location, false);
+            }
+            DataType::Union(types) => {
+                self.require(PhpVersion::PHP_8_0, "union types", location);
+                if types
+                    .iter()
+                    .any(|data_type| matches!(data_type, DataType::Intersection(_)))
+                {
+                    self.require(PhpVersion::PHP_8_2, "DNF types", location);
+                }
+
+                for data_type in types {
+                    self.data_type(data_type, location, false);
+                }
+            }
+            DataType::Intersection(types) => {
+                self.require(PhpVersion::PHP_8_1, "intersection types", location);
+                for data_type in types {
+                    self.data_type(data_type, location, false);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::attribute::AttributeGroup;
+use crate::body::Body;
+use crate::class::Class;
+use crate::comment::Document;
+use crate::comment::Element;
+use crate::constant::ClassConstant;
+use crate::constant::Constant;
+use crate::data_type::DataType;
+use crate::enum_case::EnumCase;
+use crate::file::File;
+use crate::function::Function;
+use crate::interface::Interface;
+use crate::interpolation::Expression;
+use crate::interpolation::Segment;
+use crate::literal::Value;
+use crate::method::Method;
+use crate::name::Name;
+use crate::name::SymbolKind;
+use crate::parameter::Parameter;
+use crate::property::Property;
+use crate::property::PropertyHook;
+use crate::r#enum::Enum;
+use crate::r#trait::Trait;
+use crate::usage::Usage;
+use crate::usage::UsageAdaptation;
+
+/// Visits the symbol references and raw code held by a model.
+///
+/// `code` receives PHP source that the model stores as text: attribute
+/// arguments, `Value::Literal`s and the types in docblock tags. Bodies are
+/// visited separately since they are only known once generated.
+pub(crate) trait VisitorMut {
+    fn name(&mut self, _name: &mut Name, _kind: SymbolKind) {}
+
+    fn code(&mut self, _code: &mut String) {}
+
+    fn body(&mut self, _body: &mut Body) {}
+}
+
+pub(crate) trait Walk {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut);
+}
+
+impl<T: Walk> Walk for Vec<T> {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        for item in self {
+            item.walk(visitor);
+        }
+    }
+}
+
+impl<T: Walk> Walk for Option<T> {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        if let Some(item) = self {
+            item.walk(visitor);
+        }
+    }
+}
+
+impl Walk for Name {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.name(self, SymbolKind::Class);
+    }
+}
+
+impl Walk for File {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        for (_, value) in &mut self.declares {
+            value.walk(visitor);
+        }
+
+        self.constants.walk(visitor);
+        self.functions.walk(visitor);
+        self.classes.walk(visitor);
+        self.traits.walk(visitor);
+        self.enums.walk(visitor);
+        self.interfaces.walk(visitor);
+    }
+}
+
+impl Walk for Class {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.extends.walk(visitor);
+        self.implements.walk(visitor);
+        self.usages.walk(visitor);
+        self.constants.walk(visitor);
+        self.properties.walk(visitor);
+        self.methods.walk(visitor);
+    }
+}
+
+impl Walk for Interface {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.extends.walk(visitor);
+        self.methods.walk(visitor);
+    }
+}
+
+impl Walk for Trait {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.usages.walk(visitor);
+        self.constants.walk(visitor);
+        self.properties.walk(visitor);
+        self.methods.walk(visitor);
+    }
+}
+
+impl Walk for Enum {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.implements.walk(visitor);
+        self.usages.walk(visitor);
+        self.constants.walk(visitor);
+        self.cases.walk(visitor);
+        self.methods.walk(visitor);
+    }
+}
+
+impl Walk for Function {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.parameters.walk(visitor);
+        self.return_type.walk(visitor);
+        visitor.body(&mut self.body);
+    }
+}
+
+impl Walk for Method {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.parameters.walk(visitor);
+        self.return_type.walk(visitor);
+        visitor.body(&mut self.body);
+    }
+}
+
+impl Walk for Parameter {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.attributes.walk(visitor);
+        self.data_type.walk(visitor);
+        self.default.walk(visitor);
+    }
+}
+
+impl Walk for Property {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.data_type.walk(visitor);
+        self.default.walk(visitor);
+        self.hooks.walk(visitor);
+    }
+}
+
+impl Walk for PropertyHook {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        match self {
+            PropertyHook::Get(_, body) => visitor.body(body),
+            PropertyHook::Set(parameter, body) => {
+                if let Some(parameter) = parameter {
+                    parameter.attributes.walk(visitor);
+                    parameter.data_type.walk(visitor);
+                }
+
+                visitor.body(body);
+            }
+        }
+    }
+}
+
+impl Walk for Constant {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.value.walk(visitor);
+    }
+}
+
+impl Walk for ClassConstant {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.data_type.walk(visitor);
+        self.value.walk(visitor);
+    }
+}
+
+impl Walk for EnumCase {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.documentation.walk(visitor);
+        self.attributes.walk(visitor);
+        self.value.walk(visitor);
+    }
+}
+
+impl Walk for Usage {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        self.traits.walk(visitor);
+
+        for adaptation in &mut self.adaptations {
+            if let UsageAdaptation::Precedence { insteadof, .. } = adaptation {
+                insteadof.walk(visitor);
+            }
+        }
+    }
+}
+
+/// Tags are visited up to the end of their leading type, so that names in
+/// `@param`, `@return`, `@var` and `@throws` count, but prose does not.
+impl Walk for Document {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        for element in &mut self.elements {
+            if let Element::Tag(_, description) = element {
+                let mut depth = 0;
+                let end = description
+                    .char_indices()
+                    .find(|(_, character)| {
+                        match character {
+                            '<' | '{' | '(' | '[' => depth += 1,
+                            '>' | '}' | ')' | ']' => depth -= 1,
+                            _ => {}
+                        }
+
+                        depth <= 0 && character.is_whitespace()
+                    })
+                    .map_or(description.len(), |(position, _)| position);
+
+                let mut data_type = description[..end].to_string();
+                visitor.code(&mut data_type);
+                description.replace_range(..end, &data_type);
+            }
+        }
+    }
+}
+
+impl Walk for AttributeGroup {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        for (name, arguments) in &mut self.members {
+            visitor.name(name, SymbolKind::Class);
+
+            if let Some(arguments) = arguments {
+                visitor.code(arguments);
+            }
+        }
+    }
+}
+
+impl Walk for DataType {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        match self {
+            DataType::Named(name) => visitor.name(name, SymbolKind::Class),
+            DataType::Nullable(inner) => inner.walk(visitor),
+            DataType::Union(inner) | DataType::Intersection(inner) => inner.walk(visitor),
+            _ => {}
+        }
+    }
+}
+
+impl Walk for Value {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        match self {
+            Value::Literal(code) => visitor.code(code),
+            Value::Interpolated(string) => {
+                for segment in &mut string.segments {
+                    if let Segment::Expression(expression) = segment {
+                        expression.walk(visitor);
+                    }
+                }
+            }
+            Value::List(values) => values.walk(visitor),
+            Value::HashMap(entries) => {
+                for (key, value) in entries {
+                    key.walk(visitor);
+                    value.walk(visitor);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Walk for Expression {
+    fn walk(&mut self, visitor: &mut dyn VisitorMut) {
+        match self {
+            Expression::Variable(_) => {}
+            Expression::Property(target, _) => target.walk(visitor),
+            Expression::Offset(target, offset) => {
+                target.walk(visitor);
+                offset.walk(visitor);
+            }
+            Expression::MethodCall(target, _, arguments) => {
+                target.walk(visitor);
+                arguments.walk(visitor);
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::attribute::AttributeGroup;
+use php_codegen::body::Body;
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::function::Function;
+use php_codegen::method::Method;
+use php_codegen::parameter::Parameter;
+
+#[test]
+fn test_names_in_declarations_are_imported() {
+    let file = File::new()
+        .namespaced("App\\Controller")
+        .auto_import()
+        .class(
+            Class::new("UserController")
+                .extends("\\Framework\\Http\\Controller")
+                .attributes(
+                    AttributeGroup::new().add("\\Framework\\Routing\\Route", Some("'/users'")),
+                )
+                .method(
+                    Method::new("show")
+                        .public()
+                        .parameter(
+                            Parameter::new("user")
+                                .typed(DataType::Named("\\App\\Entity\\User".into())),
+                        )
+                        .returns(DataType::Named("\\Framework\\Http\\Response".into()))
+                        .body("return new \\Framework\\Http\\Response($user);"),
+                ),
+        );
+
+    assert_eq!(
+        r#"<?php
+
+namespace App\Controller;
+
+use Framework\Routing\Route;
+use Framework\Http\Controller;
+use App\Entity\User;
+use Framework\Http\Response;
+
+
+#[Route('/users')]
+class UserController extends Controller
+{
+    public function show(
+        User $user,
+    ): Response {
+        return new Response($user);
+    }
+}
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_functions_constants_and_same_namespace_names() {
+    let file = File::new()
+        .namespaced("App")
+        .auto_import()
+        .class(Class::new("Kernel").method(Method::new("boot").public().body(
+            Body::from(
+                "$level = \\Monolog\\Level::fromName(\\Monolog\\DEFAULT_LEVEL);\n\\Vendor\\Log\\configure(new \\App\\Config(), new \\DateTime());\n// \\Not\\Imported\necho '\\Also\\Not\\Imported';",
+            ),
+        )));
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use Monolog\Level;
+
+use function Vendor\Log\configure;
+
+use const Monolog\DEFAULT_LEVEL;
+
+
+class Kernel
+{
+    public function boot() {
+        $level = Level::fromName(DEFAULT_LEVEL);
+        configure(new Config(), new \DateTime());
+        // \Not\Imported
+        echo '\Also\Not\Imported';
+    }
+}
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_conflicting_names_stay_fully_qualified() {
+    let file = File::new()
+        .namespaced("App")
+        .uses("Symfony\\Component\\HttpFoundation\\Request")
+        .auto_import()
+        .class(
+            Class::new("Client")
+                .implements("\\Psr\\Http\\Client\\ClientInterface")
+                .method(
+                    Method::new("send")
+                        .public()
+                        .parameter(
+                            Parameter::new("request")
+                                .typed(DataType::Named("\\Psr\\Http\\Message\\Request".into())),
+                        )
+                        .parameter(Parameter::new("fallback").typed(DataType::Named(
+                            "\\Symfony\\Component\\HttpFoundation\\Request".into(),
+                        )))
+                        .parameter(
+                            Parameter::new("client")
+                                .typed(DataType::Named("\\Vendor\\Client".into())),
+                        ),
+                ),
+        );
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use Symfony\Component\HttpFoundation\Request;
+use Psr\Http\Client\ClientInterface;
+
+
+class Client implements ClientInterface
+{
+    public function send(
+        \Psr\Http\Message\Request $request,
+        Request $fallback,
+        \Vendor\Client $client,
+    );
+}
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_return_and_caught_types_are_classes() {
+    let file = File::new()
+        .namespaced("App")
+        .auto_import()
+        .function(Function::new("main").body(Body::from(
+            "$make = function (): \\App\\DTO\\DTO {\n    return new DTO();\n};\n$find = fn(): ?\\App\\Model\\URL => null;\ntry {\n    $make();\n} catch (\\App\\Error\\IO) {\n    return $flag ? max() : \\App\\Config\\LIMIT;\n}",
+        )));
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use App\DTO\DTO;
+use App\Model\URL;
+use App\Error\IO;
+
+use const App\Config\LIMIT;
+
+
+function main() {
+    $make = function (): DTO {
+        return new DTO();
+    };
+    $find = fn(): ?URL => null;
+    try {
+        $make();
+    } catch (IO) {
+        return $flag ? max() : LIMIT;
+    }
+}
+"#,
+        file.to_string()
+    );
+}
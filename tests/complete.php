@@ -125,12 +125,12 @@ abstract class Example extends Foo\Bar\Baz implements Foo\Bar\BazInterface
     /**
      * This is a simple poop function.
      */
-    public abstract function poop(): void;
+    abstract public function poop(): void;
 
     /**
      * This is a simple echo function.
      */
-    public final function helloWorld(): void {
+    final public function helloWorld(): void {
         echo 'Hello World!';
     }
 }
@@ -214,12 +214,12 @@ trait ExampleTrait
     /**
      * This is a simple poop function.
      */
-    public abstract function poop(): void;
+    abstract public function poop(): void;
 
     /**
      * This is a simple echo function.
      */
-    public final function helloWorld(): void {
+    final public function helloWorld(): void {
         echo 'Hello World!';
     }
 }
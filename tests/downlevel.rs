@@ -0,0 +1,396 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::constant::ClassConstant;
+use php_codegen::data_type::DataType;
+use php_codegen::downlevel;
+use php_codegen::enum_case::EnumCase;
+use php_codegen::file::File;
+use php_codegen::method::Method;
+use php_codegen::modifiers::Modifier;
+use php_codegen::parameter::Parameter;
+use php_codegen::property::Property;
+use php_codegen::property::PropertyHook;
+use php_codegen::r#enum::Enum;
+use php_codegen::r#trait::Trait;
+use php_codegen::validation::Validator;
+use php_codegen::version;
+use php_codegen::version::PhpVersion;
+use php_codegen::Indentation;
+
+#[test]
+fn test_enums_become_final_classes() {
+    let file = File::new()
+        .r#enum(
+            Enum::new("Suit")
+                .string_backed()
+                .case(EnumCase::new("Hearts").valued("H"))
+                .case(EnumCase::new("Spades").valued("S"))
+                .method(
+                    Method::new("color")
+                        .public()
+                        .returns(DataType::String)
+                        .body("return 'Red';"),
+                ),
+        )
+        .target(PhpVersion::PHP_8_0);
+
+    assert_eq!(
+        r#"<?php
+
+final class Suit
+{
+    public const Hearts = "H";
+
+    public const Spades = "S";
+
+    /**
+     * @readonly
+     */
+    public string $name;
+    /**
+     * @readonly
+     */
+    public string $value;
+    /**
+     * @var array<string, self>
+     */
+    private static array $instances = [];
+    private function __construct(
+        string $name,
+        string $value,
+    ) {
+        $this->name = $name;
+        $this->value = $value;
+    }
+
+    public static function Hearts(): self {
+        return self::$instances['Hearts'] ?? (self::$instances['Hearts'] = new self('Hearts', self::Hearts));
+    }
+
+    public static function Spades(): self {
+        return self::$instances['Spades'] ?? (self::$instances['Spades'] = new self('Spades', self::Spades));
+    }
+
+    /**
+     * @return list<self>
+     */
+    public static function cases(): array {
+        return [self::Hearts(), self::Spades()];
+    }
+
+    public static function from(
+        string $value,
+    ): self {
+        $case = self::tryFrom($value);
+        if ($case === null) {
+            throw new \ValueError(sprintf('%s is not a valid backing value for enum %s', var_export($value, true), self::class));
+        }
+
+        return $case;
+    }
+
+    public static function tryFrom(
+        string $value,
+    ): null|self {
+        foreach (self::cases() as $case) {
+            if ($case->value === $value) {
+                return $case;
+            }
+        }
+
+        return null;
+    }
+
+    public function color(): string {
+        return 'Red';
+    }
+}
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_readonly_and_types_move_to_docblocks() {
+    let file = File::new()
+        .class(
+            Class::new("Money")
+                .modifier(Modifier::Readonly)
+                .constant(
+                    ClassConstant::new("CURRENCY")
+                        .typed(DataType::String)
+                        .modifier(Modifier::Final)
+                        .valued("EUR"),
+                )
+                .property(
+                    Property::new("cents")
+                        .public()
+                        .typed(DataType::Union(vec![DataType::Integer, DataType::Float])),
+                )
+                .method(
+                    Method::new("__construct")
+                        .parameter(Parameter::new("amount").public().typed(DataType::Integer))
+                        .body("assert($amount >= 0);"),
+                )
+                .method(
+                    Method::new("with")
+                        .public()
+                        .parameter(Parameter::new("value").typed(DataType::Mixed))
+                        .returns(DataType::StaticReference)
+                        .body("return clone $this;"),
+                )
+                .method(
+                    Method::new("fail")
+                        .public()
+                        .returns(DataType::Never)
+                        .body("throw new \\LogicException();"),
+                ),
+        )
+        .target(PhpVersion::PHP_7_4);
+
+    assert_eq!(
+        r#"<?php
+
+class Money
+{
+    /**
+     * @final
+     * @var string
+     */
+    const CURRENCY = "EUR";
+
+    /**
+     * @readonly
+     * @var int|float
+     */
+    private $cents;
+    /**
+     * @readonly
+     */
+    private int $amount;
+    function __construct(
+        int $amount
+    ) {
+        $this->amount = $amount;
+
+        assert($amount >= 0);
+    }
+
+    /**
+     * @param mixed $value
+     * @return static
+     */
+    public function with(
+        $value
+    ): self {
+        return clone $this;
+    }
+
+    /**
+     * @return never
+     */
+    public function fail() {
+        throw new \LogicException();
+    }
+}
+"#,
+        file.to_string()
+    );
+
+    let mut lowered = file.clone();
+    lowered.target = None;
+    assert!(downlevel::lower(&mut lowered, PhpVersion::PHP_7_4).is_empty());
+    assert_eq!(PhpVersion::PHP_7_4, version::minimum_version(&lowered));
+}
+
+#[test]
+fn test_constructs_without_equivalent_are_errors() {
+    let file = File::new()
+        .r#trait(Trait::new("HasVersion").constant(("VERSION", 2)))
+        .class(
+            Class::new("User").property(
+                Property::new("email")
+                    .public()
+                    .typed(DataType::String)
+                    .hook(PropertyHook::Get(
+                        false,
+                        "return 'user@example.com';".into(),
+                    )),
+            ),
+        )
+        .target(PhpVersion::PHP_8_0);
+
+    assert_eq!(
+        vec![
+            "error[unsupported-feature] File > Class User > property $email: property hooks require PHP 8.4 and cannot be lowered to PHP 8.0".to_string(),
+            "error[unsupported-feature] File > Trait HasVersion > constant VERSION: constants in traits require PHP 8.2 and cannot be lowered to PHP 8.0".to_string(),
+        ],
+        file.validate()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<String>>()
+    );
+
+    assert!(file.try_generate(Indentation::default(), 0).is_err());
+    assert!(file
+        .clone()
+        .target(PhpVersion::PHP_8_4)
+        .try_generate(Indentation::default(), 0)
+        .is_ok());
+}
+
+#[test]
+fn test_final_and_abstract_properties() {
+    let file = File::new()
+        .class(
+            Class::new("User").property(
+                Property::new("name")
+                    .public()
+                    .modifier(Modifier::Final)
+                    .typed(DataType::String),
+            ),
+        )
+        .class(
+            Class::new("Model").modifier(Modifier::Abstract).property(
+                Property::new("id")
+                    .public()
+                    .modifier(Modifier::Abstract)
+                    .typed(DataType::Integer)
+                    .hook(PropertyHook::Get(false, "".into())),
+            ),
+        )
+        .target(PhpVersion::PHP_7_4);
+
+    assert_eq!(
+        vec![
+            "error[unsupported-feature] File > Class Model > property $id: property hooks require PHP 8.4 and cannot be lowered to PHP 7.4".to_string(),
+            "error[unsupported-feature] File > Class Model > property $id: abstract properties require PHP 8.4 and cannot be lowered to PHP 7.4".to_string(),
+        ],
+        file.validate()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<String>>()
+    );
+    assert!(file
+        .to_string()
+        .contains("    /**\n     * @final\n     */\n    public string $name;"));
+}
+
+#[test]
+fn test_nullable_types_before_php_8() {
+    let file = File::new()
+        .class(
+            Class::new("Cache")
+                .property(
+                    Property::new("ttl")
+                        .public()
+                        .typed(DataType::Nullable(Box::new(DataType::Integer))),
+                )
+                .method(
+                    Method::new("get")
+                        .public()
+                        .parameter(
+                            Parameter::new("key")
+                                .typed(DataType::Union(vec![DataType::String, DataType::Null])),
+                        )
+                        .returns(DataType::Nullable(Box::new(DataType::String)))
+                        .body("return null;"),
+                ),
+        )
+        .target(PhpVersion::PHP_7_4);
+
+    assert_eq!(
+        r#"<?php
+
+class Cache
+{
+    public ?int $ttl;
+    public function get(
+        ?string $key
+    ): ?string {
+        return null;
+    }
+}
+"#,
+        file.to_string()
+    );
+
+    let mut lowered = file.clone();
+    lowered.target = None;
+    downlevel::lower(&mut lowered, PhpVersion::PHP_7_4);
+    assert_eq!(PhpVersion::PHP_7_4, version::minimum_version(&lowered));
+
+    assert_eq!(
+        r#"<?php
+
+class Cache
+{
+    /**
+     * @var null|int
+     */
+    public $ttl;
+    /**
+     * @param null|string $key
+     * @return null|string
+     */
+    public function get(
+        $key
+    ) {
+        return null;
+    }
+}
+"#,
+        file.target(PhpVersion::PHP_7_0).to_string()
+    );
+}
+
+#[test]
+fn test_unsupported_constructs_are_reported_not_generated() {
+    let file = File::new()
+        .class(
+            Class::new("User").property(
+                Property::new("email")
+                    .public()
+                    .hook(PropertyHook::Get(false, "return '';".into())),
+            ),
+        )
+        .target(PhpVersion::PHP_8_0);
+
+    assert!(file
+        .to_string()
+        .contains("    public $email {\n        get {"));
+    assert!(file
+        .try_generate(Indentation::default(), 0)
+        .unwrap_err()
+        .to_string()
+        .contains("property hooks require PHP 8.4"));
+}
+
+#[test]
+fn test_case_references_in_enum_methods_are_warnings() {
+    let file = File::new()
+        .r#enum(
+            Enum::new("Suit")
+                .case(EnumCase::new("Hearts"))
+                .case(EnumCase::new("Spades"))
+                .method(
+                    Method::new("isRed")
+                        .public()
+                        .returns(DataType::Boolean)
+                        .body("return $this === self::Hearts || $this === Suit::Hearts();"),
+                ),
+        )
+        .target(PhpVersion::PHP_8_0);
+
+    assert_eq!(
+        vec![
+            "warning[unsupported-feature] File > Enum Suit > method isRed: `self::Hearts` refers to the value of case Hearts once lowered to PHP 8.0, use `self::Hearts()` for the case".to_string(),
+        ],
+        file.validate()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<String>>()
+    );
+    assert!(file.try_generate(Indentation::default(), 0).is_ok());
+}
@@ -0,0 +1,101 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::file::File;
+use php_codegen::interface::Interface;
+use php_codegen::method::Method;
+use php_codegen::modifiers::Modifier;
+use php_codegen::property::Property;
+use php_codegen::r#enum::Enum;
+use php_codegen::r#trait::Trait;
+use php_codegen::usage::Usage;
+use php_codegen::validation::Validator;
+
+fn summary<T: Validator>(node: &T) -> Vec<String> {
+    node.validate()
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect()
+}
+
+#[test]
+fn test_duplicate_members_follow_php_case_rules() {
+    let class = Class::new("User")
+        .constant(("STATUS", 1))
+        .constant(("status", 2))
+        .constant(("STATUS", 3))
+        .property(Property::new("id"))
+        .property(Property::new("ID"))
+        .property(Property::new("id"))
+        .method(Method::new("getId").body("return 1;"))
+        .method(Method::new("getID").body("return 2;"));
+
+    assert_eq!(
+        vec![
+            "error[duplicate-method] Class User > method getID: method getID conflicts with method getId, as names are case-insensitive".to_string(),
+            "error[duplicate-property] Class User > property $id: property $id is declared more than once".to_string(),
+            "error[duplicate-constant] Class User > constant STATUS: constant STATUS is declared more than once".to_string(),
+        ],
+        summary(&class)
+    );
+}
+
+#[test]
+fn test_duplicate_enum_cases_and_interface_methods() {
+    let r#enum = Enum::new("Suit")
+        .case("Hearts")
+        .case("hearts")
+        .case("Hearts");
+
+    assert_eq!(
+        vec!["error[duplicate-enum-case] Enum Suit > case Hearts: case Hearts is declared more than once".to_string()],
+        summary(&r#enum)
+    );
+
+    let interface = Interface::new("Countable")
+        .method(Method::new("count"))
+        .method(Method::new("Count"));
+
+    assert_eq!(
+        vec!["error[duplicate-method] Interface Countable > method Count: method Count conflicts with method count, as names are case-insensitive".to_string()],
+        summary(&interface)
+    );
+}
+
+#[test]
+fn test_trait_method_conflicts() {
+    let file = File::new()
+        .namespaced("App")
+        .r#trait(Trait::new("Hello").method(Method::new("say").body("echo 'hello';")))
+        .r#trait(Trait::new("World").method(Method::new("Say").body("echo 'world';")))
+        .r#trait(
+            Trait::new("Greeting")
+                .method(Method::new("say").modifier(Modifier::Abstract))
+                .method(Method::new("greet").body("echo 'hi';")),
+        )
+        .class(Class::new("Conflicting").using(vec!["Hello", "World"]))
+        .class(
+            Class::new("Resolved").using(
+                Usage::new(vec!["Hello", "World"])
+                    .precede("Hello::say", vec!["World"])
+                    .rename("World::say", "sayWorld"),
+            ),
+        )
+        .class(
+            Class::new("Aliased")
+                .using(Usage::new(vec!["Hello", "Greeting"]).rename("Hello::say", "greet")),
+        )
+        .class(
+            Class::new("Overridden")
+                .using(vec!["Hello", "World", "Greeting"])
+                .method(Method::new("say").body("echo 'mine';")),
+        );
+
+    assert_eq!(
+        vec![
+            "error[trait-method-conflict] File > Class Conflicting > method say: method `say` is inherited from both `Hello` and `World`; resolve it with `insteadof` or declare it".to_string(),
+            "error[trait-method-conflict] File > Class Aliased > method greet: method `greet` is inherited from both `Greeting` and `Hello`; resolve it with `insteadof` or declare it".to_string(),
+        ],
+        summary(&file)
+    );
+}
@@ -0,0 +1,117 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::constant::ClassConstant;
+use php_codegen::enum_case::EnumCase;
+use php_codegen::literal::Value;
+use php_codegen::r#enum::Enum;
+use php_codegen::r#enum::EnumBackingType;
+use php_codegen::r#enum::EnumError;
+use php_codegen::validation::Validator;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+fn summary(r#enum: &Enum) -> Vec<String> {
+    r#enum
+        .validate()
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect()
+}
+
+#[test]
+fn test_backed_enum_values_are_checked() {
+    let r#enum = Enum::new("Status")
+        .string_backed()
+        .constant(ClassConstant::new("Archived").valued("archived"))
+        .case(EnumCase::new("Active").valued("active"))
+        .case(EnumCase::new("Pending").valued(1))
+        .case(EnumCase::new("Enabled").valued("active"))
+        .case(EnumCase::new("Archived").valued("archived"))
+        .case("Deleted");
+
+    assert_eq!(
+        vec![
+            "error[enum-case-value-type] Enum Status > case Pending: case `Pending` of enum `Status` must have a value of type `string`, found `1`".to_string(),
+            "error[duplicate-enum-value] Enum Status > case Enabled: case `Enabled` has the same value as case `Active`".to_string(),
+            "error[enum-case-constant-clash] Enum Status > case Archived: case `Archived` clashes with a constant of the same name in enum `Status`".to_string(),
+            "error[enum-case-missing-value] Enum Status > case Deleted: case `Deleted` of backed enum `Status` must have a value".to_string(),
+        ],
+        summary(&r#enum)
+    );
+
+    let pure = Enum::new("Suit")
+        .case("Hearts")
+        .case(EnumCase::new("Spades").valued("S"));
+
+    assert_eq!(
+        vec!["error[enum-case-unexpected-value] Enum Suit > case Spades: case `Spades` of pure enum `Suit` cannot have a value".to_string()],
+        summary(&pure)
+    );
+}
+
+#[test]
+fn test_backed_from_infers_the_backing_type() {
+    let r#enum =
+        Enum::backed_from("Priority", vec![("Low", 1), ("Normal", 5), ("High", 10)]).unwrap();
+
+    assert_eq!(Some(EnumBackingType::Int), r#enum.backing_type);
+    assert!(r#enum.validate().is_empty());
+    assert_eq!(
+        r#"enum Priority: int
+{
+    case Low = 1;
+
+    case Normal = 5;
+
+    case High = 10;
+}
+"#,
+        r#enum.generate(Indentation::default(), 0)
+    );
+
+    let r#enum = Enum::backed_from("Currency", vec![("Euro", "EUR"), ("Dollar", "USD")]).unwrap();
+    assert_eq!(Some(EnumBackingType::String), r#enum.backing_type);
+}
+
+#[test]
+fn test_backed_from_rejects_invalid_cases() {
+    assert_eq!(
+        EnumError::Empty {
+            name: "Nothing".to_string()
+        },
+        Enum::backed_from("Nothing", Vec::<(&str, i64)>::new()).unwrap_err()
+    );
+
+    assert_eq!(
+        "case `Half` must have an int or string value",
+        Enum::backed_from("Ratio", vec![("Half", 0.5)])
+            .unwrap_err()
+            .to_string()
+    );
+
+    assert_eq!(
+        EnumError::MismatchedValue {
+            case: "Two".to_string(),
+            backing_type: EnumBackingType::Int,
+        },
+        Enum::backed_from(
+            "Numbers",
+            vec![("One", Value::Integer(1)), ("Two", Value::from("2"))]
+        )
+        .unwrap_err()
+    );
+
+    assert_eq!(
+        "case `Uno` has the same value as case `One`",
+        Enum::backed_from("Numbers", vec![("One", 1), ("Uno", 1)])
+            .unwrap_err()
+            .to_string()
+    );
+
+    assert_eq!(
+        EnumError::DuplicateCase {
+            case: "One".to_string()
+        },
+        Enum::backed_from("Numbers", vec![("One", 1), ("One", 2)]).unwrap_err()
+    );
+}
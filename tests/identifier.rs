@@ -0,0 +1,81 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::identifier;
+use php_codegen::identifier::Context;
+use php_codegen::identifier::IdentifierError;
+
+#[test]
+fn test_reserved_words_depend_on_context() {
+    assert!(identifier::is_valid("list", Context::Method));
+    assert!(identifier::is_valid("class", Context::Property));
+    assert!(identifier::is_valid("Enum", Context::Class));
+    assert!(identifier::is_valid("int", Context::Function));
+
+    assert!(!identifier::is_valid("List", Context::Class));
+    assert!(!identifier::is_valid("list", Context::Function));
+    assert!(!identifier::is_valid("string", Context::Class));
+    assert!(!identifier::is_valid("NULL", Context::Constant));
+    assert!(!identifier::is_valid("class", Context::ClassConstant));
+    assert!(!identifier::is_valid("this", Context::Variable));
+
+    assert_eq!(
+        Err(IdentifierError::Reserved {
+            name: "List".to_string(),
+            context: Context::Class,
+        }),
+        identifier::validate("List", Context::Class)
+    );
+    assert_eq!(
+        "`List` is reserved and cannot be used as a class name",
+        identifier::validate("List", Context::Class)
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        Err(IdentifierError::SoftReserved {
+            name: "Resource".to_string(),
+            context: Context::Class,
+        }),
+        identifier::validate("Resource", Context::Class)
+    );
+}
+
+#[test]
+fn test_grammar() {
+    assert!(identifier::is_valid("_private", Context::Property));
+    assert!(identifier::is_valid("größe", Context::Property));
+
+    assert_eq!(
+        Err(IdentifierError::Empty),
+        identifier::validate("", Context::Property)
+    );
+    assert_eq!(
+        Err(IdentifierError::Invalid {
+            name: "123abc".to_string()
+        }),
+        identifier::validate("123abc", Context::Property)
+    );
+    assert!(!identifier::is_valid("my-field", Context::Property));
+    assert!(!identifier::is_valid("App\\User", Context::Class));
+}
+
+#[test]
+fn test_sanitize() {
+    assert_eq!(
+        "my_field",
+        identifier::sanitize("my-field", Context::Property)
+    );
+    assert_eq!(
+        "first_name",
+        identifier::sanitize("first -- name", Context::Property)
+    );
+    assert_eq!("_123abc", identifier::sanitize("123abc", Context::Class));
+    assert_eq!("_", identifier::sanitize("", Context::Method));
+    assert_eq!("List_", identifier::sanitize("List", Context::Class));
+    assert_eq!("list", identifier::sanitize("list", Context::Method));
+    assert_eq!("this_", identifier::sanitize("this", Context::Variable));
+    assert_eq!(
+        "UserProfile",
+        identifier::sanitize("UserProfile", Context::Class)
+    );
+}
@@ -0,0 +1,119 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::function::Function;
+use php_codegen::method::Method;
+use php_codegen::parameter::Parameter;
+
+#[test]
+fn test_explicit_aliases() {
+    let file = File::new()
+        .namespaced("App")
+        .uses("App\\Entity\\User")
+        .uses(("Vendor\\Sdk\\User", "SdkUser"))
+        .uses("Vendor\\Sdk\\Client AS Sdk")
+        .uses_function(("Vendor\\Sdk\\helper", "sdk_helper"))
+        .uses_constant(("Vendor\\Sdk\\VERSION", "SDK_VERSION"));
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use App\Entity\User;
+use Vendor\Sdk\User as SdkUser;
+use Vendor\Sdk\Client as Sdk;
+
+use function Vendor\Sdk\helper as sdk_helper;
+
+use const Vendor\Sdk\VERSION as SDK_VERSION;
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_clashing_imports_are_aliased() {
+    let file = File::new()
+        .namespaced("App\\Sync")
+        .uses("App\\Entity\\User")
+        .uses("Vendor\\Sdk\\User")
+        .uses("Vendor\\Sdk\\Mapper")
+        .uses_function("Vendor\\Sdk\\sync")
+        .alias_conflicts()
+        .function(Function::new("sync"))
+        .class(Class::new("Mapper"));
+
+    assert_eq!(
+        r#"<?php
+
+namespace App\Sync;
+
+use App\Entity\User;
+use Vendor\Sdk\User as SdkUser;
+use Vendor\Sdk\Mapper as SdkMapper;
+
+use function Vendor\Sdk\sync as sdk_sync;
+
+
+function sync() {}
+
+class Mapper
+{
+}
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_automatic_imports_are_aliased() {
+    let file = File::new()
+        .namespaced("App")
+        .auto_import()
+        .alias_conflicts()
+        .class(
+            Class::new("Synchronizer").method(
+                Method::new("sync")
+                    .public()
+                    .parameter(
+                        Parameter::new("local")
+                            .typed(DataType::Named("\\App\\Entity\\User".into())),
+                    )
+                    .parameter(
+                        Parameter::new("remote")
+                            .typed(DataType::Named("\\Vendor\\Sdk\\User".into())),
+                    )
+                    .parameter(
+                        Parameter::new("legacy")
+                            .typed(DataType::Named("\\Legacy\\Sdk\\User".into())),
+                    )
+                    .returns(DataType::Named("\\Vendor\\Synchronizer".into())),
+            ),
+        );
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use App\Entity\User;
+use Vendor\Sdk\User as SdkUser;
+use Legacy\Sdk\User as LegacySdkUser;
+use Vendor\Synchronizer as VendorSynchronizer;
+
+
+class Synchronizer
+{
+    public function sync(
+        User $local,
+        SdkUser $remote,
+        LegacySdkUser $legacy,
+    ): VendorSynchronizer;
+}
+"#,
+        file.to_string()
+    );
+}
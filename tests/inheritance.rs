@@ -0,0 +1,252 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::inheritance::Hierarchy;
+use php_codegen::interface::Interface;
+use php_codegen::method::Method;
+use php_codegen::modifiers::Modifier;
+use php_codegen::name::Name;
+use php_codegen::parameter::Parameter;
+use php_codegen::r#trait::Trait;
+
+fn named(name: &str) -> DataType {
+    DataType::Named(Name::new(name))
+}
+
+fn summary(files: &[File]) -> Vec<String> {
+    Hierarchy::from_files(files)
+        .check()
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect()
+}
+
+#[test]
+fn test_subtypes() {
+    let files = vec![File::new()
+        .interface(Interface::new("Shape"))
+        .class(Class::new("Square").implements("Shape"))
+        .class(Class::new("Collection").implements("\\IteratorAggregate"))];
+    let hierarchy = Hierarchy::from_files(&files);
+    let extends = |child: &Name, parent: &Name| hierarchy.is_subclass(child, parent);
+    let is_subtype = |child: &DataType, parent: &DataType| child.is_subtype_of(parent, &extends);
+
+    assert!(is_subtype(&named("\\Square"), &named("\\Shape")));
+    assert!(!is_subtype(&named("\\Shape"), &named("\\Square")));
+    assert!(is_subtype(&named("\\Collection"), &DataType::Iterable));
+    assert!(is_subtype(&named("\\Traversable"), &DataType::Iterable));
+    assert!(is_subtype(&named("\\Generator"), &DataType::Iterable));
+    assert!(!is_subtype(&named("\\Countable"), &DataType::Iterable));
+    assert!(is_subtype(&DataType::Never, &DataType::Void));
+    assert!(is_subtype(&DataType::Integer, &DataType::Mixed));
+    assert!(!is_subtype(&DataType::Void, &DataType::Mixed));
+    assert!(!is_subtype(&DataType::Mixed, &DataType::String));
+    assert!(is_subtype(&DataType::True, &DataType::Boolean));
+    assert!(is_subtype(
+        &DataType::Union(vec![DataType::Integer, DataType::Null]),
+        &DataType::Nullable(Box::new(DataType::Union(vec![
+            DataType::Integer,
+            DataType::String
+        ])))
+    ));
+    assert!(!is_subtype(
+        &DataType::Nullable(Box::new(DataType::Integer)),
+        &DataType::Integer
+    ));
+    assert!(is_subtype(
+        &DataType::Intersection(vec![named("\\Square"), named("\\Countable")]),
+        &DataType::Union(vec![named("\\Shape"), DataType::Null])
+    ));
+    assert!(!is_subtype(
+        &named("\\Square"),
+        &DataType::Intersection(vec![named("\\Shape"), named("\\Countable")])
+    ));
+    assert!(is_subtype(
+        &DataType::StaticReference,
+        &DataType::SelfReference
+    ));
+    assert!(!is_subtype(
+        &DataType::SelfReference,
+        &DataType::StaticReference
+    ));
+    assert!(is_subtype(&DataType::StaticReference, &DataType::Object));
+}
+
+#[test]
+fn test_unimplemented_and_final_methods() {
+    let files = vec![
+        File::new().namespaced("App\\Contract").interface(
+            Interface::new("Repository")
+                .method(Method::new("find").parameter(Parameter::new("id")))
+                .method(Method::new("save")),
+        ),
+        File::new()
+            .namespaced("App")
+            .uses("App\\Contract\\Repository")
+            .r#trait(Trait::new("Saves").method(Method::new("save").public().body("")))
+            .class(
+                Class::new("Base")
+                    .modifier(Modifier::Abstract)
+                    .implements("Repository")
+                    .method(
+                        Method::new("count")
+                            .modifier(Modifier::Abstract)
+                            .public()
+                            .returns(DataType::Integer),
+                    )
+                    .method(Method::new("flush").modifier(Modifier::Final).body("")),
+            )
+            .class(
+                Class::new("UserRepository")
+                    .extends("Base")
+                    .using("Saves")
+                    .method(Method::new("flush").body("")),
+            ),
+    ];
+
+    assert_eq!(
+        vec![
+            "error[unimplemented-method] Class App\\UserRepository: class `UserRepository` must implement `Base::count()` or be declared abstract".to_string(),
+            "error[unimplemented-method] Class App\\UserRepository: class `UserRepository` must implement `Repository::find()` or be declared abstract".to_string(),
+            "error[final-method-override] Class App\\UserRepository > method flush: method `flush` overrides final method `Base::flush()`".to_string(),
+        ],
+        summary(&files)
+    );
+}
+
+#[test]
+fn test_unknown_classes_may_be_subtypes() {
+    let files = vec![File::new()
+        .interface(
+            Interface::new("Source")
+                .method(Method::new("items").returns(DataType::Iterable))
+                .method(
+                    Method::new("load")
+                        .parameter(Parameter::new("from").typed(named("\\Vendor\\Path"))),
+                )
+                .method(Method::new("error").returns(named("\\Throwable"))),
+        )
+        .class(
+            Class::new("ArraySource")
+                .implements("Source")
+                .method(
+                    Method::new("items")
+                        .returns(named("\\ArrayIterator"))
+                        .body(""),
+                )
+                .method(
+                    Method::new("load")
+                        .parameter(Parameter::new("from").typed(named("\\Vendor\\File")))
+                        .body(""),
+                )
+                .method(Method::new("error").returns(named("\\Countable")).body("")),
+        )];
+
+    assert_eq!(
+        vec![
+            "error[incompatible-signature] Class ArraySource > method error: method `error` is not compatible with `Source::error()`: return type `\\Countable` is not a subtype of `\\Throwable`".to_string(),
+        ],
+        summary(&files)
+    );
+}
+
+#[test]
+fn test_unknown_ancestors_may_implement_methods() {
+    let files = vec![File::new()
+        .interface(Interface::new("Handler").method(Method::new("handle")))
+        .class(
+            Class::new("Controller")
+                .extends("\\Vendor\\Base")
+                .implements("Handler"),
+        )
+        .class(
+            Class::new("Action")
+                .using("\\Vendor\\Handles")
+                .implements("Handler"),
+        )
+        .class(Class::new("Broken").implements("Handler"))];
+
+    assert_eq!(
+        vec![
+            "error[unimplemented-method] Class Broken: class `Broken` must implement `Handler::handle()` or be declared abstract".to_string(),
+        ],
+        summary(&files)
+    );
+}
+
+#[test]
+fn test_signature_variance() {
+    let files = vec![File::new()
+        .interface(
+            Interface::new("Handler")
+                .method(
+                    Method::new("handle")
+                        .parameter(Parameter::new("request").typed(named("Request")))
+                        .parameter(Parameter::new("items").typed(DataType::Array).reference())
+                        .parameter(Parameter::new("options").variadic())
+                        .returns(DataType::Nullable(Box::new(DataType::StaticReference))),
+                )
+                .method(
+                    Method::new("name")
+                        .parameter(Parameter::new("short").typed(DataType::Boolean))
+                        .returns(DataType::Union(vec![DataType::String, DataType::Null])),
+                ),
+        )
+        .class(
+            Class::new("Valid")
+                .implements("Handler")
+                .method(
+                    Method::new("handle")
+                        .parameter(Parameter::new("request"))
+                        .parameter(
+                            Parameter::new("items")
+                                .typed(DataType::Iterable)
+                                .reference(),
+                        )
+                        .parameter(Parameter::new("rest").variadic())
+                        .returns(DataType::StaticReference)
+                        .body(""),
+                )
+                .method(
+                    Method::new("name")
+                        .parameter(Parameter::new("short").typed(DataType::Boolean))
+                        .parameter(Parameter::new("upper").default(false))
+                        .returns(DataType::Never)
+                        .body(""),
+                ),
+        )
+        .class(
+            Class::new("Invalid")
+                .implements("Handler")
+                .method(
+                    Method::new("handle")
+                        .parameter(Parameter::new("request").typed(named("SpecialRequest")))
+                        .parameter(Parameter::new("items").typed(DataType::Array))
+                        .returns(DataType::SelfReference)
+                        .body(""),
+                )
+                .method(
+                    Method::new("name")
+                        .parameter(Parameter::new("short").typed(DataType::True))
+                        .parameter(Parameter::new("upper"))
+                        .body(""),
+                ),
+        )
+        .class(Class::new("Request"))
+        .class(Class::new("SpecialRequest"))];
+
+    assert_eq!(
+        vec![
+            "error[incompatible-signature] Class Invalid > method handle: method `handle` is not compatible with `Handler::handle()`: parameter $request of type `SpecialRequest` does not accept `Request`".to_string(),
+            "error[incompatible-signature] Class Invalid > method handle: method `handle` is not compatible with `Handler::handle()`: parameter $items must be passed by reference".to_string(),
+            "error[incompatible-signature] Class Invalid > method handle: method `handle` is not compatible with `Handler::handle()`: parameter $options is missing".to_string(),
+            "error[incompatible-signature] Class Invalid > method handle: method `handle` is not compatible with `Handler::handle()`: return type `self` is not a subtype of `null|static`".to_string(),
+            "error[incompatible-signature] Class Invalid > method name: method `name` is not compatible with `Handler::name()`: parameter $short of type `true` does not accept `bool`".to_string(),
+            "error[incompatible-signature] Class Invalid > method name: method `name` is not compatible with `Handler::name()`: additional parameter $upper must be optional".to_string(),
+            "error[incompatible-signature] Class Invalid > method name: method `name` is not compatible with `Handler::name()`: return type must be compatible with `string|null`".to_string(),
+        ],
+        summary(&files)
+    );
+}
@@ -0,0 +1,51 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::interpolation::Expression;
+use php_codegen::interpolation::InterpolatedString;
+use php_codegen::literal::Value;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+#[test]
+fn test_double_quoted_interpolation() {
+    let string = InterpolatedString::new()
+        .literal("User ")
+        .expression(Expression::variable("user").property("name"))
+        .literal(" has ")
+        .variable("count")
+        .literal(" items");
+
+    assert_eq!(
+        "\"User {$user->name} has {$count} items\"",
+        string.generate(Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_literal_segments_are_escaped() {
+    let string = InterpolatedString::new()
+        .literal("Price: $5 \"each\" {$not} \\ \n\t\u{1B}\u{0}")
+        .expression(
+            Expression::variable("items")
+                .offset("first")
+                .call("format", vec![Value::Integer(2)]),
+        );
+
+    assert_eq!(
+        "\"Price: \\$5 \\\"each\\\" {\\$not} \\\\ \\n\\t\\e\\x00{$items[\"first\"]->format(2)}\"",
+        string.generate(Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_heredoc_output() {
+    let string = InterpolatedString::new()
+        .literal("Dear $name,\n\"quoted\"\nEOT is not the end\n")
+        .variable("signature")
+        .heredoc("EOT");
+
+    assert_eq!(
+        "<<<EOT_1\nDear \\$name,\n\"quoted\"\nEOT is not the end\n{$signature}\nEOT_1",
+        Value::from(string).generate(Indentation::default(), 0)
+    );
+}
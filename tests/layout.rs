@@ -0,0 +1,178 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::method::Method;
+use php_codegen::name::Name;
+use php_codegen::parameter::Parameter;
+use php_codegen::property::Property;
+use php_codegen::r#enum::Enum;
+use php_codegen::style::Style;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+#[test]
+fn test_parameter_lists() {
+    let class = Class::new("UserService")
+        .method(
+            Method::new("__construct")
+                .public()
+                .parameter(
+                    Parameter::new("users")
+                        .private()
+                        .typed(DataType::Named(Name::new("UserRepository"))),
+                )
+                .body(""),
+        )
+        .method(
+            Method::new("find")
+                .public()
+                .parameter(Parameter::new("id").typed(DataType::Integer))
+                .returns(DataType::Nullable(Box::new(DataType::Named(Name::new(
+                    "User",
+                )))))
+                .body("return $this->users->find($id);"),
+        )
+        .method(
+            Method::new("search")
+                .public()
+                .parameter(Parameter::new("query").typed(DataType::String))
+                .parameter(
+                    Parameter::new("filters")
+                        .typed(DataType::Array)
+                        .default(vec![1i64, 2, 3]),
+                )
+                .parameter(
+                    Parameter::new("page")
+                        .typed(DataType::Integer)
+                        .default(1i64),
+                )
+                .parameter(
+                    Parameter::new("perPage")
+                        .typed(DataType::Integer)
+                        .default(50i64),
+                )
+                .returns(DataType::Array)
+                .body("return [];"),
+        );
+
+    let expected = r#"class UserService
+{
+    public function __construct(
+        private UserRepository $users,
+    ) {}
+
+    public function find(int $id): null|User
+    {
+        return $this->users->find($id);
+    }
+
+    public function search(
+        string $query,
+        array $filters = [1, 2, 3],
+        int $page = 1,
+        int $perPage = 50,
+    ): array {
+        return [];
+    }
+}
+"#;
+
+    assert_eq!(
+        expected,
+        class.generate_styled(&Style::per_cs().line_width(80), Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_implements_lists() {
+    let style = Style::per_cs().line_width(60);
+
+    let class = Class::new("Collection")
+        .extends("AbstractCollection")
+        .implements("\\ArrayAccess")
+        .implements("\\IteratorAggregate")
+        .implements("\\Countable");
+    assert_eq!(
+        "class Collection extends AbstractCollection implements\n    \\ArrayAccess,\n    \\IteratorAggregate,\n    \\Countable\n{\n}\n",
+        class.generate_styled(&style, Indentation::default(), 0)
+    );
+
+    let r#enum = Enum::new("Status")
+        .string_backed()
+        .implements("HasLabel")
+        .case(("Active", "active"));
+    assert_eq!(
+        "enum Status: string implements HasLabel\n{\n    case Active = \"active\";\n}\n",
+        r#enum.generate_styled(&style, Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_long_union_types() {
+    let union = DataType::Union(vec![
+        DataType::Named(Name::new("\\DateTimeInterface")),
+        DataType::Named(Name::new("\\Stringable")),
+        DataType::String,
+        DataType::Null,
+    ]);
+
+    let class = Class::new("Event")
+        .property(Property::new("date").private().typed(union.clone()))
+        .method(
+            Method::new("at")
+                .public()
+                .parameter(Parameter::new("date").typed(union))
+                .parameter(Parameter::new("zone").typed(DataType::String))
+                .body("return $this;"),
+        );
+
+    let expected = r#"class Event
+{
+    private \DateTimeInterface
+        |\Stringable
+        |string
+        |null $date;
+
+    public function at(
+        \DateTimeInterface
+            |\Stringable
+            |string
+            |null $date,
+        string $zone,
+    ) {
+        return $this;
+    }
+}
+"#;
+
+    assert_eq!(
+        expected,
+        class.generate_styled(&Style::per_cs().line_width(40), Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_signatures_that_fit_stay_on_one_line() {
+    let method = Method::new("find")
+        .public()
+        .parameter(Parameter::new("id").typed(DataType::Integer))
+        .body("return null;");
+
+    // `public function find(int $id)` is exactly 29 characters wide.
+    assert_eq!(
+        "public function find(int $id)\n{\n    return null;\n}\n",
+        method.generate_styled(&Style::per_cs().line_width(29), Indentation::default(), 0)
+    );
+    assert_eq!(
+        "public function find(\n    int $id,\n) {\n    return null;\n}\n",
+        method.generate_styled(&Style::per_cs().line_width(28), Indentation::default(), 0)
+    );
+
+    // Methods without parameters have nothing to wrap.
+    let method = Method::new("reset").public().body("");
+    assert_eq!(
+        "public function reset() {}\n",
+        method.generate_styled(&Style::per_cs().line_width(1), Indentation::default(), 0)
+    );
+}
@@ -0,0 +1,103 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::comment::Document;
+use php_codegen::file::File;
+use php_codegen::method::Method;
+use php_codegen::style::LineEnding;
+use php_codegen::style::Style;
+
+fn file() -> File {
+    File::new().namespaced("App").class(
+        Class::new("Greeter")
+            .document(Document::new().text("Greets people.\r\nPolitely.\rAlways."))
+            .method(
+                Method::new("greet")
+                    .public()
+                    .body("$greeting = 'Hello';\r\nreturn $greeting;\rreturn null;"),
+            ),
+    )
+}
+
+#[test]
+fn test_mixed_line_endings_are_normalized() {
+    let expected = r#"<?php
+
+namespace App;
+
+/**
+ * Greets people.
+ * Politely.
+ * Always.
+ */
+class Greeter
+{
+    public function greet() {
+        $greeting = 'Hello';
+        return $greeting;
+        return null;
+    }
+}
+"#;
+
+    assert_eq!(expected, file().to_string());
+}
+
+#[test]
+fn test_crlf() {
+    let code = file()
+        .style(Style::new().line_ending(LineEnding::CrLf))
+        .to_string();
+
+    assert!(code.starts_with("<?php\r\n\r\nnamespace App;\r\n"));
+    assert!(code.ends_with("    }\r\n}\r\n"));
+    assert_eq!(code.matches('\n').count(), code.matches("\r\n").count());
+}
+
+#[test]
+fn test_final_newline_and_bom() {
+    let code = file()
+        .style(Style::new().final_newline(false).bom(true))
+        .to_string();
+
+    assert!(code.starts_with("\u{feff}<?php\n"));
+    assert!(code.ends_with("    }\n}"));
+
+    let code = File::new()
+        .style(Style::per_cs().line_ending(LineEnding::CrLf).bom(true))
+        .to_string();
+
+    assert_eq!("\u{feff}<?php\r\n", code);
+}
+
+#[test]
+fn test_crlf_input_is_not_doubled() {
+    let code = File::new()
+        .class(
+            Class::new("Greeter")
+                .method(Method::new("greet").body("$a = 1;\r\n\r\nreturn $a;\r\n")),
+        )
+        .style(Style::new().line_ending(LineEnding::CrLf))
+        .to_string();
+
+    assert!(!code.contains("\r\r"));
+    assert_eq!(
+        "<?php\r\n\r\nclass Greeter\r\n{\r\n    function greet() {\r\n        $a = 1;\r\n        \r\n        return $a;\r\n    }\r\n}\r\n",
+        code
+    );
+}
+
+#[test]
+fn test_final_newline_is_not_added_twice() {
+    let code = File::new()
+        .style(Style::new().final_newline(true))
+        .to_string();
+
+    assert_eq!("<?php\n", code);
+
+    let code = File::new()
+        .style(Style::new().final_newline(false))
+        .to_string();
+
+    assert_eq!("<?php", code);
+}
@@ -0,0 +1,149 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::constant::ClassConstant;
+use php_codegen::file::File;
+use php_codegen::lexer::tokenize;
+use php_codegen::lexer::TokenKind;
+use php_codegen::lint::check;
+use php_codegen::lint::lint;
+use php_codegen::literal::Value;
+use php_codegen::method::Method;
+use php_codegen::style::LineEnding;
+use php_codegen::style::Style;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+fn summary(code: &str) -> Vec<String> {
+    lint(code).iter().map(|error| error.to_string()).collect()
+}
+
+#[test]
+fn test_tokenize() {
+    let code = "<?php\n\n#[Pure]\nfunction foo(int $a = 0x1F, ...$rest): ?\\App\\User {\n    return \"{$a['key']}\" . <<<EOT\n    text\n    EOT;\n}\n";
+    let tokens: Vec<(TokenKind, String, usize, usize)> = tokenize(code)
+        .unwrap()
+        .into_iter()
+        .filter(|token| token.kind != TokenKind::Whitespace)
+        .map(|token| (token.kind, token.text, token.line, token.column))
+        .collect();
+
+    let expected = vec![
+        (TokenKind::OpenTag, "<?php", 1, 1),
+        (TokenKind::Symbol, "#[", 3, 1),
+        (TokenKind::Name, "Pure", 3, 3),
+        (TokenKind::Symbol, "]", 3, 7),
+        (TokenKind::Name, "function", 4, 1),
+        (TokenKind::Name, "foo", 4, 10),
+        (TokenKind::Symbol, "(", 4, 13),
+        (TokenKind::Name, "int", 4, 14),
+        (TokenKind::Variable, "$a", 4, 18),
+        (TokenKind::Symbol, "=", 4, 21),
+        (TokenKind::Integer, "0x1F", 4, 23),
+        (TokenKind::Symbol, ",", 4, 27),
+        (TokenKind::Symbol, "...", 4, 29),
+        (TokenKind::Variable, "$rest", 4, 32),
+        (TokenKind::Symbol, ")", 4, 37),
+        (TokenKind::Symbol, ":", 4, 38),
+        (TokenKind::Symbol, "?", 4, 40),
+        (TokenKind::Name, "\\App\\User", 4, 41),
+        (TokenKind::Symbol, "{", 4, 51),
+        (TokenKind::Name, "return", 5, 5),
+        (TokenKind::String, "\"{$a['key']}\"", 5, 12),
+        (TokenKind::Symbol, ".", 5, 26),
+        (TokenKind::Heredoc, "<<<EOT\n    text\n    EOT", 5, 28),
+        (TokenKind::Symbol, ";", 7, 8),
+        (TokenKind::Symbol, "}", 8, 1),
+    ];
+
+    assert_eq!(
+        expected
+            .into_iter()
+            .map(|(kind, text, line, column)| (kind, text.to_string(), line, column))
+            .collect::<Vec<_>>(),
+        tokens
+    );
+}
+
+#[test]
+fn test_lint_errors() {
+    assert_eq!(Vec::<String>::new(), summary("<?php\n\necho [1, (2)];\n"));
+    assert_eq!(
+        vec!["3:6: unterminated string".to_string()],
+        summary("<?php\n\necho 'foo;\n")
+    );
+    assert_eq!(
+        vec!["2:1: unterminated comment".to_string()],
+        summary("<?php\n/* foo\n")
+    );
+    assert_eq!(
+        vec!["1:6: invalid numeric literal".to_string()],
+        summary("echo 1abc;")
+    );
+    assert_eq!(
+        vec!["1:6: expected an identifier after `\\`".to_string()],
+        summary("echo App\\;")
+    );
+    assert_eq!(
+        vec!["1:11: unexpected character `\\u{7}`".to_string()],
+        summary("echo $foo \u{7};")
+    );
+    assert_eq!(
+        vec![
+            "1:13: unexpected `)`, expected `]` to close `[` at 1:8".to_string(),
+            "1:1: unclosed `{`".to_string(),
+        ],
+        summary("{ echo [1, 2);")
+    );
+}
+
+#[test]
+fn test_check_generated_code() {
+    let valid = Class::new("Foo").method(Method::new("bar").body("return [1, 2];"));
+    assert!(check(&valid, Indentation::default(), 0).is_ok());
+
+    let file = File::new().class(
+        Class::new("Foo")
+            .constant(ClassConstant::new("BAR").valued(Value::Literal("['a' => 1".to_string())))
+            .method(Method::new("baz").body("return 1;")),
+    );
+    assert_eq!(
+        vec![
+            "10:1: unexpected `}`, expected `]` to close `[` at 5:17".to_string(),
+            "4:1: unclosed `{`".to_string(),
+        ],
+        summary(&file.generate(Indentation::default(), 0))
+    );
+
+    let method = Method::new("baz").body("return \"unterminated;");
+    let errors: Vec<String> = check(&method, Indentation::default(), 0)
+        .unwrap_err()
+        .iter()
+        .map(|error| error.to_string())
+        .collect();
+
+    assert_eq!(vec!["2:12: unterminated string".to_string()], errors);
+}
+
+#[test]
+fn test_crlf_and_cr_line_endings() {
+    assert_eq!(
+        Vec::<String>::new(),
+        summary("<?php\r\n$a = <<<EOT\r\nhi\r\nEOT;\r\n// done\r\n")
+    );
+    assert_eq!(
+        Vec::<String>::new(),
+        summary("<?php\r$a = <<<'EOT'\rhi\r  EOT;\r")
+    );
+    assert_eq!(
+        vec!["4:6: unterminated string".to_string()],
+        summary("<?php\r\n$a = <<<EOT\r\nhi\r\nEOT; 'foo\r\n")
+    );
+
+    let file = File::new()
+        .class(
+            Class::new("Greeter").method(Method::new("greet").body("return <<<EOT\nHello\nEOT;")),
+        )
+        .style(Style::new().line_ending(LineEnding::CrLf));
+    assert_eq!(Vec::<String>::new(), summary(&file.to_string()));
+}
@@ -0,0 +1,228 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::constant::ClassConstant;
+use php_codegen::data_type::DataType;
+use php_codegen::method::Method;
+use php_codegen::modifiers;
+use php_codegen::modifiers::Modifier;
+use php_codegen::modifiers::ModifierError;
+use php_codegen::modifiers::ModifierSite;
+use php_codegen::modifiers::VisibilityModifier;
+use php_codegen::parameter::Parameter;
+use php_codegen::property::Property;
+use php_codegen::validation::Validator;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+#[test]
+fn test_modifier_rules_per_site() {
+    let private = Some(&VisibilityModifier::Private);
+    let public = Some(&VisibilityModifier::Public);
+
+    assert!(modifiers::validate(
+        ModifierSite::Class,
+        None,
+        &[Modifier::Final, Modifier::Readonly]
+    )
+    .is_ok());
+    assert!(modifiers::validate(ModifierSite::Property, public, &[Modifier::Readonly]).is_ok());
+    assert!(modifiers::validate(ModifierSite::TraitMethod, private, &[Modifier::Abstract]).is_ok());
+    assert!(
+        modifiers::validate(ModifierSite::InterfaceMethod, public, &[Modifier::Static]).is_ok()
+    );
+
+    assert_eq!(
+        Err(ModifierError::Conflict {
+            first: "abstract".to_string(),
+            second: "final".to_string(),
+            site: ModifierSite::Class,
+        }),
+        modifiers::validate(
+            ModifierSite::Class,
+            None,
+            &[Modifier::Abstract, Modifier::Final]
+        )
+    );
+    assert_eq!(
+        "`static` and `readonly` modifiers cannot be combined on properties",
+        modifiers::validate(
+            ModifierSite::Property,
+            public,
+            &[Modifier::Static, Modifier::Readonly]
+        )
+        .unwrap_err()
+        .to_string()
+    );
+    assert_eq!(
+        "`abstract` and `private` modifiers cannot be combined on methods",
+        modifiers::validate(ModifierSite::Method, private, &[Modifier::Abstract])
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "`static` modifier is not allowed on class constants",
+        modifiers::validate(ModifierSite::ClassConstant, public, &[Modifier::Static])
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "`final` and `private` modifiers cannot be combined on class constants",
+        modifiers::validate(ModifierSite::ClassConstant, private, &[Modifier::Final])
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "`final` modifier is used more than once",
+        modifiers::validate(
+            ModifierSite::Method,
+            public,
+            &[Modifier::Final, Modifier::Static, Modifier::Final]
+        )
+        .unwrap_err()
+        .to_string()
+    );
+    assert_eq!(
+        "`private` modifier is not allowed on interface methods",
+        modifiers::validate(ModifierSite::InterfaceMethod, private, &[])
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "`abstract` modifier is not allowed on enum methods",
+        modifiers::validate(ModifierSite::EnumMethod, public, &[Modifier::Abstract])
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "`static` modifier is not allowed on promoted parameters",
+        modifiers::validate(ModifierSite::PromotedParameter, public, &[Modifier::Static])
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[test]
+fn test_modifiers_print_in_canonical_order() {
+    let class = Class::new("Foo")
+        .modifier(Modifier::Readonly)
+        .modifier(Modifier::Final)
+        .constant(
+            ClassConstant::new("A")
+                .valued(1)
+                .modifier(Modifier::Final)
+                .public(),
+        )
+        .property(
+            Property::new("cache")
+                .modifier(Modifier::Static)
+                .protected()
+                .typed(DataType::Array),
+        )
+        .method(
+            Method::new("__construct")
+                .parameter(
+                    Parameter::new("id")
+                        .modifier(Modifier::Readonly)
+                        .private()
+                        .typed(DataType::Integer),
+                )
+                .public()
+                .body(""),
+        )
+        .method(
+            Method::new("create")
+                .modifier(Modifier::Static)
+                .modifier(Modifier::Final)
+                .public()
+                .body("return new self(1);"),
+        );
+
+    assert_eq!(
+        r#"final readonly class Foo
+{
+    final public const A = 1;
+
+    protected static array $cache;
+    public function __construct(
+        private readonly int $id,
+    ) {
+
+    }
+
+    final public static function create() {
+        return new self(1);
+    }
+}
+"#,
+        class.generate(Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_modifier_diagnostics() {
+    let class = Class::new("Foo")
+        .modifier(Modifier::Abstract)
+        .modifier(Modifier::Final)
+        .constant(ClassConstant::new("A").valued(1).modifier(Modifier::Static))
+        .method(Method::new("bar").private().modifier(Modifier::Abstract));
+
+    assert_eq!(
+        vec![
+            "error[invalid-modifiers] Class Foo: `abstract` and `final` modifiers cannot be combined on classes".to_string(),
+            "error[invalid-modifiers] Class Foo > constant A: `static` modifier is not allowed on class constants".to_string(),
+            "error[invalid-modifiers] Class Foo > method bar: `abstract` and `private` modifiers cannot be combined on methods".to_string(),
+        ],
+        class
+            .validate()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_final_private_members() {
+    let private = Some(&VisibilityModifier::Private);
+
+    assert!(modifiers::validate(ModifierSite::Constructor, private, &[Modifier::Final]).is_ok());
+    assert_eq!(
+        "`final` and `private` modifiers cannot be combined on methods",
+        modifiers::validate(ModifierSite::Method, private, &[Modifier::Final])
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "`final` and `private` modifiers cannot be combined on properties",
+        modifiers::validate(ModifierSite::Property, private, &[Modifier::Final])
+            .unwrap_err()
+            .to_string()
+    );
+
+    let class = Class::new("Foo")
+        .property(Property::new("id").private().modifier(Modifier::Final))
+        .method(
+            Method::new("__construct")
+                .private()
+                .modifier(Modifier::Final)
+                .body(""),
+        )
+        .method(
+            Method::new("reset")
+                .private()
+                .modifier(Modifier::Final)
+                .body(""),
+        );
+
+    assert_eq!(
+        vec![
+            "error[invalid-modifiers] Class Foo > property $id: `final` and `private` modifiers cannot be combined on properties".to_string(),
+            "error[invalid-modifiers] Class Foo > method reset: `final` and `private` modifiers cannot be combined on methods".to_string(),
+        ],
+        class
+            .validate()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>()
+    );
+}
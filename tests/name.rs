@@ -0,0 +1,106 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::name::Name;
+use php_codegen::name::NameError;
+use php_codegen::name::NameKind;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+#[test]
+fn test_name_kinds() {
+    assert_eq!(NameKind::Unqualified, Name::new("Foo").kind);
+    assert_eq!(NameKind::Qualified, Name::new("Foo\\Bar").kind);
+    assert_eq!(NameKind::FullyQualified, Name::new("\\Foo\\Bar").kind);
+    assert_eq!(NameKind::Relative, Name::new("namespace\\Foo").kind);
+
+    assert_eq!("\\Foo\\Bar", Name::new("\\Foo\\Bar").to_string());
+    assert_eq!("namespace\\Foo", Name::new("NameSpace\\Foo").to_string());
+    assert_eq!("Bar", Name::new("\\Foo\\Bar").last());
+}
+
+#[test]
+fn test_name_validation() {
+    assert!(Name::parse("\\App\\Entity\\User").is_ok());
+    assert!(Name::parse("_Ünïcode\\Näme2").is_ok());
+
+    assert_eq!(Err(NameError::Empty), Name::parse(""));
+    assert_eq!(
+        Err(NameError::InvalidSegment {
+            name: "App\\my-class".to_string(),
+            segment: "my-class".to_string(),
+        }),
+        Name::parse("App\\my-class")
+    );
+    assert!(Name::parse("App\\\\User").is_err());
+    assert!(Name::parse("App\\123").is_err());
+}
+
+#[test]
+fn test_name_resolution() {
+    let file = File::new()
+        .namespaced("App\\Service")
+        .uses("App\\Entity\\User")
+        .uses("Vendor\\Sdk as Sdk")
+        .uses_function("Vendor\\Sdk\\helper")
+        .uses_constant("Vendor\\Sdk\\VERSION");
+
+    let scope = file.scope();
+
+    assert_eq!(
+        "\\App\\Entity\\User",
+        Name::new("user").resolve(&scope).to_string()
+    );
+    assert_eq!(
+        "\\Vendor\\Sdk\\Client",
+        Name::new("Sdk\\Client").resolve(&scope).to_string()
+    );
+    assert_eq!(
+        "\\App\\Service\\Mailer",
+        Name::new("Mailer").resolve(&scope).to_string()
+    );
+    assert_eq!(
+        "\\App\\Service\\Sub\\Mailer",
+        Name::new("namespace\\Sub\\Mailer")
+            .resolve(&scope)
+            .to_string()
+    );
+    assert_eq!(
+        "\\DateTime",
+        Name::new("\\DateTime").resolve(&scope).to_string()
+    );
+    assert_eq!("static", Name::new("static").resolve(&scope).to_string());
+
+    assert_eq!(
+        "\\Vendor\\Sdk\\helper",
+        scope.resolve_function(&Name::new("HELPER")).to_string()
+    );
+    assert_eq!(
+        "\\App\\Service\\version",
+        scope.resolve_constant(&Name::new("version")).to_string()
+    );
+    assert_eq!(
+        "\\Vendor\\Sdk\\VERSION",
+        scope.resolve_constant(&Name::new("VERSION")).to_string()
+    );
+}
+
+#[test]
+fn test_names_are_accepted_by_builders() {
+    let class = Class::new("Foo")
+        .extends(Name::new("\\App\\Base"))
+        .implements("Countable")
+        .implements(Name::new("namespace\\Contract"));
+
+    assert_eq!(
+        "class Foo extends \\App\\Base implements Countable, namespace\\Contract\n{\n}\n",
+        class.generate(Indentation::default(), 0)
+    );
+
+    assert_eq!(
+        "\\Foo\\Bar",
+        DataType::from(Name::new("\\Foo\\Bar")).generate(Indentation::default(), 0)
+    );
+}
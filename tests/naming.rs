@@ -0,0 +1,77 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::constant::ClassConstant;
+use php_codegen::identifier::Context;
+use php_codegen::naming;
+use php_codegen::naming::Case;
+use php_codegen::naming::Naming;
+use php_codegen::property::Property;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+#[test]
+fn test_case_conversion() {
+    assert_eq!(
+        vec!["HTTP", "Server", "Error"],
+        naming::words("HTTPServerError")
+    );
+    assert_eq!(vec!["base64", "Encode"], naming::words("base64Encode"));
+    assert_eq!(vec!["user", "id", "v2"], naming::words("user-id__v2"));
+
+    assert_eq!("userProfileId", Case::Camel.apply("user_profile_id"));
+    assert_eq!("HttpServer", Case::Pascal.apply("HTTP-server"));
+    assert_eq!("created_at", Case::Snake.apply("createdAt"));
+    assert_eq!(
+        "MAX_UTF8_LENGTH",
+        Case::ScreamingSnake.apply("maxUtf8Length")
+    );
+    assert_eq!("my-field", Case::Preserve.apply("my-field"));
+}
+
+#[test]
+fn test_naming_policy() {
+    let naming = Naming::new().acronym("ID").acronym("URL");
+
+    assert_eq!("UserProfile", naming.apply("user_profile", Context::Class));
+    assert_eq!("avatarURL", naming.apply("avatar_url", Context::Property));
+    assert_eq!("userID", naming.apply("user-id", Context::Variable));
+    assert_eq!("urlPath", naming.apply("URL_PATH", Context::Method));
+    assert_eq!(
+        "PAGE_SIZE",
+        naming.apply("pageSize", Context::ClassConstant)
+    );
+
+    let naming = Naming::new().case(Context::Method, Case::Snake).sanitize();
+
+    assert_eq!("list_items", naming.apply("listItems", Context::Method));
+    assert_eq!("List_", naming.apply("list", Context::Class));
+    assert_eq!("_3dModel", naming.apply("3d-model", Context::Property));
+}
+
+#[test]
+fn test_named_constructors_keep_the_original_name() {
+    let naming = Naming::new();
+
+    let class = Class::named("user_profile", &naming)
+        .constant(ClassConstant::named("max-length", &naming).valued(64))
+        .property(Property::named("first_name", &naming).public());
+
+    assert_eq!(Some("user_profile".to_string()), class.original_name);
+    assert_eq!(
+        Some("first_name".to_string()),
+        class.properties[0].original_name
+    );
+    assert_eq!(None, Class::new("UserProfile").original_name);
+
+    assert_eq!(
+        r#"class UserProfile
+{
+    const MAX_LENGTH = 64;
+
+    public $firstName;
+}
+"#,
+        class.generate(Indentation::default(), 0)
+    );
+}
@@ -0,0 +1,246 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::constant::ClassConstant;
+use php_codegen::data_type::DataType;
+use php_codegen::enum_case::EnumCase;
+use php_codegen::interface::Interface;
+use php_codegen::method::Method;
+use php_codegen::modifiers::Modifier;
+use php_codegen::ordering::MemberOrder;
+use php_codegen::property::Property;
+use php_codegen::r#enum::Enum;
+use php_codegen::style::Style;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+fn class() -> Class {
+    Class::new("Account")
+        .constant(ClassConstant::new("TYPE").private().valued("user"))
+        .constant(ClassConstant::new("KIND").public().valued("account"))
+        .property(Property::new("name").private().typed(DataType::String))
+        .property(
+            Property::new("count")
+                .public()
+                .modifier(Modifier::Static)
+                .typed(DataType::Integer),
+        )
+        .property(Property::new("email").protected().typed(DataType::String))
+        .method(Method::new("rename").public().body("$this->name = $name;"))
+        .method(
+            Method::new("create")
+                .public()
+                .modifier(Modifier::Static)
+                .body("return new self();"),
+        )
+        .method(Method::new("__construct").public().body("self::$count++;"))
+        .method(Method::new("audit").private().body("return [];"))
+}
+
+#[test]
+fn test_insertion_order() {
+    let expected = r#"class Account
+{
+    private const TYPE = "user";
+
+    public const KIND = "account";
+
+    private string $name;
+    public static int $count;
+    protected string $email;
+    public function rename() {
+        $this->name = $name;
+    }
+
+    public static function create() {
+        return new self();
+    }
+
+    public function __construct() {
+        self::$count++;
+    }
+
+    private function audit() {
+        return [];
+    }
+}
+"#;
+
+    assert_eq!(expected, class().generate(Indentation::default(), 0));
+}
+
+#[test]
+fn test_ordering_policies() {
+    let order = MemberOrder::new()
+        .constructor_first()
+        .statics_first()
+        .by_visibility()
+        .alphabetical();
+
+    let expected = r#"class Account
+{
+    public const KIND = "account";
+
+    private const TYPE = "user";
+
+    public static int $count;
+
+    protected string $email;
+
+    private string $name;
+
+    public function __construct() {
+        self::$count++;
+    }
+
+    public static function create() {
+        return new self();
+    }
+
+    public function rename() {
+        $this->name = $name;
+    }
+
+    private function audit() {
+        return [];
+    }
+}
+"#;
+
+    let style = Style::new()
+        .member_order(order)
+        .blank_lines_between_groups(1);
+    assert_eq!(
+        expected,
+        class().generate_styled(&style, Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_reordered_inputs_print_the_same() {
+    let style = Style::new()
+        .member_order(MemberOrder::new().by_visibility().alphabetical())
+        .blank_lines_between_members(1)
+        .blank_lines_between_groups(2);
+
+    let first = Class::new("Point")
+        .property(Property::new("y").public().typed(DataType::Integer))
+        .property(Property::new("x").public().typed(DataType::Integer))
+        .method(Method::new("length").public().body("return 0;"))
+        .method(Method::new("clone").private().body("return $this;"));
+    let second = Class::new("Point")
+        .property(Property::new("x").public().typed(DataType::Integer))
+        .property(Property::new("y").public().typed(DataType::Integer))
+        .method(Method::new("clone").private().body("return $this;"))
+        .method(Method::new("length").public().body("return 0;"));
+
+    let expected = r#"class Point
+{
+    public int $x;
+
+    public int $y;
+
+
+    public function length() {
+        return 0;
+    }
+
+
+    private function clone() {
+        return $this;
+    }
+}
+"#;
+
+    assert_eq!(
+        expected,
+        first.generate_styled(&style, Indentation::default(), 0)
+    );
+    assert_eq!(
+        expected,
+        second.generate_styled(&style, Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_policies_ignore_case() {
+    let class = Class::new("Account")
+        .method(Method::new("zip").public().body(""))
+        .method(Method::new("Alpha").public().body(""))
+        .method(Method::new("__CONSTRUCT").public().body(""));
+
+    let style = Style::new()
+        .member_order(MemberOrder::new().constructor_first().alphabetical())
+        .blank_lines_between_groups(0)
+        .blank_lines_between_members(0);
+
+    let code = class.generate_styled(&style, Indentation::default(), 0);
+    let names = code
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("public function "))
+        .collect::<Vec<&str>>();
+
+    assert_eq!(vec!["__CONSTRUCT() {", "Alpha() {", "zip() {"], names);
+}
+
+#[test]
+fn test_policies_on_an_empty_class() {
+    let style = Style::new()
+        .member_order(MemberOrder::new().statics_first().by_visibility())
+        .blank_lines_between_groups(3);
+
+    assert_eq!(
+        "class Account\n{\n}\n",
+        Class::new("Account").generate_styled(&style, Indentation::default(), 0)
+    );
+}
+
+#[test]
+fn test_enums_and_interfaces_follow_the_style() {
+    let style = Style::new()
+        .member_order(MemberOrder::new().by_visibility().alphabetical())
+        .blank_lines_between_members(0)
+        .blank_lines_between_groups(1);
+
+    let r#enum = Enum::new("Suit")
+        .constant(ClassConstant::new("WILD").valued("joker"))
+        .constant(ClassConstant::new("DEFAULT").valued("spades"))
+        .case(EnumCase::new("Spades"))
+        .case(EnumCase::new("Hearts"))
+        .method(Method::new("label").public().body("return '';"))
+        .method(Method::new("color").private().body("return '';"))
+        .method(Method::new("cards").public().body("return [];"));
+
+    assert_eq!(
+        r#"enum Suit
+{
+    const DEFAULT = "spades";
+    const WILD = "joker";
+
+    case Spades;
+    case Hearts;
+
+    public function cards() {
+        return [];
+    }
+    public function label() {
+        return '';
+    }
+
+    private function color() {
+        return '';
+    }
+}
+"#,
+        r#enum.generate_styled(&style, Indentation::default(), 0)
+    );
+
+    let interface = Interface::new("Shape")
+        .method(Method::new("perimeter"))
+        .method(Method::new("area"));
+
+    assert_eq!(
+        "interface Shape\n{\n    public function area();\n    public function perimeter();\n}\n",
+        interface.generate_styled(&style, Indentation::default(), 0)
+    );
+}
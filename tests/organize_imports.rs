@@ -0,0 +1,194 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::attribute::AttributeGroup;
+use php_codegen::class::Class;
+use php_codegen::comment::Document;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::import::ImportOrder;
+use php_codegen::method::Method;
+use php_codegen::property::Property;
+use php_codegen::property::PropertyHook;
+use php_codegen::property::PropertySetHookParameter;
+
+fn imports() -> File {
+    File::new()
+        .namespaced("App")
+        .uses("App\\FooBar")
+        .uses("App\\Foo\\Baz")
+        .uses("app\\Alpha")
+        .uses("App\\Foo")
+}
+
+#[test]
+fn test_import_sorting() {
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use App\Foo;
+use App\FooBar;
+use App\Foo\Baz;
+use app\Alpha;
+"#,
+        imports()
+            .sort_imports(ImportOrder::Alphabetical)
+            .to_string()
+    );
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use app\Alpha;
+use App\Foo;
+use App\Foo\Baz;
+use App\FooBar;
+"#,
+        imports().sort_imports(ImportOrder::Psr12).to_string()
+    );
+}
+
+#[test]
+fn test_deduplication_and_pruning() {
+    let file = File::new()
+        .namespaced("App")
+        .uses("App\\Entity\\User")
+        .uses("App\\Entity\\User")
+        .uses("App\\Entity\\Post")
+        .uses("Vendor\\Sdk")
+        .uses("Psr\\Log\\LoggerInterface")
+        .uses_function("Vendor\\Sdk\\helper")
+        .uses_function("Vendor\\Sdk\\unused")
+        .uses_constant("Vendor\\Sdk\\VERSION")
+        .deduplicate_imports()
+        .prune_imports()
+        .class(
+            Class::new("Publisher").method(
+                Method::new("publish")
+                    .public()
+                    .returns(DataType::Named("User".into()))
+                    .body("// LoggerInterface\n$this->Post = helper(VERSION);\nreturn new Sdk\\User();"),
+            ),
+        );
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use App\Entity\User;
+use Vendor\Sdk;
+
+use function Vendor\Sdk\helper;
+
+use const Vendor\Sdk\VERSION;
+
+
+class Publisher
+{
+    public function publish(): User {
+        // LoggerInterface
+        $this->Post = helper(VERSION);
+        return new Sdk\User();
+    }
+}
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_group_imports() {
+    let file = File::new()
+        .namespaced("App")
+        .uses("App\\Model\\User")
+        .uses("Psr\\Log\\LoggerInterface")
+        .uses("App\\Model\\Post as Article")
+        .uses("App\\Model\\Comment")
+        .uses("Psr\\Log\\LogLevel")
+        .uses_function("Vendor\\Sdk\\helper")
+        .group_imports(3);
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use App\Model\{User, Post as Article, Comment};
+use Psr\Log\LoggerInterface;
+use Psr\Log\LogLevel;
+
+use function Vendor\Sdk\helper;
+"#,
+        file.to_string()
+    );
+}
+
+#[test]
+fn test_pruning_keeps_imports_used_in_docblocks() {
+    let file = File::new()
+        .namespaced("App")
+        .uses("App\\Model\\User")
+        .uses("App\\Model\\Post")
+        .uses("App\\Support\\Collection")
+        .uses("App\\Exception\\NotFound")
+        .uses("App\\Attribute\\Sensitive")
+        .uses("App\\Model\\Unused")
+        .class(
+            Class::new("Repository")
+                .property(
+                    Property::new("secret").public().hook(PropertyHook::Set(
+                        Some(
+                            PropertySetHookParameter::new("$value")
+                                .attributes(AttributeGroup::new().add("Sensitive", None)),
+                        ),
+                        "$this->secret = $value;".into(),
+                    )),
+                )
+                .method(
+                    Method::new("all")
+                        .document(
+                            Document::new()
+                                .text("Unused posts are skipped.")
+                                .tag("return", "Collection<int, User> Users, not Post")
+                                .tag("throws", "NotFound"),
+                        )
+                        .body("return [];"),
+                ),
+        )
+        .prune_imports();
+
+    assert_eq!(
+        r#"<?php
+
+namespace App;
+
+use App\Model\User;
+use App\Support\Collection;
+use App\Exception\NotFound;
+use App\Attribute\Sensitive;
+
+
+class Repository
+{
+    public $secret {
+        set (#[Sensitive] $value) {
+            $this->secret = $value;
+        }
+    }
+    /**
+     * Unused posts are skipped.
+     * @return Collection<int, User> Users, not Post
+     * @throws NotFound
+     */
+    function all() {
+        return [];
+    }
+}
+"#,
+        file.to_string()
+    );
+}
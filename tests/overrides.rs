@@ -0,0 +1,186 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::attribute::AttributeGroup;
+use php_codegen::class::Class;
+use php_codegen::file::File;
+use php_codegen::inheritance;
+use php_codegen::inheritance::Hierarchy;
+use php_codegen::interface::Interface;
+use php_codegen::method::Method;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+fn stubs() -> Vec<File> {
+    vec![File::new()
+        .namespaced("Psr\\Log")
+        .interface(Interface::new("LoggerInterface").method(Method::new("log")))
+        .class(Class::new("AbstractLogger").implements("LoggerInterface"))]
+}
+
+#[test]
+fn test_override_attributes_are_added() {
+    let stubs = stubs();
+    let mut files = vec![File::new()
+        .namespaced("App")
+        .uses("Psr\\Log\\AbstractLogger")
+        .class(
+            Class::new("Base")
+                .method(Method::new("boot").public().body(""))
+                .method(Method::new("secret").private().body("")),
+        )
+        .class(
+            Class::new("Logger")
+                .extends("AbstractLogger")
+                .method(Method::new("log").public().body(""))
+                .method(Method::new("flush").public().body("")),
+        )
+        .class(
+            Class::new("Kernel")
+                .extends("Base")
+                .method(Method::new("Boot").public().body(""))
+                .method(Method::new("secret").private().body(""))
+                .method(
+                    Method::new("terminate")
+                        .attributes(AttributeGroup::new().add("\\Override", None))
+                        .public()
+                        .body(""),
+                ),
+        )
+        .class(
+            Class::new("Handler")
+                .extends("\\Vendor\\Unknown")
+                .method(Method::new("handle").public().body("")),
+        )];
+
+    inheritance::add_override_attributes(&mut files, &stubs);
+
+    let overridden = files[0]
+        .classes
+        .iter()
+        .flat_map(|class| {
+            class.methods.iter().map(move |method| {
+                format!(
+                    "{}::{} {}",
+                    class.name,
+                    method.name,
+                    method.attributes.len()
+                )
+            })
+        })
+        .collect::<Vec<String>>();
+
+    assert_eq!(
+        vec![
+            "Base::boot 0",
+            "Base::secret 0",
+            "Logger::log 1",
+            "Logger::flush 0",
+            "Kernel::Boot 1",
+            "Kernel::secret 0",
+            "Kernel::terminate 1",
+            "Handler::handle 0",
+        ],
+        overridden
+    );
+
+    assert_eq!(
+        r#"    #[\Override]
+    public function log() {
+
+    }
+"#,
+        files[0].classes[1].methods[0].generate(Indentation::default(), 1)
+    );
+
+    // Adding the attributes again changes nothing.
+    let before = files[0].to_string();
+    inheritance::add_override_attributes(&mut files, &stubs);
+    assert_eq!(before, files[0].to_string());
+}
+
+#[test]
+fn test_invalid_override_attributes_are_flagged() {
+    let files = vec![File::new()
+        .namespaced("App")
+        .uses("Override")
+        .class(
+            Class::new("Base")
+                .method(
+                    Method::new("boot")
+                        .attributes(AttributeGroup::new().add("Override", None))
+                        .public()
+                        .body(""),
+                )
+                .method(Method::new("secret").private().body("")),
+        )
+        .class(
+            Class::new("Kernel")
+                .extends("Base")
+                .method(
+                    Method::new("boot")
+                        .attributes(AttributeGroup::new().add("Override", None))
+                        .public()
+                        .body(""),
+                )
+                .method(
+                    Method::new("secret")
+                        .attributes(AttributeGroup::new().add("\\Override", None))
+                        .private()
+                        .body(""),
+                ),
+        )
+        .class(
+            Class::new("Handler").extends("\\Vendor\\Unknown").method(
+                Method::new("handle")
+                    .attributes(AttributeGroup::new().add("\\Override", None))
+                    .public()
+                    .body(""),
+            ),
+        )];
+
+    assert_eq!(
+        vec![
+            "error[invalid-override] Class App\\Base > method boot: method `boot` has `#[\\Override]` but does not override or implement any inherited method".to_string(),
+            "error[invalid-override] Class App\\Kernel > method secret: method `secret` has `#[\\Override]` but does not override or implement any inherited method".to_string(),
+        ],
+        Hierarchy::from_files(&files)
+            .check()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<String>>()
+    );
+}
+
+#[test]
+fn test_stubs_resolve_but_are_not_checked() {
+    let stubs = stubs();
+    let summary = |class: Class| {
+        let files = vec![File::new().namespaced("App").class(class)];
+
+        Hierarchy::from_files(&files)
+            .external(&stubs)
+            .check()
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<String>>()
+    };
+
+    assert!(summary(
+        Class::new("Logger")
+            .extends("\\Psr\\Log\\AbstractLogger")
+            .method(
+                Method::new("log")
+                    .attributes(AttributeGroup::new().add("\\Override", None))
+                    .public()
+                    .body(""),
+            )
+    )
+    .is_empty());
+
+    assert_eq!(
+        vec![
+            "error[unimplemented-method] Class App\\Logger: class `Logger` must implement `LoggerInterface::log()` or be declared abstract".to_string(),
+        ],
+        summary(Class::new("Logger").extends("\\Psr\\Log\\AbstractLogger"))
+    );
+}
@@ -0,0 +1,159 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::data_type::DataType;
+use php_codegen::literal::Value;
+use php_codegen::modifiers::Modifier;
+use php_codegen::parser::parse;
+
+#[test]
+fn test_round_trip() {
+    let code = include_str!("complete.php");
+    let file = parse(code).unwrap();
+
+    assert_eq!(code, file.to_string());
+    assert_eq!(code, parse(&file.to_string()).unwrap().to_string());
+}
+
+#[test]
+fn test_parse_declarations() {
+    let code = r#"<?php
+
+declare(strict_types=1);
+
+namespace App\Http;
+
+use App\Contract\{Handler, Request as BaseRequest};
+
+/**
+ * Handles requests.
+ *
+ * @internal
+ */
+#[Attribute(Attribute::TARGET_CLASS)]
+abstract class Controller extends Base implements Handler, \Countable
+{
+    public function __construct(
+        protected readonly BaseRequest $request,
+        private array &$options = ['debug' => false],
+    ) {}
+
+    abstract protected function name(): ?string;
+
+    // Counts the routes.
+    public function count(): int
+    {
+        if ($this->request->has("{")) {
+            return strlen('}');
+        }
+
+        return 0;
+    }
+
+    final public const int LIMIT = 10, OFFSET = 0;
+
+    public string $title = 'Home' {
+        get => strtoupper($this->title);
+    }
+
+    use Loggable { log as protected; }
+}
+"#;
+
+    let file = parse(code).unwrap();
+    let class = &file.classes[0];
+
+    assert_eq!(vec![Modifier::Abstract], class.modifiers);
+    assert_eq!(2, class.constants.len());
+    assert!(matches!(class.constants[1].value, Value::Integer(0)));
+    assert!(matches!(
+        &class.methods[1].return_type,
+        Some(DataType::Nullable(inner)) if matches!(**inner, DataType::String)
+    ));
+    assert!(class.methods[0].parameters[1].by_reference);
+
+    // Printing is not faithful to the source: members are reordered, laid
+    // out in the default style, and the comment on `count()` is dropped.
+    let expected = r#"<?php
+
+declare(strict_types=1);
+
+namespace App\Http;
+
+use App\Contract\{Handler, Request as BaseRequest};
+
+
+/**
+ * Handles requests.
+ *
+ * @internal
+ */
+#[Attribute(Attribute::TARGET_CLASS)]
+abstract class Controller extends Base implements Handler, \Countable
+{
+    use Loggable {
+        log as protected;
+    }
+
+    final public const int LIMIT = 10;
+
+    final public const int OFFSET = 0;
+
+    public string $title = 'Home' {
+        get {
+            return strtoupper($this->title);
+        }
+    }
+    public function __construct(
+        protected readonly BaseRequest $request,
+        private array &$options = ['debug' => false],
+    ) {}
+
+    abstract protected function name(): null|string;
+
+    public function count(): int {
+        if ($this->request->has("{")) {
+            return strlen('}');
+        }
+        
+        return 0;
+    }
+}
+"#;
+
+    let printed = file.to_string();
+    assert_eq!(expected, printed);
+    assert_eq!(printed, parse(&printed).unwrap().to_string());
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |code: &str| parse(code).unwrap_err().to_string();
+
+    assert_eq!(
+        "3:1: expected a declaration, found `echo`",
+        error("<?php\n\necho 'hello';\n")
+    );
+    assert_eq!(
+        "3:28: expected a variable, found `)`",
+        error("<?php\n\nfunction foo(int $a, string) {}\n")
+    );
+    assert_eq!(
+        "3:12: expected a name, found end of file",
+        error("<?php\n\nclass Foo {\n")
+    );
+    assert_eq!(
+        "3:15: braced namespaces are not supported",
+        error("<?php\n\nnamespace App {\n}\n")
+    );
+    assert_eq!(
+        "5:1: multiple namespaces are not supported",
+        error("<?php\n\nnamespace A; class X {}\n\nnamespace B; class Y {}\n")
+    );
+}
+
+#[test]
+fn test_parse_set_hook_parameter_attributes() {
+    let code = "<?php\n\nclass User\n{\n    public string $name {\n        set (#[\\SensitiveParameter] string $value) {\n            $this->name = $value;\n        }\n    }\n}\n";
+
+    assert_eq!(code, parse(code).unwrap().to_string());
+}
@@ -0,0 +1,173 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::attribute::AttributeGroup;
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::interface::Interface;
+use php_codegen::method::Method;
+use php_codegen::parameter::Parameter;
+use php_codegen::rename::Rename;
+
+#[test]
+fn test_namespace_relocation() {
+    let mut files = vec![
+        File::new()
+            .namespaced("App\\Generated\\Contract")
+            .interface(Interface::new("Entity")),
+        File::new()
+            .namespaced("App\\Generated\\Model")
+            .uses("App\\Generated\\Contract\\Entity")
+            .uses("App\\Other\\Entity as OtherEntity")
+            .class(
+                Class::new("User")
+                    .implements("Entity")
+                    .implements("OtherEntity")
+                    .attributes(AttributeGroup::new().add("\\App\\Generated\\Mapping", None))
+                    .method(
+                        Method::new("parent")
+                            .returns(DataType::Named("Group".into()))
+                            .body("return new \\App\\Generated\\Model\\Group();"),
+                    ),
+            ),
+    ];
+
+    Rename::new()
+        .namespace("App\\Generated", "Acme\\Sdk\\V2")
+        .apply(&mut files);
+
+    assert_eq!(
+        r#"<?php
+
+namespace Acme\Sdk\V2\Contract;
+
+interface Entity
+{
+}
+"#,
+        files[0].to_string()
+    );
+
+    assert_eq!(
+        r#"<?php
+
+namespace Acme\Sdk\V2\Model;
+
+use Acme\Sdk\V2\Contract\Entity;
+use App\Other\Entity as OtherEntity;
+
+
+#[\Acme\Sdk\V2\Mapping]
+class User implements Entity, OtherEntity
+{
+    function parent(): Group {
+        return new \Acme\Sdk\V2\Model\Group();
+    }
+}
+"#,
+        files[1].to_string()
+    );
+}
+
+#[test]
+fn test_class_rename_leaves_other_symbols_alone() {
+    let mut files = vec![
+        File::new().namespaced("App\\Model").class(
+            Class::new("User").method(
+                Method::new("copy")
+                    .parameter(Parameter::new("other").typed(DataType::Named("User".into())))
+                    .returns(DataType::Named("self".into())),
+            ),
+        ),
+        File::new()
+            .namespaced("App\\Service")
+            .uses("App\\Model\\User")
+            .class(
+                Class::new("Mailer").method(
+                    Method::new("send")
+                        .parameter(Parameter::new("to").typed(DataType::Named("User".into())))
+                        .parameter(
+                            Parameter::new("from").typed(DataType::Named("\\Vendor\\User".into())),
+                        ),
+                ),
+            ),
+        File::new()
+            .namespaced("Vendor")
+            .class(Class::new("Client").extends("User")),
+    ];
+
+    Rename::new()
+        .class("App\\Model\\User", "Customer")
+        .apply(&mut files);
+
+    assert_eq!(
+        r#"<?php
+
+namespace App\Model;
+
+class Customer
+{
+    function copy(
+        Customer $other,
+    ): self;
+}
+"#,
+        files[0].to_string()
+    );
+
+    assert_eq!(
+        r#"<?php
+
+namespace App\Service;
+
+use App\Model\Customer;
+
+
+class Mailer
+{
+    function send(
+        Customer $to,
+        \Vendor\User $from,
+    );
+}
+"#,
+        files[1].to_string()
+    );
+
+    assert_eq!(
+        "User",
+        files[2].classes[0].extends.as_ref().unwrap().to_string()
+    );
+}
+
+#[test]
+fn test_named_arguments_and_labels_are_not_renamed() {
+    let mut files = vec![File::new().namespaced("App\\Generated").class(
+        Class::new("User").method(
+            Method::new("copy")
+                .public()
+                .body("User:\n$user = new User(User: 1, name: User::NAME);\nreturn $user;"),
+        ),
+    )];
+
+    Rename::new()
+        .class("App\\Generated\\User", "Member")
+        .apply(&mut files);
+
+    assert_eq!(
+        r#"<?php
+
+namespace App\Generated;
+
+class Member
+{
+    public function copy() {
+        User:
+        $user = new Member(User: 1, name: Member::NAME);
+        return $user;
+    }
+}
+"#,
+        files[0].to_string()
+    );
+}
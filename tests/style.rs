@@ -0,0 +1,160 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::function::Function;
+use php_codegen::method::Method;
+use php_codegen::parameter::Parameter;
+use php_codegen::property::Property;
+use php_codegen::style::BracePlacement;
+use php_codegen::style::EmptyBody;
+use php_codegen::style::Style;
+
+fn file() -> File {
+    File::new()
+        .declare("strict_types", 1i64)
+        .function(Function::new("noop"))
+        .class(
+            Class::new("Counter")
+                .property(Property::new("count").private().typed(DataType::Integer))
+                .method(Method::new("reset").public().body(""))
+                .method(
+                    Method::new("map")
+                        .public()
+                        .parameter(Parameter::new("items").typed(DataType::Array))
+                        .returns(DataType::Array)
+                        .body("return array_map(fn($item) => $item + 1, $items);"),
+                )
+                .method(
+                    Method::new("handler")
+                        .public()
+                        .body("return function() {\n    return $this->count;\n};"),
+                ),
+        )
+}
+
+#[test]
+fn test_per_cs() {
+    let expected = r#"<?php
+
+declare(strict_types=1);
+
+function noop() {}
+
+class Counter
+{
+    private int $count;
+
+    public function reset() {}
+
+    public function map(array $items): array
+    {
+        return array_map(fn($item) => $item + 1, $items);
+    }
+
+    public function handler()
+    {
+        return function () {
+            return $this->count;
+        };
+    }
+}
+"#;
+
+    assert_eq!(expected, file().style(Style::per_cs()).to_string());
+}
+
+#[test]
+fn test_psr12() {
+    let expected = r#"<?php
+
+declare(strict_types=1);
+
+function noop()
+{
+}
+
+class Counter
+{
+    private int $count;
+
+    public function reset()
+    {
+    }
+
+    public function map(array $items): array
+    {
+        return array_map(fn ($item) => $item + 1, $items);
+    }
+
+    public function handler()
+    {
+        return function () {
+            return $this->count;
+        };
+    }
+}
+"#;
+
+    assert_eq!(expected, file().style(Style::psr12()).to_string());
+}
+
+#[test]
+fn test_custom_style() {
+    let style = Style::new()
+        .class_braces(BracePlacement::SameLine)
+        .empty_body(EmptyBody::Collapsed)
+        .declare_spacing(true)
+        .space_after_fn(true);
+
+    let expected = r#"<?php
+
+declare(strict_types = 1);
+
+function noop() {}
+
+class Counter {
+    private int $count;
+    public function reset() {}
+
+    public function map(
+        array $items,
+    ): array {
+        return array_map(fn ($item) => $item + 1, $items);
+    }
+
+    public function handler() {
+        return function() {
+            return $this->count;
+        };
+    }
+}
+"#;
+
+    assert_eq!(expected, file().style(style).to_string());
+}
+
+#[test]
+fn test_closure_spacing_skips_method_calls() {
+    let function = Function::new("call").body(
+        "$this->fn($y);\n$this?->function ($y);\nStatic::fn($y);\n$f = fn($y) => function() {};",
+    );
+
+    assert_eq!(
+        r#"<?php
+
+function call()
+{
+    $this->fn($y);
+    $this?->function ($y);
+    Static::fn($y);
+    $f = fn ($y) => function () {};
+}
+"#,
+        File::new()
+            .function(function)
+            .style(Style::psr12())
+            .to_string()
+    );
+}
@@ -0,0 +1,135 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::attribute::AttributeGroup;
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::file::File;
+use php_codegen::interface::Interface;
+use php_codegen::method::Method;
+use php_codegen::name::Name;
+use php_codegen::name::SymbolKind;
+use php_codegen::parameter::Parameter;
+use php_codegen::r#trait::Trait;
+use php_codegen::symbols::SymbolTable;
+
+fn files() -> Vec<File> {
+    vec![
+        File::new()
+            .namespaced("App\\Contract")
+            .interface(Interface::new("Repository")),
+        File::new()
+            .namespaced("App\\Repository")
+            .uses("App\\Contract\\Repository")
+            .uses("Doctrine\\ORM\\EntityManager")
+            .class(
+                Class::new("UserRepository")
+                    .extends("BaseRepositry")
+                    .implements("Repository")
+                    .implements("\\Countable")
+                    .using("Logging")
+                    .attributes(AttributeGroup::new().add("\\Attribute", None))
+                    .method(
+                        Method::new("find")
+                            .parameter(
+                                Parameter::new("manager")
+                                    .typed(DataType::Named("EntityManager".into())),
+                            )
+                            .parameter(Parameter::new("id").typed(DataType::Integer))
+                            .returns(DataType::Nullable(Box::new(DataType::Named("Usr".into())))),
+                    ),
+            )
+            .r#trait(Trait::new("Logging")),
+    ]
+}
+
+#[test]
+fn test_undefined_references() {
+    let files = files();
+    let table =
+        SymbolTable::from_files(&files).external("Doctrine\\ORM\\EntityManager", SymbolKind::Class);
+
+    let undefined = table.undefined(&files);
+
+    assert_eq!(
+        vec![
+            (
+                1,
+                "\\App\\Repository\\BaseRepositry".to_string(),
+                "File > Class UserRepository".to_string()
+            ),
+            (
+                1,
+                "\\App\\Repository\\Usr".to_string(),
+                "File > Class UserRepository > method find".to_string()
+            ),
+        ],
+        undefined
+            .iter()
+            .map(|reference| (
+                reference.file,
+                reference.name.to_string(),
+                reference.location.to_string()
+            ))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_external_symbols_are_required() {
+    let files = files();
+    let undefined = SymbolTable::from_files(&files).undefined(&files);
+
+    assert_eq!(3, undefined.len());
+    assert_eq!(
+        Name::new("\\Doctrine\\ORM\\EntityManager"),
+        undefined[1].name
+    );
+    assert_eq!(
+        "File > Class UserRepository > method find > param $manager",
+        undefined[1].location.to_string()
+    );
+}
+
+#[test]
+fn test_lookup_is_case_insensitive_for_classes() {
+    let table = SymbolTable::new()
+        .external("\\Vendor\\Client", SymbolKind::Class)
+        .external("Vendor\\VERSION", SymbolKind::Constant);
+
+    assert!(table.contains(&Name::new("\\vendor\\CLIENT"), SymbolKind::Class));
+    assert!(table.contains(&Name::new("\\stdclass"), SymbolKind::Class));
+    assert!(table.contains(&Name::new("\\Vendor\\VERSION"), SymbolKind::Constant));
+    assert!(!table.contains(&Name::new("\\Vendor\\version"), SymbolKind::Constant));
+    assert!(!table.contains(&Name::new("\\Vendor\\Client"), SymbolKind::Function));
+}
+
+#[test]
+fn test_global_and_extension_classes_are_builtin() {
+    let files = vec![File::new().namespaced("App").class(
+        Class::new("Inspector").implements("\\Reflector").method(
+            Method::new("inspect")
+                .parameter(
+                    Parameter::new("type").typed(DataType::Named("\\ReflectionUnionType".into())),
+                )
+                .parameter(
+                    Parameter::new("document").typed(DataType::Named("\\DOMDocument".into())),
+                )
+                .parameter(
+                    Parameter::new("engine")
+                        .typed(DataType::Named("\\Random\\Engine\\Mt19937".into())),
+                )
+                .parameter(
+                    Parameter::new("client").typed(DataType::Named("\\Vendor\\Client".into())),
+                ),
+        ),
+    )];
+
+    assert_eq!(
+        vec![Name::new("\\Vendor\\Client")],
+        SymbolTable::from_files(&files)
+            .undefined(&files)
+            .into_iter()
+            .map(|reference| reference.name)
+            .collect::<Vec<Name>>()
+    );
+}
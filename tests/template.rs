@@ -0,0 +1,141 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::body::Body;
+use php_codegen::data_type::DataType;
+use php_codegen::literal::Value;
+use php_codegen::method::Method;
+use php_codegen::template::Template;
+use php_codegen::template::TemplateError;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+#[test]
+fn test_template_placeholders() {
+    let body = Template::new(
+        r#"
+if (!$value instanceof {type}) {
+    {guard}
+}
+
+return new {class}({args});
+"#,
+    )
+    .typed("type", DataType::Named("Countable".into()))
+    .body(
+        "guard",
+        vec![
+            "throw new \\InvalidArgumentException(",
+            "    'Not countable.'",
+            ");",
+        ],
+    )
+    .name("class", "\\App\\Counter")
+    .value(
+        "args",
+        Value::Literal("count($value), \"a $b\"".to_string()),
+    )
+    .render()
+    .unwrap();
+
+    let method = Method::new("count").public().body(body);
+
+    assert_eq!(
+        r#"    public function count() {
+        if (!$value instanceof Countable) {
+            throw new \InvalidArgumentException(
+                'Not countable.'
+            );
+        }
+
+        return new \App\Counter(count($value), "a $b");
+    }
+"#,
+        method.generate(Indentation::Spaces(4), 1)
+    );
+}
+
+#[test]
+fn test_template_indentation_and_escaped_braces() {
+    let body = Body::try_from(
+        Template::new("$map = [{{'key' => {value}}];\n\tforeach ($map as $v) {}")
+            .value("value", "it's"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        "\t$map = [{'key' => \"it's\"}];\n\t\tforeach ($map as $v) {}",
+        body.code(Indentation::Tabs(1), 1).unwrap()
+    );
+}
+
+#[test]
+fn test_template_errors() {
+    assert_eq!(
+        TemplateError::MissingArgument("class".to_string()),
+        Template::new("return new {class}();").render().unwrap_err()
+    );
+
+    assert_eq!(
+        TemplateError::UnusedArgument("extra".to_string()),
+        Template::new("return {value};")
+            .value("value", 1)
+            .value("extra", 2)
+            .render()
+            .unwrap_err()
+    );
+
+    assert_eq!(
+        TemplateError::DuplicateArgument("value".to_string()),
+        Template::new("return {value};")
+            .value("value", 1)
+            .value("value", 2)
+            .render()
+            .unwrap_err()
+    );
+
+    let error = Template::new("return new {class}();")
+        .name("class", "my-class")
+        .render()
+        .unwrap_err();
+    assert_eq!(
+        "argument `class` is not a valid PHP name: `my-class`",
+        error.to_string()
+    );
+
+    assert_eq!(
+        TemplateError::MisplacedBody {
+            placeholder: "inner".to_string(),
+            line: 2,
+        },
+        Template::new("if (true) {\n    {inner} // inline\n}")
+            .body("inner", "return;")
+            .render()
+            .unwrap_err()
+    );
+}
+
+#[test]
+fn test_template_escapes_string_values() {
+    let body = Template::new("return {value};")
+        .value("value", "a $b \"c\" \\d")
+        .render()
+        .unwrap();
+
+    assert_eq!(
+        r#"return "a \$b \"c\" \\d";"#,
+        body.code(Indentation::default(), 0).unwrap()
+    );
+
+    let body = Template::new("return {values};")
+        .value(
+            "values",
+            Value::HashMap(vec![(Value::from("$key"), Value::from("line\nbreak"))]),
+        )
+        .render()
+        .unwrap();
+
+    assert_eq!(
+        r#"return ["\$key" => "line\nbreak"];"#,
+        body.code(Indentation::default(), 0).unwrap()
+    );
+}
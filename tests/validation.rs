@@ -0,0 +1,118 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::class::Class;
+use php_codegen::data_type::DataType;
+use php_codegen::enum_case::EnumCase;
+use php_codegen::file::File;
+use php_codegen::interface::Interface;
+use php_codegen::method::Method;
+use php_codegen::modifiers::Modifier;
+use php_codegen::parameter::Parameter;
+use php_codegen::property::Property;
+use php_codegen::r#enum::Enum;
+use php_codegen::validation::Severity;
+use php_codegen::validation::Validator;
+use php_codegen::Generator;
+use php_codegen::Indentation;
+
+fn summary<T: Validator>(node: &T) -> Vec<String> {
+    node.validate()
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect()
+}
+
+#[test]
+fn test_file_diagnostics_have_paths() {
+    let mut interface = Interface::new("Baz");
+    interface
+        .methods
+        .push(Method::new("qux").protected().body("return;"));
+
+    let file = File::new()
+        .class(
+            Class::new("Foo")
+                .method(
+                    Method::new("bar")
+                        .modifier(Modifier::Abstract)
+                        .parameter(Parameter::new("x").typed(DataType::Void))
+                        .body("return 1;"),
+                )
+                .property(Property::new("id").modifier(Modifier::Readonly)),
+        )
+        .interface(interface)
+        .r#enum(
+            Enum::new("Status")
+                .string_backed()
+                .case(EnumCase::new("Active").valued("active"))
+                .case("Inactive"),
+        );
+
+    assert_eq!(
+        vec![
+            "error[readonly-property-without-type] File > Class Foo > property $id: readonly property `$id` must have a type".to_string(),
+            "error[invalid-parameter-type] File > Class Foo > method bar > param $x: `void` and `never` can only be used as return types".to_string(),
+            "error[abstract-method-body] File > Class Foo > method bar: abstract method `bar` cannot have a body".to_string(),
+            "error[abstract-method-in-concrete-class] File > Class Foo > method bar: class `Foo` must be abstract to declare abstract method `bar`".to_string(),
+            "error[enum-case-missing-value] File > Enum Status > case Inactive: case `Inactive` of backed enum `Status` must have a value".to_string(),
+            "error[invalid-modifiers] File > Interface Baz > method qux: `protected` modifier is not allowed on interface methods".to_string(),
+            "error[interface-method-body] File > Interface Baz > method qux: interface method `qux` cannot have a body".to_string(),
+        ],
+        summary(&file)
+    );
+}
+
+#[test]
+fn test_parameter_rules() {
+    let method = Method::new("handle")
+        .parameter(Parameter::new("a").default(1))
+        .parameter(Parameter::new("b"))
+        .parameter(Parameter::new("rest").variadic())
+        .parameter(Parameter::new("a").private())
+        .parameter(Parameter::new("this"))
+        .returns(DataType::Nullable(Box::new(DataType::Mixed)))
+        .body("");
+
+    let diagnostics = method.validate();
+
+    assert_eq!(
+        vec![
+            ("required-parameter-after-optional", Severity::Warning),
+            ("variadic-parameter-not-last", Severity::Error),
+            ("duplicate-parameter", Severity::Error),
+            ("required-parameter-after-optional", Severity::Warning),
+            ("promoted-parameter-outside-constructor", Severity::Error),
+            ("reserved-identifier", Severity::Error),
+            ("required-parameter-after-optional", Severity::Warning),
+            ("standalone-type", Severity::Error),
+        ],
+        diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.severity))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "method handle > param $this",
+        diagnostics[5].location.to_string()
+    );
+}
+
+#[test]
+fn test_try_generate() {
+    let valid = Class::new("Foo").method(Method::new("bar").public().body("return 1;"));
+    assert_eq!(
+        Ok(valid.generate(Indentation::default(), 0)),
+        valid.try_generate(Indentation::default(), 0)
+    );
+
+    let invalid = Class::new("List").method(Method::new("bar"));
+    let error = invalid.try_generate(Indentation::default(), 0).unwrap_err();
+    assert_eq!(
+        "error[reserved-identifier] Class List: `List` is reserved and cannot be used as a class name\nerror[method-without-body] Class List > method bar: non-abstract method `bar` must have a body",
+        error.to_string()
+    );
+
+    let warned = Class::new("Resource");
+    assert_eq!(1, warned.validate().len());
+    assert!(warned.try_generate(Indentation::default(), 0).is_ok());
+}
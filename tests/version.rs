@@ -0,0 +1,213 @@
+use pretty_assertions::assert_eq;
+
+use php_codegen::attribute::AttributeGroup;
+use php_codegen::body::Body;
+use php_codegen::class::Class;
+use php_codegen::constant::ClassConstant;
+use php_codegen::data_type::DataType;
+use php_codegen::enum_case::EnumCase;
+use php_codegen::file::File;
+use php_codegen::function::Function;
+use php_codegen::method::Method;
+use php_codegen::modifiers::Modifier;
+use php_codegen::name::Name;
+use php_codegen::parameter::Parameter;
+use php_codegen::property::Property;
+use php_codegen::property::PropertyHook;
+use php_codegen::r#enum::Enum;
+use php_codegen::style::Style;
+use php_codegen::version;
+use php_codegen::version::PhpVersion;
+
+#[test]
+fn test_minimum_version_of_simple_code() {
+    let file = File::new().function(
+        Function::new("main")
+            .returns(DataType::Void)
+            .body("exit(0);"),
+    );
+
+    assert_eq!(PhpVersion::PHP_7_1, version::minimum_version(&file));
+    assert_eq!(
+        vec!["PHP 7.1 File > Function main: void type".to_string()],
+        version::requirements(&file)
+            .iter()
+            .map(|requirement| requirement.to_string())
+            .collect::<Vec<String>>()
+    );
+
+    assert_eq!(PhpVersion::PHP_7_0, version::minimum_version(&File::new()));
+    assert!(PhpVersion::new(8, 10) > PhpVersion::PHP_8_4);
+}
+
+#[test]
+fn test_requirements_list_node_and_feature() {
+    let file = File::new()
+        .class(
+            Class::new("Money")
+                .modifier(Modifier::Readonly)
+                .constant(
+                    ClassConstant::new("CURRENCY")
+                        .typed(DataType::String)
+                        .valued("EUR"),
+                )
+                .property(
+                    Property::new("amount")
+                        .typed(DataType::Integer)
+                        .hook(PropertyHook::Get(false, Body::from("return 1;"))),
+                )
+                .method(
+                    Method::new("fail")
+                        .attributes(AttributeGroup::new().add("\\Override", None))
+                        .returns(DataType::Never),
+                )
+                .method(
+                    Method::new("filter")
+                        .parameter(Parameter::new("filter").typed(DataType::Union(vec![
+                            DataType::Intersection(vec![
+                                DataType::Named(Name::new("Countable")),
+                                DataType::Named(Name::new("Traversable")),
+                            ]),
+                            DataType::Null,
+                        ])))
+                        .body(""),
+                ),
+        )
+        .r#enum(Enum::new("Suit").case(EnumCase::new("Hearts")));
+
+    assert_eq!(
+        vec![
+            "PHP 8.2 File > Class Money: readonly classes",
+            "PHP 8.3 File > Class Money > constant CURRENCY: typed class constants",
+            "PHP 7.4 File > Class Money > property $amount: typed properties",
+            "PHP 8.4 File > Class Money > property $amount: property hooks",
+            "PHP 8.0 File > Class Money > method fail: attributes",
+            "PHP 8.1 File > Class Money > method fail: never type",
+            "PHP 8.0 File > Class Money > method filter: trailing commas in parameter lists",
+            "PHP 8.0 File > Class Money > method filter > param $filter: union types",
+            "PHP 8.2 File > Class Money > method filter > param $filter: DNF types",
+            "PHP 8.1 File > Class Money > method filter > param $filter: intersection types",
+            "PHP 8.1 File > Enum Suit: enums",
+        ],
+        version::requirements(&file)
+            .iter()
+            .map(|requirement| requirement.to_string())
+            .collect::<Vec<String>>()
+    );
+
+    assert_eq!(PhpVersion::PHP_8_4, version::minimum_version(&file));
+}
+
+#[test]
+fn test_promoted_and_nullable_parameters() {
+    let file = File::new().class(
+        Class::new("User").method(
+            Method::new("__construct")
+                .parameter(
+                    Parameter::new("name")
+                        .private()
+                        .modifier(Modifier::Readonly)
+                        .typed(DataType::String),
+                )
+                .parameter(
+                    Parameter::new("email")
+                        .typed(DataType::Nullable(Box::new(DataType::String)))
+                        .default(()),
+                )
+                .body(""),
+        ),
+    );
+
+    let requirements = version::requirements(&file);
+
+    assert_eq!(
+        vec![
+            (PhpVersion::PHP_8_0, "trailing commas in parameter lists"),
+            (PhpVersion::PHP_8_0, "constructor property promotion"),
+            (PhpVersion::PHP_8_1, "readonly properties"),
+            (PhpVersion::PHP_8_0, "union types"),
+        ],
+        requirements
+            .iter()
+            .map(|requirement| (requirement.version, requirement.feature))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(PhpVersion::PHP_8_1, version::minimum_version(&file));
+}
+
+#[test]
+fn test_requirements_follow_the_printed_signature() {
+    let function = Function::new("f")
+        .parameter(Parameter::new("x").typed(DataType::Nullable(Box::new(DataType::Integer))))
+        .body("return $x;");
+
+    let features = |file: &File| {
+        version::requirements(file)
+            .iter()
+            .map(|requirement| (requirement.version, requirement.feature))
+            .collect::<Vec<_>>()
+    };
+
+    let file = File::new().function(function.clone());
+    assert_eq!(
+        vec![
+            (PhpVersion::PHP_8_0, "trailing commas in parameter lists"),
+            (PhpVersion::PHP_8_0, "union types"),
+        ],
+        features(&file)
+    );
+
+    let file = file.style(Style::per_cs().short_nullable(true));
+    assert_eq!(
+        vec![(PhpVersion::PHP_7_1, "nullable types")],
+        features(&file)
+    );
+    assert_eq!(PhpVersion::PHP_7_1, version::minimum_version(&file));
+
+    let file = File::new()
+        .function(function.parameter(Parameter::new("y").typed(DataType::Integer)))
+        .style(Style::per_cs().short_nullable(true).line_width(20));
+    assert_eq!(
+        vec![
+            (PhpVersion::PHP_8_0, "trailing commas in parameter lists"),
+            (PhpVersion::PHP_7_1, "nullable types"),
+        ],
+        features(&file)
+    );
+}
+
+#[test]
+fn test_final_and_abstract_properties() {
+    let file = File::new().class(
+        Class::new("User")
+            .modifier(Modifier::Abstract)
+            .property(
+                Property::new("name")
+                    .public()
+                    .modifier(Modifier::Final)
+                    .typed(DataType::String),
+            )
+            .property(
+                Property::new("email")
+                    .public()
+                    .modifier(Modifier::Abstract)
+                    .typed(DataType::String)
+                    .hook(PropertyHook::Get(false, Body::from("return '';"))),
+            ),
+    );
+
+    assert_eq!(
+        vec![
+            "PHP 7.4 File > Class User > property $name: typed properties",
+            "PHP 8.4 File > Class User > property $name: final properties",
+            "PHP 7.4 File > Class User > property $email: typed properties",
+            "PHP 8.4 File > Class User > property $email: abstract properties",
+            "PHP 8.4 File > Class User > property $email: property hooks",
+        ],
+        version::requirements(&file)
+            .iter()
+            .map(|requirement| requirement.to_string())
+            .collect::<Vec<String>>()
+    );
+    assert_eq!(PhpVersion::PHP_8_4, version::minimum_version(&file));
+}